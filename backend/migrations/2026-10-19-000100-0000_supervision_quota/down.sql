@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+
+drop table SupervisionQuota;
+
+alter table Teacher
+   drop column teacher_title;
@@ -0,0 +1,17 @@
+/*==============================================================*/
+/* Column: Teacher.teacher_title                                */
+/*==============================================================*/
+alter table Teacher
+   add column teacher_title INT2 not null default 4
+      constraint CKC_TEACHER_TITLE_TEACHER check (teacher_title in (0,1,2,3,4));
+
+/*==============================================================*/
+/* Table: SupervisionQuota                                      */
+/*==============================================================*/
+create table SupervisionQuota (
+   teacher_title        INT2                 not null
+      constraint CKC_TEACHER_TITLE_SUPERVIS check (teacher_title in (0,1,2,3,4)),
+   quota_max_students   INT4                 not null
+      constraint CKC_QUOTA_MAX_STUDENT_SUPERVIS check (quota_max_students >= 0),
+   constraint PK_SUPERVISIONQUOTA primary key (teacher_title)
+);
@@ -0,0 +1,31 @@
+/*==============================================================*/
+/* Table: Tag                                                   */
+/*==============================================================*/
+create table Tag (
+   tag_id               SERIAL               not null,
+   tag_name             VARCHAR(32)          not null,
+   constraint PK_TAG primary key (tag_id),
+   constraint AK_TAG_NAME_TAG unique (tag_name)
+);
+
+/*==============================================================*/
+/* Table: TopicTag                                              */
+/*==============================================================*/
+create table TopicTag (
+   topic_id             INT4                 not null,
+   tag_id               INT4                 not null,
+   constraint PK_TOPICTAG primary key (topic_id, tag_id),
+   constraint FK_TOPICTAG_TAGGING_TOPIC foreign key (topic_id)
+      references Topic (topic_id)
+      on delete restrict on update restrict,
+   constraint FK_TOPICTAG_TAGGING2_TAG foreign key (tag_id)
+      references Tag (tag_id)
+      on delete restrict on update restrict
+);
+
+/*==============================================================*/
+/* Index: Tagging2_FK                                           */
+/*==============================================================*/
+create  index Tagging2_FK on TopicTag (
+tag_id
+);
@@ -0,0 +1,22 @@
+/*==============================================================*/
+/* Table: TopicCoSupervisor                                     */
+/*==============================================================*/
+create table TopicCoSupervisor (
+   topic_id             INT4                 not null,
+   teacher_user_name    VARCHAR(16)          not null,
+   cosup_can_approve    BOOL                 not null default false,
+   constraint PK_TOPICCOSUPERVISOR primary key (topic_id, teacher_user_name),
+   constraint FK_TOPICCOS_COTUTORIN_TOPIC foreign key (topic_id)
+      references Topic (topic_id)
+      on delete restrict on update restrict,
+   constraint FK_TOPICCOS_COTUTORIN_TEACHER foreign key (teacher_user_name)
+      references Teacher (user_name)
+      on delete restrict on update restrict
+);
+
+/*==============================================================*/
+/* Index: CoTutoring2_FK                                        */
+/*==============================================================*/
+create  index CoTutoring2_FK on TopicCoSupervisor (
+teacher_user_name
+);
@@ -0,0 +1,7 @@
+-- This file should undo anything in `up.sql`
+
+alter table Topic
+   drop column topic_review_comment;
+
+alter table Topic
+   drop column topic_version;
@@ -0,0 +1,12 @@
+/*==============================================================*/
+/* Column: Topic.topic_version                                  */
+/*==============================================================*/
+alter table Topic
+   add column topic_version INT4 not null default 1
+      constraint CKC_TOPIC_VERSION_TOPIC check (topic_version >= 1);
+
+/*==============================================================*/
+/* Column: Topic.topic_review_comment                           */
+/*==============================================================*/
+alter table Topic
+   add column topic_review_comment TEXT null;
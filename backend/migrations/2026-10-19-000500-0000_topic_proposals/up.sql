@@ -0,0 +1,49 @@
+/*==============================================================*/
+/* Table: TopicProposal                                         */
+/*==============================================================*/
+create table TopicProposal (
+   prop_id                SERIAL             not null,
+   student_user_name      VARCHAR(16)        not null,
+   teacher_user_name      VARCHAR(16)        not null,
+   topic_id               INT4               null,
+   prop_topic_name        VARCHAR(128)       not null,
+   prop_topic_description TEXT               not null,
+   prop_topic_type        INT2               not null
+      constraint CKC_PROP_TOPIC_TYPE_TOPICPRO check (prop_topic_type in (0,1,2,3,4)),
+   prop_status            INT2               not null default 0
+      constraint CKC_PROP_STATUS_TOPICPRO check (prop_status in (0,1,2)),
+   prop_time              TIMESTAMP WITH TIME ZONE not null,
+   prop_decided_time      TIMESTAMP WITH TIME ZONE null,
+   prop_decline_reason    TEXT               null,
+   constraint PK_TOPICPROPOSAL primary key (prop_id),
+   constraint FK_TOPICPRO_PROPOSING_STUDENT foreign key (student_user_name)
+      references Student (user_name)
+      on delete restrict on update restrict,
+   constraint FK_TOPICPRO_ADDRESSIN_TEACHER foreign key (teacher_user_name)
+      references Teacher (user_name)
+      on delete restrict on update restrict,
+   constraint FK_TOPICPRO_REALIZING_TOPIC foreign key (topic_id)
+      references Topic (topic_id)
+      on delete restrict on update restrict
+);
+
+/*==============================================================*/
+/* Index: Proposing_FK                                          */
+/*==============================================================*/
+create  index Proposing_FK on TopicProposal (
+student_user_name
+);
+
+/*==============================================================*/
+/* Index: Addressing_FK                                         */
+/*==============================================================*/
+create  index Addressing_FK on TopicProposal (
+teacher_user_name
+);
+
+/*==============================================================*/
+/* Index: AK_PENDING_PROP_TOPICPRO                              */
+/*==============================================================*/
+create unique index AK_PENDING_PROP_TOPICPRO on TopicProposal (
+student_user_name
+) where prop_status = 0;
@@ -0,0 +1,7 @@
+-- This file should undo anything in `up.sql`
+
+drop table ApplicantRank;
+
+drop table TopicPreference;
+
+drop table MatchingRound;
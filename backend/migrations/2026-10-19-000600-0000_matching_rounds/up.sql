@@ -0,0 +1,66 @@
+/*==============================================================*/
+/* Table: MatchingRound                                         */
+/*==============================================================*/
+create table MatchingRound (
+   match_round_id         SERIAL             not null,
+   match_round_name       VARCHAR(64)        not null,
+   match_round_max_prefs  INT2               not null
+      constraint CKC_MATCH_ROUND_MAX_P_MATCHING check (match_round_max_prefs >= 1),
+   match_round_open_time  TIMESTAMP WITH TIME ZONE not null,
+   match_round_close_time TIMESTAMP WITH TIME ZONE not null,
+   match_round_commit_time TIMESTAMP WITH TIME ZONE null,
+   constraint PK_MATCHINGROUND primary key (match_round_id),
+   constraint CKC_MATCH_ROUND_WINDOW_MATCHING check (match_round_open_time < match_round_close_time)
+);
+
+/*==============================================================*/
+/* Table: TopicPreference                                       */
+/*==============================================================*/
+create table TopicPreference (
+   match_round_id         INT4               not null,
+   student_user_name      VARCHAR(16)        not null,
+   topic_id               INT4               not null,
+   pref_rank              INT2               not null
+      constraint CKC_PREF_RANK_TOPICPRE check (pref_rank >= 1),
+   constraint PK_TOPICPREFERENCE primary key (match_round_id, student_user_name, topic_id),
+   constraint AK_PREF_RANK_TOPICPRE unique (match_round_id, student_user_name, pref_rank),
+   constraint FK_TOPICPRE_PREFERRIN_MATCHING foreign key (match_round_id)
+      references MatchingRound (match_round_id)
+      on delete restrict on update restrict,
+   constraint FK_TOPICPRE_PREFERRIN_STUDENT foreign key (student_user_name)
+      references Student (user_name)
+      on delete restrict on update restrict,
+   constraint FK_TOPICPRE_PREFERRED_TOPIC foreign key (topic_id)
+      references Topic (topic_id)
+      on delete restrict on update restrict
+);
+
+/*==============================================================*/
+/* Index: Preferred_FK                                          */
+/*==============================================================*/
+create  index Preferred_FK on TopicPreference (
+match_round_id,
+topic_id
+);
+
+/*==============================================================*/
+/* Table: ApplicantRank                                         */
+/*==============================================================*/
+create table ApplicantRank (
+   match_round_id         INT4               not null,
+   topic_id               INT4               not null,
+   student_user_name      VARCHAR(16)        not null,
+   appl_rank              INT4               not null
+      constraint CKC_APPL_RANK_APPLICAN check (appl_rank >= 1),
+   constraint PK_APPLICANTRANK primary key (match_round_id, topic_id, student_user_name),
+   constraint AK_APPL_RANK_APPLICAN unique (match_round_id, topic_id, appl_rank),
+   constraint FK_APPLICAN_RANKINGIN_MATCHING foreign key (match_round_id)
+      references MatchingRound (match_round_id)
+      on delete restrict on update restrict,
+   constraint FK_APPLICAN_RANKINGFO_TOPIC foreign key (topic_id)
+      references Topic (topic_id)
+      on delete restrict on update restrict,
+   constraint FK_APPLICAN_RANKED_STUDENT foreign key (student_user_name)
+      references Student (user_name)
+      on delete restrict on update restrict
+);
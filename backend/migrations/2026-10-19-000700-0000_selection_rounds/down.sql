@@ -0,0 +1,8 @@
+-- This file should undo anything in `up.sql`
+
+alter table AssignmentRequest
+   drop column sel_round_id;
+
+drop table SelectionRoundMajor;
+
+drop table SelectionRound;
@@ -0,0 +1,54 @@
+/*==============================================================*/
+/* Table: SelectionRound                                        */
+/*==============================================================*/
+create table SelectionRound (
+   sel_round_id           SERIAL             not null,
+   sel_round_prev_id      INT4               null,
+   sel_round_name         VARCHAR(64)        not null,
+   sel_round_open_time    TIMESTAMP WITH TIME ZONE not null,
+   sel_round_close_time   TIMESTAMP WITH TIME ZONE not null,
+   sel_round_max_requests INT2               not null
+      constraint CKC_SEL_ROUND_MAX_REQ_SELECTIO check (sel_round_max_requests >= 1),
+   constraint PK_SELECTIONROUND primary key (sel_round_id),
+   constraint CKC_SEL_ROUND_WINDOW_SELECTIO check (sel_round_open_time < sel_round_close_time),
+   constraint FK_SELECTIO_CARRYING_SELECTIO foreign key (sel_round_prev_id)
+      references SelectionRound (sel_round_id)
+      on delete restrict on update restrict
+);
+
+/*==============================================================*/
+/* Table: SelectionRoundMajor                                   */
+/*==============================================================*/
+create table SelectionRoundMajor (
+   sel_round_id           INT4               not null,
+   major_id               INT4               not null,
+   constraint PK_SELECTIONROUNDMAJOR primary key (sel_round_id, major_id),
+   constraint FK_SELECTIO_ELIGIBILI_SELECTIO foreign key (sel_round_id)
+      references SelectionRound (sel_round_id)
+      on delete restrict on update restrict,
+   constraint FK_SELECTIO_ELIGIBILI_MAJOR foreign key (major_id)
+      references Major (major_id)
+      on delete restrict on update restrict
+);
+
+/*==============================================================*/
+/* Index: Eligibility2_FK                                       */
+/*==============================================================*/
+create  index Eligibility2_FK on SelectionRoundMajor (
+major_id
+);
+
+/*==============================================================*/
+/* Column: AssignmentRequest.sel_round_id                       */
+/*==============================================================*/
+alter table AssignmentRequest
+   add column sel_round_id INT4 null
+      constraint FK_ASSIGNME_REQUESTIN_SELECTIO references SelectionRound (sel_round_id)
+         on delete restrict on update restrict;
+
+/*==============================================================*/
+/* Index: RequestingIn_FK                                       */
+/*==============================================================*/
+create  index RequestingIn_FK on AssignmentRequest (
+sel_round_id
+);
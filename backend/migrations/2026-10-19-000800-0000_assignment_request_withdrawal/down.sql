@@ -0,0 +1,18 @@
+-- This file should undo anything in `up.sql`
+
+drop index AK_PENDING_ASSN_REQ_ASSIGNME;
+
+delete from AssignmentRequest
+   where assn_req_status <> 0;
+
+alter table AssignmentRequest
+   drop constraint PK_ASSIGNMENTREQUEST,
+   drop column assn_req_decided_time,
+   drop column assn_req_status,
+   drop column assn_req_id,
+   add constraint PK_ASSIGNMENTREQUEST primary key (student_user_name, topic_id);
+
+create unique index AssignmentRequest_PK on AssignmentRequest (
+student_user_name,
+topic_id
+);
@@ -0,0 +1,23 @@
+/*==============================================================*/
+/* Table: AssignmentRequest                                     */
+/*==============================================================*/
+alter table AssignmentRequest
+   drop constraint PK_ASSIGNMENTREQUEST;
+
+drop index AssignmentRequest_PK;
+
+alter table AssignmentRequest
+   add column assn_req_id SERIAL not null,
+   add column assn_req_status INT2 not null default 0
+      constraint CKC_ASSN_REQ_STATUS_ASSIGNME check (assn_req_status in (0,3)),
+   add column assn_req_decided_time TIMESTAMP WITH TIME ZONE null,
+   add constraint PK_ASSIGNMENTREQUEST primary key (assn_req_id);
+
+/*==============================================================*/
+/* Index: AK_PENDING_ASSN_REQ_ASSIGNME                          */
+/*==============================================================*/
+create unique index AK_PENDING_ASSN_REQ_ASSIGNME on AssignmentRequest (
+student_user_name,
+topic_id
+)
+where assn_req_status = 0;
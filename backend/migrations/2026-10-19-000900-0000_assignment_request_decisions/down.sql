@@ -0,0 +1,13 @@
+-- This file should undo anything in `up.sql`
+
+drop index Deciding_FK;
+
+delete from AssignmentRequest
+   where assn_req_status in (1,2);
+
+alter table AssignmentRequest
+   drop constraint FK_ASSIGNME_DECIDING_TEACHER,
+   drop column assn_req_reason,
+   drop column assn_req_decided_by,
+   drop constraint CKC_ASSN_REQ_STATUS_ASSIGNME,
+   add constraint CKC_ASSN_REQ_STATUS_ASSIGNME check (assn_req_status in (0,3));
@@ -0,0 +1,18 @@
+/*==============================================================*/
+/* Table: AssignmentRequest                                     */
+/*==============================================================*/
+alter table AssignmentRequest
+   drop constraint CKC_ASSN_REQ_STATUS_ASSIGNME,
+   add constraint CKC_ASSN_REQ_STATUS_ASSIGNME check (assn_req_status in (0,1,2,3)),
+   add column assn_req_decided_by VARCHAR(16) null,
+   add column assn_req_reason TEXT null,
+   add constraint FK_ASSIGNME_DECIDING_TEACHER foreign key (assn_req_decided_by)
+      references Teacher (user_name)
+      on delete restrict on update restrict;
+
+/*==============================================================*/
+/* Index: Deciding_FK                                           */
+/*==============================================================*/
+create  index Deciding_FK on AssignmentRequest (
+assn_req_decided_by
+);
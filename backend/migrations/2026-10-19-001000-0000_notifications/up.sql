@@ -0,0 +1,21 @@
+/*==============================================================*/
+/* Table: Notification                                          */
+/*==============================================================*/
+create table Notification (
+   notif_id             SERIAL               not null,
+   user_name            VARCHAR(16)          not null,
+   notif_content        TEXT                 not null,
+   notif_time           TIMESTAMP WITH TIME ZONE not null,
+   notif_read_time      TIMESTAMP WITH TIME ZONE null,
+   constraint PK_NOTIFICATION primary key (notif_id),
+   constraint FK_NOTIFICA_NOTIFYING_SYSUSER foreign key (user_name)
+      references SysUser (user_name)
+      on delete restrict on update restrict
+);
+
+/*==============================================================*/
+/* Index: Notifying_FK                                          */
+/*==============================================================*/
+create  index Notifying_FK on Notification (
+user_name
+);
@@ -0,0 +1,42 @@
+/*==============================================================*/
+/* Table: AssignmentChange                                      */
+/*==============================================================*/
+create table AssignmentChange (
+   assn_change_id       SERIAL               not null,
+   student_user_name    VARCHAR(16)          not null,
+   office_user_name     VARCHAR(16)          not null,
+   assn_change_old_topic_id INT4             null,
+   assn_change_new_topic_id INT4             null,
+   assn_change_reason   TEXT                 not null,
+   assn_change_time     TIMESTAMP WITH TIME ZONE not null,
+   assn_change_overrode_capacity BOOL        not null default false,
+   assn_change_moved_records BOOL            not null default false,
+   constraint PK_ASSIGNMENTCHANGE primary key (assn_change_id),
+   constraint CKC_ASSN_CHANGE_TOPICS_ASSIGNME check (assn_change_old_topic_id is not null or assn_change_new_topic_id is not null),
+   constraint FK_ASSIGNME_CHANGING_STUDENT foreign key (student_user_name)
+      references Student (user_name)
+      on delete restrict on update restrict,
+   constraint FK_ASSIGNME_ORDERING_OFFICE foreign key (office_user_name)
+      references Office (user_name)
+      on delete restrict on update restrict,
+   constraint FK_ASSIGNME_LEAVING_TOPIC foreign key (assn_change_old_topic_id)
+      references Topic (topic_id)
+      on delete restrict on update restrict,
+   constraint FK_ASSIGNME_ENTERING_TOPIC foreign key (assn_change_new_topic_id)
+      references Topic (topic_id)
+      on delete restrict on update restrict
+);
+
+/*==============================================================*/
+/* Index: Changing_FK                                           */
+/*==============================================================*/
+create  index Changing_FK on AssignmentChange (
+student_user_name
+);
+
+/*==============================================================*/
+/* Index: Ordering_FK                                           */
+/*==============================================================*/
+create  index Ordering_FK on AssignmentChange (
+office_user_name
+);
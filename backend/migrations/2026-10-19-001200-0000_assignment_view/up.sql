@@ -0,0 +1,41 @@
+/*==============================================================*/
+/* View: AssignmentView                                         */
+/*==============================================================*/
+create view AssignmentView as
+select
+   'r' || r.assn_req_id   as assn_key,
+   r.student_user_name,
+   s.student_name,
+   s.major_id,
+   m.major_name,
+   r.topic_id,
+   t.topic_name,
+   t.teacher_user_name,
+   r.assn_req_time        as assn_request_time,
+   r.assn_req_status      as assn_status,
+   r.assn_req_decided_time as assn_decided_time,
+   r.assn_req_decided_by  as assn_decided_by,
+   r.assn_req_reason      as assn_reason
+from AssignmentRequest r
+   join Student s on s.user_name = r.student_user_name
+   join Major m on m.major_id = s.major_id
+   join Topic t on t.topic_id = r.topic_id
+where r.assn_req_status <> 1
+union all
+select
+   's' || s.user_name,
+   s.user_name,
+   s.student_name,
+   s.major_id,
+   m.major_name,
+   s.topic_id,
+   t.topic_name,
+   t.teacher_user_name,
+   s.assn_time,
+   1::INT2,
+   null::TIMESTAMP WITH TIME ZONE,
+   null::VARCHAR(16),
+   null::TEXT
+from Student s
+   join Major m on m.major_id = s.major_id
+   join Topic t on t.topic_id = s.topic_id;
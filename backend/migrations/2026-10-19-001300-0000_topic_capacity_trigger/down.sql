@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+
+drop trigger TRG_TOPIC_CAPACITY_STUDENT on Student;
+
+drop function check_topic_capacity();
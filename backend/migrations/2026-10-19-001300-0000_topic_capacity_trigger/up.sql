@@ -0,0 +1,47 @@
+/*==============================================================*/
+/* Function: check_topic_capacity                               */
+/*==============================================================*/
+-- Rejects placing a student on a topic that is already full. The topic row is locked first,
+-- so concurrent placements on the same topic are serialized and each one counts the
+-- students committed before it. Office overrides set `thesis_mgmt.capacity_override` to
+-- `on` for their transaction to skip the check.
+create function check_topic_capacity() returns trigger as $$
+declare
+   max_students INT4;
+   current_students INT8;
+begin
+   if new.topic_id is null then
+      return new;
+   end if;
+   if TG_OP = 'UPDATE' then
+      if new.topic_id is not distinct from old.topic_id then
+         return new;
+      end if;
+   end if;
+   if current_setting('thesis_mgmt.capacity_override', true) = 'on' then
+      return new;
+   end if;
+
+   select topic_max_students into max_students
+      from Topic
+      where topic_id = new.topic_id
+      for update;
+   select count(*) into current_students
+      from Student
+      where topic_id = new.topic_id;
+   if current_students >= max_students then
+      raise exception 'Topic % is full', new.topic_id
+         using errcode = 'check_violation', constraint = 'CKC_TOPIC_CAPACITY_STUDENT';
+   end if;
+
+   return new;
+end;
+$$ language plpgsql;
+
+/*==============================================================*/
+/* Trigger: TRG_TOPIC_CAPACITY_STUDENT                          */
+/*==============================================================*/
+create trigger TRG_TOPIC_CAPACITY_STUDENT
+   before insert or update of topic_id on Student
+   for each row
+   execute function check_topic_capacity();
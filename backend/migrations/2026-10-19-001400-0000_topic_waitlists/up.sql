@@ -0,0 +1,24 @@
+/*==============================================================*/
+/* Table: TopicWaitlist                                         */
+/*==============================================================*/
+create table TopicWaitlist (
+   waitlist_id            SERIAL             not null,
+   topic_id               INT4               not null,
+   student_user_name      VARCHAR(16)        not null,
+   waitlist_time          TIMESTAMP WITH TIME ZONE not null,
+   constraint PK_TOPICWAITLIST primary key (waitlist_id),
+   constraint AK_WAITLIST_TOPICWAI unique (topic_id, student_user_name),
+   constraint FK_TOPICWAI_WAITINGFO_TOPIC foreign key (topic_id)
+      references Topic (topic_id)
+      on delete restrict on update restrict,
+   constraint FK_TOPICWAI_WAITING_STUDENT foreign key (student_user_name)
+      references Student (user_name)
+      on delete restrict on update restrict
+);
+
+/*==============================================================*/
+/* Index: Waiting_FK                                            */
+/*==============================================================*/
+create  index Waiting_FK on TopicWaitlist (
+student_user_name
+);
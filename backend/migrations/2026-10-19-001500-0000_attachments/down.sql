@@ -0,0 +1,39 @@
+-- This file should undo anything in `up.sql`
+
+-- Contents already moved into the attachment store cannot be restored from SQL, so such
+-- attachments come back as empty strings.
+alter table ProgressReport
+   add prog_report_attachment TEXT not null default '';
+
+update ProgressReport
+   set prog_report_attachment = coalesce(
+      (select 'data:' || attach_mime_type || ';base64,'
+                 || translate(encode(attach_legacy_content, 'base64'), E'\n', '')
+       from Attachment
+       where attach_id = prog_report_attach_id),
+      '');
+
+alter table ProgressReport
+   alter column prog_report_attachment drop default;
+
+alter table ProgressReport
+   drop column prog_report_attach_id;
+
+alter table FinalDefense
+   add final_def_attachment TEXT not null default '';
+
+update FinalDefense
+   set final_def_attachment = coalesce(
+      (select 'data:' || attach_mime_type || ';base64,'
+                 || translate(encode(attach_legacy_content, 'base64'), E'\n', '')
+       from Attachment
+       where attach_id = final_def_attach_id),
+      '');
+
+alter table FinalDefense
+   alter column final_def_attachment drop default;
+
+alter table FinalDefense
+   drop column final_def_attach_id;
+
+drop table Attachment;
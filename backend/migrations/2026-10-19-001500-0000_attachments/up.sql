@@ -0,0 +1,115 @@
+/*==============================================================*/
+/* Table: Attachment                                            */
+/*==============================================================*/
+create table Attachment (
+   attach_id              SERIAL             not null,
+   uploader_user_name     VARCHAR(16)        not null,
+   attach_file_name       VARCHAR(255)       not null,
+   attach_mime_type       VARCHAR(128)       not null,
+   attach_size            INT8               not null
+      constraint CKC_ATTACH_SIZE_ATTACHME check (attach_size >= 0),
+   attach_sha256          CHAR(64)           not null,
+   attach_upload_time     TIMESTAMP WITH TIME ZONE not null,
+   attach_legacy_content  BYTEA              null,
+   constraint PK_ATTACHMENT primary key (attach_id),
+   constraint FK_ATTACHME_UPLOADING_SYSUSER foreign key (uploader_user_name)
+      references SysUser (user_name)
+      on delete restrict on update restrict
+);
+
+/*==============================================================*/
+/* Index: Uploading_FK                                          */
+/*==============================================================*/
+create  index Uploading_FK on Attachment (
+uploader_user_name
+);
+
+alter table ProgressReport
+   add prog_report_attach_id INT4 null;
+
+alter table FinalDefense
+   add final_def_attach_id INT4 null;
+
+/*==============================================================*/
+/* Move attachments held inline as data URLs into Attachment.   */
+/* Their contents stay in attach_legacy_content until the       */
+/* server writes them into the attachment store on startup.     */
+/*==============================================================*/
+create function import_legacy_attachment(
+   uploader VARCHAR(16),
+   file_name VARCHAR(255),
+   upload_time TIMESTAMP WITH TIME ZONE,
+   inline TEXT
+) returns INT4 as $$
+declare
+   mime_type VARCHAR(128);
+   content BYTEA;
+   new_id INT4;
+begin
+   if inline ~ '^data:[^,]*;base64,' then
+      mime_type := coalesce(nullif(substring(inline from '^data:([^;,]*)'), ''),
+                            'application/octet-stream');
+      content := decode(substring(inline from position(',' in inline) + 1), 'base64');
+   else
+      mime_type := 'text/plain';
+      content := convert_to(inline, 'UTF8');
+   end if;
+
+   insert into Attachment (uploader_user_name, attach_file_name, attach_mime_type,
+                           attach_size, attach_sha256, attach_upload_time,
+                           attach_legacy_content)
+   values (uploader, file_name, mime_type, length(content),
+           encode(sha256(content), 'hex'), upload_time, content)
+   returning attach_id into new_id;
+
+   return new_id;
+end;
+$$ language plpgsql;
+
+update ProgressReport
+   set prog_report_attach_id = import_legacy_attachment(
+      student_user_name, 'progress-report-' || prog_report_id,
+      prog_report_time, prog_report_attachment);
+
+update FinalDefense
+   set final_def_attach_id = import_legacy_attachment(
+      student_user_name, 'final-defense-' || final_def_id,
+      final_def_time, final_def_attachment);
+
+drop function import_legacy_attachment(VARCHAR, VARCHAR, TIMESTAMP WITH TIME ZONE, TEXT);
+
+alter table ProgressReport
+   alter column prog_report_attach_id set not null;
+
+alter table ProgressReport
+   drop column prog_report_attachment;
+
+alter table ProgressReport
+   add constraint FK_PROGRESS_ATTACHING_ATTACHME foreign key (prog_report_attach_id)
+      references Attachment (attach_id)
+      on delete restrict on update restrict;
+
+/*==============================================================*/
+/* Index: ProgressReportAttaching_FK                            */
+/*==============================================================*/
+create  index ProgressReportAttaching_FK on ProgressReport (
+prog_report_attach_id
+);
+
+alter table FinalDefense
+   alter column final_def_attach_id set not null;
+
+alter table FinalDefense
+   drop column final_def_attachment;
+
+alter table FinalDefense
+   add constraint FK_FINALDEF_ATTACHING_ATTACHME foreign key (final_def_attach_id)
+      references Attachment (attach_id)
+      on delete restrict on update restrict;
+
+/*==============================================================*/
+/* Index: FinalDefenseAttaching_FK                              */
+/*==============================================================*/
+create  index FinalDefenseAttaching_FK on FinalDefense (
+final_def_attach_id
+);
@@ -0,0 +1,59 @@
+-- This file should undo anything in `up.sql`
+
+-- Only one attachment per submission survives: its thesis file, or its earliest uploaded
+-- file if it has none.
+alter table ProgressReport
+   add prog_report_attach_id INT4 null;
+
+update ProgressReport r
+   set prog_report_attach_id = (
+      select f.attach_id
+      from ProgressReportFile f
+      where f.prog_report_id = r.prog_report_id
+      order by f.prog_file_kind, f.attach_id
+      limit 1);
+
+alter table ProgressReport
+   alter column prog_report_attach_id set not null;
+
+alter table ProgressReport
+   add constraint FK_PROGRESS_ATTACHING_ATTACHME foreign key (prog_report_attach_id)
+      references Attachment (attach_id)
+      on delete restrict on update restrict;
+
+create  index ProgressReportAttaching_FK on ProgressReport (
+prog_report_attach_id
+);
+
+alter table ProgressReport
+   drop column prog_report_version;
+
+alter table FinalDefense
+   add final_def_attach_id INT4 null;
+
+update FinalDefense d
+   set final_def_attach_id = (
+      select f.attach_id
+      from FinalDefenseFile f
+      where f.final_def_id = d.final_def_id
+      order by f.def_file_kind, f.attach_id
+      limit 1);
+
+alter table FinalDefense
+   alter column final_def_attach_id set not null;
+
+alter table FinalDefense
+   add constraint FK_FINALDEF_ATTACHING_ATTACHME foreign key (final_def_attach_id)
+      references Attachment (attach_id)
+      on delete restrict on update restrict;
+
+create  index FinalDefenseAttaching_FK on FinalDefense (
+final_def_attach_id
+);
+
+alter table FinalDefense
+   drop column final_def_version;
+
+drop table FinalDefenseFile;
+
+drop table ProgressReportFile;
@@ -0,0 +1,104 @@
+/*==============================================================*/
+/* Table: ProgressReportFile                                    */
+/*==============================================================*/
+create table ProgressReportFile (
+   prog_report_id         INT4               not null,
+   attach_id              INT4               not null,
+   prog_file_kind         INT2               not null
+      constraint CKC_PROG_FILE_KIND_PROGRESS check (prog_file_kind in (0,1,2,3)),
+   constraint PK_PROGRESSREPORTFILE primary key (prog_report_id, attach_id),
+   constraint FK_PROGRESS_CONTAININ_PROGRESS foreign key (prog_report_id)
+      references ProgressReport (prog_report_id)
+      on delete restrict on update restrict,
+   constraint FK_PROGRESS_CONTAINED_ATTACHME foreign key (attach_id)
+      references Attachment (attach_id)
+      on delete restrict on update restrict
+);
+
+/*==============================================================*/
+/* Index: ProgressReportContained_FK                            */
+/*==============================================================*/
+create  index ProgressReportContained_FK on ProgressReportFile (
+attach_id
+);
+
+/*==============================================================*/
+/* Table: FinalDefenseFile                                      */
+/*==============================================================*/
+create table FinalDefenseFile (
+   final_def_id           INT4               not null,
+   attach_id              INT4               not null,
+   def_file_kind          INT2               not null
+      constraint CKC_DEF_FILE_KIND_FINALDEF check (def_file_kind in (0,1,2,3)),
+   constraint PK_FINALDEFENSEFILE primary key (final_def_id, attach_id),
+   constraint FK_FINALDEF_CONTAININ_FINALDEF foreign key (final_def_id)
+      references FinalDefense (final_def_id)
+      on delete restrict on update restrict,
+   constraint FK_FINALDEF_CONTAINED_ATTACHME foreign key (attach_id)
+      references Attachment (attach_id)
+      on delete restrict on update restrict
+);
+
+/*==============================================================*/
+/* Index: FinalDefenseContained_FK                              */
+/*==============================================================*/
+create  index FinalDefenseContained_FK on FinalDefenseFile (
+attach_id
+);
+
+/*==============================================================*/
+/* Number each submission among those of the same student,      */
+/* topic and stage, and keep its single attachment as its       */
+/* thesis file.                                                 */
+/*==============================================================*/
+alter table ProgressReport
+   add prog_report_version INT4 null;
+
+update ProgressReport r
+   set prog_report_version = v.version
+   from (select prog_report_id,
+                row_number() over (partition by student_user_name, topic_id, prog_report_type
+                                   order by prog_report_time, prog_report_id) as version
+         from ProgressReport) v
+   where r.prog_report_id = v.prog_report_id;
+
+alter table ProgressReport
+   alter column prog_report_version set not null;
+
+alter table ProgressReport
+   add constraint CKC_PROG_REPORT_VERSI_PROGRESS check (prog_report_version >= 1);
+
+insert into ProgressReportFile (prog_report_id, attach_id, prog_file_kind)
+   select prog_report_id, prog_report_attach_id, 0
+   from ProgressReport;
+
+drop index ProgressReportAttaching_FK;
+
+alter table ProgressReport
+   drop column prog_report_attach_id;
+
+alter table FinalDefense
+   add final_def_version INT4 null;
+
+update FinalDefense d
+   set final_def_version = v.version
+   from (select final_def_id,
+                row_number() over (partition by student_user_name, topic_id
+                                   order by final_def_time, final_def_id) as version
+         from FinalDefense) v
+   where d.final_def_id = v.final_def_id;
+
+alter table FinalDefense
+   alter column final_def_version set not null;
+
+alter table FinalDefense
+   add constraint CKC_FINAL_DEF_VERSION_FINALDEF check (final_def_version >= 1);
+
+insert into FinalDefenseFile (final_def_id, attach_id, def_file_kind)
+   select final_def_id, final_def_attach_id, 0
+   from FinalDefense;
+
+drop index FinalDefenseAttaching_FK;
+
+alter table FinalDefense
+   drop column final_def_attach_id;
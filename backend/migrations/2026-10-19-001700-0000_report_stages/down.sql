@@ -0,0 +1,24 @@
+-- This file should undo anything in `up.sql`
+
+-- Reports for stages other than the proposal and the midterm cannot be represented any more.
+delete from ProgressReportFile
+   where prog_report_id in (
+      select prog_report_id from ProgressReport where prog_report_stage_id not in (0,1));
+
+delete from ProgressReport
+   where prog_report_stage_id not in (0,1);
+
+drop index ReportingStage_FK;
+
+alter table ProgressReport
+   drop constraint FK_PROGRESS_REPORTING_REPORTST;
+
+alter table ProgressReport
+   rename column prog_report_stage_id to prog_report_type;
+
+alter table ProgressReport
+   add constraint CKC_PROG_REPORT_TYPE_PROGRESS check (prog_report_type in (0,1));
+
+drop table ReportStagePrerequisite;
+
+drop table ReportStage;
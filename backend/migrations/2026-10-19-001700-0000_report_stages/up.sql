@@ -0,0 +1,67 @@
+/*==============================================================*/
+/* Table: ReportStage                                           */
+/*==============================================================*/
+create table ReportStage (
+   stage_id               SMALLSERIAL        not null,
+   stage_name             VARCHAR(32)        not null,
+   stage_order            INT4               not null,
+   stage_reviewer         INT2               not null
+      constraint CKC_STAGE_REVIEWER_REPORTST check (stage_reviewer in (0,1)),
+   stage_grade_required   BOOL               not null,
+   constraint PK_REPORTSTAGE primary key (stage_id),
+   constraint AK_STAGE_NAME_REPORTST unique (stage_name)
+);
+
+/*==============================================================*/
+/* Table: ReportStagePrerequisite                               */
+/*==============================================================*/
+create table ReportStagePrerequisite (
+   stage_id               INT2               not null,
+   prereq_stage_id        INT2               not null,
+   constraint PK_REPORTSTAGEPREREQUISITE primary key (stage_id, prereq_stage_id),
+   constraint CKC_PREREQ_STAGE_ID_REPORTST check (prereq_stage_id <> stage_id),
+   constraint FK_REPORTST_REQUIRING_REPORTST foreign key (stage_id)
+      references ReportStage (stage_id)
+      on delete restrict on update restrict,
+   constraint FK_REPORTST_REQUIRED_REPORTST foreign key (prereq_stage_id)
+      references ReportStage (stage_id)
+      on delete restrict on update restrict
+);
+
+/*==============================================================*/
+/* Index: Required_FK                                           */
+/*==============================================================*/
+create  index Required_FK on ReportStagePrerequisite (
+prereq_stage_id
+);
+
+/*==============================================================*/
+/* Keep the former report types as the first two stages, under  */
+/* the same identifiers.                                        */
+/*==============================================================*/
+insert into ReportStage (stage_id, stage_name, stage_order, stage_reviewer, stage_grade_required)
+values (0, '开题报告', 1, 0, false),
+       (1, '中期检查', 2, 0, false);
+
+select setval(pg_get_serial_sequence('reportstage', 'stage_id'), 1);
+
+insert into ReportStagePrerequisite (stage_id, prereq_stage_id)
+values (1, 0);
+
+alter table ProgressReport
+   drop constraint CKC_PROG_REPORT_TYPE_PROGRESS;
+
+alter table ProgressReport
+   rename column prog_report_type to prog_report_stage_id;
+
+alter table ProgressReport
+   add constraint FK_PROGRESS_REPORTING_REPORTST foreign key (prog_report_stage_id)
+      references ReportStage (stage_id)
+      on delete restrict on update restrict;
+
+/*==============================================================*/
+/* Index: ReportingStage_FK                                     */
+/*==============================================================*/
+create  index ReportingStage_FK on ProgressReport (
+prog_report_stage_id
+);
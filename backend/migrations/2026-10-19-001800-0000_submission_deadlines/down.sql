@@ -0,0 +1,11 @@
+-- This file should undo anything in `up.sql`
+
+alter table FinalDefense
+   drop column final_def_due_time;
+
+alter table ProgressReport
+   drop column prog_report_due_time;
+
+drop table DeadlineExtension;
+
+drop table SubmissionDeadline;
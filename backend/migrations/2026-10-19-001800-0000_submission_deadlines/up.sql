@@ -0,0 +1,71 @@
+/*==============================================================*/
+/* Table: SubmissionDeadline                                    */
+/*==============================================================*/
+create table SubmissionDeadline (
+   deadline_id            SERIAL             not null,
+   major_id               INT4               not null,
+   stage_id               INT2               null,
+   deadline_due_time      TIMESTAMP WITH TIME ZONE not null,
+   deadline_is_hard       BOOL               not null,
+   constraint PK_SUBMISSIONDEADLINE primary key (deadline_id),
+   constraint FK_SUBMISSI_BINDING_MAJOR foreign key (major_id)
+      references Major (major_id)
+      on delete restrict on update restrict,
+   constraint FK_SUBMISSI_SCHEDULIN_REPORTST foreign key (stage_id)
+      references ReportStage (stage_id)
+      on delete restrict on update restrict
+);
+
+/*==============================================================*/
+/* Index: StageDeadline_AK                                      */
+/*==============================================================*/
+create unique index StageDeadline_AK on SubmissionDeadline (
+major_id,
+stage_id
+) where stage_id is not null;
+
+/*==============================================================*/
+/* Index: DefenseDeadline_AK                                    */
+/*==============================================================*/
+create unique index DefenseDeadline_AK on SubmissionDeadline (
+major_id
+) where stage_id is null;
+
+/*==============================================================*/
+/* Index: Scheduling_FK                                         */
+/*==============================================================*/
+create  index Scheduling_FK on SubmissionDeadline (
+stage_id
+);
+
+/*==============================================================*/
+/* Table: DeadlineExtension                                     */
+/*==============================================================*/
+create table DeadlineExtension (
+   deadline_id            INT4               not null,
+   student_user_name      VARCHAR(16)        not null,
+   ext_due_time           TIMESTAMP WITH TIME ZONE not null,
+   constraint PK_DEADLINEEXTENSION primary key (deadline_id, student_user_name),
+   constraint FK_DEADLINE_EXTENDING_SUBMISSI foreign key (deadline_id)
+      references SubmissionDeadline (deadline_id)
+      on delete restrict on update restrict,
+   constraint FK_DEADLINE_GRANTED_STUDENT foreign key (student_user_name)
+      references Student (user_name)
+      on delete restrict on update restrict
+);
+
+/*==============================================================*/
+/* Index: Granted_FK                                            */
+/*==============================================================*/
+create  index Granted_FK on DeadlineExtension (
+student_user_name
+);
+
+/*==============================================================*/
+/* Effective deadline at the time of each submission            */
+/*==============================================================*/
+alter table ProgressReport
+   add column prog_report_due_time TIMESTAMP WITH TIME ZONE null;
+
+alter table FinalDefense
+   add column final_def_due_time TIMESTAMP WITH TIME ZONE null;
@@ -0,0 +1,13 @@
+-- This file should undo anything in `up.sql`
+
+alter table FinalDefense
+   drop constraint CKC_FINAL_DEF_GRADE_FINALDEF;
+
+alter table ProgressReport
+   drop constraint CKC_PROG_REPORT_GRADE_PROGRESS;
+
+drop table FinalDefenseScore;
+
+drop table ProgressReportScore;
+
+drop table RubricCriterion;
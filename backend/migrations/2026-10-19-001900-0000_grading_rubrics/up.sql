@@ -0,0 +1,83 @@
+/*==============================================================*/
+/* Table: RubricCriterion                                       */
+/*==============================================================*/
+create table RubricCriterion (
+   crit_id                SERIAL             not null,
+   stage_id               INT2               null,
+   crit_name              VARCHAR(64)        not null,
+   crit_order             INT2               not null,
+   crit_weight            DECIMAL(5,2)       not null
+      constraint CKC_CRIT_WEIGHT_RUBRICCR check (crit_weight > 0),
+   crit_max_score         DECIMAL(5,2)       not null
+      constraint CKC_CRIT_MAX_SCORE_RUBRICCR check (crit_max_score > 0),
+   constraint PK_RUBRICCRITERION primary key (crit_id),
+   constraint FK_RUBRICCR_ASSESSING_REPORTST foreign key (stage_id)
+      references ReportStage (stage_id)
+      on delete restrict on update restrict
+);
+
+/*==============================================================*/
+/* Index: Assessing_FK                                          */
+/*==============================================================*/
+create  index Assessing_FK on RubricCriterion (
+stage_id
+);
+
+/*==============================================================*/
+/* Table: ProgressReportScore                                   */
+/*==============================================================*/
+create table ProgressReportScore (
+   prog_report_id         INT4               not null,
+   crit_id                INT4               not null,
+   prog_score             DECIMAL(5,2)       not null
+      constraint CKC_PROG_SCORE_PROGRESS check (prog_score >= 0),
+   constraint PK_PROGRESSREPORTSCORE primary key (prog_report_id, crit_id),
+   constraint FK_PROGRESS_SCORING_PROGRESS foreign key (prog_report_id)
+      references ProgressReport (prog_report_id)
+      on delete restrict on update restrict,
+   constraint FK_PROGRESS_SCORED_RUBRICCR foreign key (crit_id)
+      references RubricCriterion (crit_id)
+      on delete restrict on update restrict
+);
+
+/*==============================================================*/
+/* Index: ProgressReportScored_FK                               */
+/*==============================================================*/
+create  index ProgressReportScored_FK on ProgressReportScore (
+crit_id
+);
+
+/*==============================================================*/
+/* Table: FinalDefenseScore                                     */
+/*==============================================================*/
+create table FinalDefenseScore (
+   final_def_id           INT4               not null,
+   crit_id                INT4               not null,
+   def_score              DECIMAL(5,2)       not null
+      constraint CKC_DEF_SCORE_FINALDEF check (def_score >= 0),
+   constraint PK_FINALDEFENSESCORE primary key (final_def_id, crit_id),
+   constraint FK_FINALDEF_SCORING_FINALDEF foreign key (final_def_id)
+      references FinalDefense (final_def_id)
+      on delete restrict on update restrict,
+   constraint FK_FINALDEF_SCORED_RUBRICCR foreign key (crit_id)
+      references RubricCriterion (crit_id)
+      on delete restrict on update restrict
+);
+
+/*==============================================================*/
+/* Index: FinalDefenseScored_FK                                 */
+/*==============================================================*/
+create  index FinalDefenseScored_FK on FinalDefenseScore (
+crit_id
+);
+
+/*==============================================================*/
+/* Grades are out of 100                                        */
+/* Grades given before rubrics were not bounded, so existing    */
+/* rows are left unchecked and only new grades must comply      */
+/*==============================================================*/
+alter table ProgressReport
+   add constraint CKC_PROG_REPORT_GRADE_PROGRESS check (prog_report_grade between 0 and 100) not valid;
+
+alter table FinalDefense
+   add constraint CKC_FINAL_DEF_GRADE_FINALDEF check (final_def_grade between 0 and 100) not valid;
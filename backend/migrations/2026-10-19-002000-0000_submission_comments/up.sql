@@ -0,0 +1,67 @@
+/*==============================================================*/
+/* Table: SubmissionComment                                     */
+/*==============================================================*/
+create table SubmissionComment (
+   comment_id             SERIAL             not null,
+   prog_report_id         INT4               null,
+   final_def_id           INT4               null,
+   comment_parent_id      INT4               null,
+   author_user_name       VARCHAR(16)        not null,
+   comment_author_role    INT2               not null
+      constraint CKC_COMMENT_AUTHOR_ROLE_SUBMISSI check (comment_author_role in (1,2,3,4)),
+   comment_content        TEXT               not null,
+   comment_time           TIMESTAMP WITH TIME ZONE not null,
+   attach_id              INT4               null,
+   constraint PK_SUBMISSIONCOMMENT primary key (comment_id),
+   constraint CKC_COMMENT_SUBJECT_SUBMISSI check ((prog_report_id is null) <> (final_def_id is null)),
+   constraint FK_SUBMISSI_DISCUSSIN_PROGRESS foreign key (prog_report_id)
+      references ProgressReport (prog_report_id)
+      on delete restrict on update restrict,
+   constraint FK_SUBMISSI_DISCUSSIN_FINALDEF foreign key (final_def_id)
+      references FinalDefense (final_def_id)
+      on delete restrict on update restrict,
+   constraint FK_SUBMISSI_REPLYING_SUBMISSI foreign key (comment_parent_id)
+      references SubmissionComment (comment_id)
+      on delete restrict on update restrict,
+   constraint FK_SUBMISSI_AUTHORING_SYSUSER foreign key (author_user_name)
+      references SysUser (user_name)
+      on delete restrict on update restrict,
+   constraint FK_SUBMISSI_REFERRING_ATTACHME foreign key (attach_id)
+      references Attachment (attach_id)
+      on delete restrict on update restrict
+);
+
+/*==============================================================*/
+/* Index: DiscussingReport_FK                                   */
+/*==============================================================*/
+create  index DiscussingReport_FK on SubmissionComment (
+prog_report_id
+);
+
+/*==============================================================*/
+/* Index: DiscussingDefense_FK                                  */
+/*==============================================================*/
+create  index DiscussingDefense_FK on SubmissionComment (
+final_def_id
+);
+
+/*==============================================================*/
+/* Index: Replying_FK                                           */
+/*==============================================================*/
+create  index Replying_FK on SubmissionComment (
+comment_parent_id
+);
+
+/*==============================================================*/
+/* Index: Authoring_FK                                          */
+/*==============================================================*/
+create  index Authoring_FK on SubmissionComment (
+author_user_name
+);
+
+/*==============================================================*/
+/* Index: Referring_FK                                          */
+/*==============================================================*/
+create  index Referring_FK on SubmissionComment (
+attach_id
+);
@@ -0,0 +1,62 @@
+/*==============================================================*/
+/* Table: RevisionLock                                          */
+/*==============================================================*/
+create table RevisionLock (
+   major_id             INT4                 not null,
+   rev_lock_time        TIMESTAMP WITH TIME ZONE not null,
+   constraint PK_REVISIONLOCK primary key (major_id),
+   constraint FK_REVISION_LOCKING_MAJOR foreign key (major_id)
+      references Major (major_id)
+      on delete restrict on update restrict
+);
+
+/*==============================================================*/
+/* Table: ProgressReportRevision                                */
+/*==============================================================*/
+create table ProgressReportRevision (
+   rev_id               SERIAL               not null,
+   prog_report_id       INT4                 not null,
+   rev_user_name        VARCHAR(16)          not null,
+   rev_cause_id         INT4                 null,
+   rev_reason           TEXT                 not null,
+   rev_time             TIMESTAMP WITH TIME ZONE not null,
+   rev_old_outcome      INT2                 not null
+      constraint CKC_REV_OLD_OUTCOME_PROGRESS check (rev_old_outcome in (0,1,2)),
+   rev_old_comment      TEXT                 null,
+   rev_old_grade        DECIMAL(5,2)         null,
+   rev_new_outcome      INT2                 not null
+      constraint CKC_REV_NEW_OUTCOME_PROGRESS check (rev_new_outcome in (1,2)),
+   rev_new_comment      TEXT                 null,
+   rev_new_grade        DECIMAL(5,2)         null,
+   constraint PK_PROGRESSREPORTREVISION primary key (rev_id),
+   constraint FK_PROGRESS_REVISING_PROGRESS foreign key (prog_report_id)
+      references ProgressReport (prog_report_id)
+      on delete restrict on update restrict,
+   constraint FK_PROGRESS_AMENDING_SYSUSER foreign key (rev_user_name)
+      references SysUser (user_name)
+      on delete restrict on update restrict,
+   constraint FK_PROGRESS_CAUSING_PROGRESS foreign key (rev_cause_id)
+      references ProgressReportRevision (rev_id)
+      on delete restrict on update restrict
+);
+
+/*==============================================================*/
+/* Index: Revising_FK                                           */
+/*==============================================================*/
+create  index Revising_FK on ProgressReportRevision (
+prog_report_id
+);
+
+/*==============================================================*/
+/* Index: Amending_FK                                           */
+/*==============================================================*/
+create  index Amending_FK on ProgressReportRevision (
+rev_user_name
+);
+
+/*==============================================================*/
+/* Index: Causing_FK                                            */
+/*==============================================================*/
+create  index Causing_FK on ProgressReportRevision (
+rev_cause_id
+);
@@ -0,0 +1,12 @@
+/*==============================================================*/
+/* Table: AttachmentText                                        */
+/*==============================================================*/
+create table AttachmentText (
+   attach_id            INT4                 not null,
+   attach_text          TEXT                 null,
+   attach_text_time     TIMESTAMP WITH TIME ZONE not null,
+   constraint PK_ATTACHMENTTEXT primary key (attach_id),
+   constraint FK_ATTACHME_EXTRACTE_ATTACHME foreign key (attach_id)
+      references Attachment (attach_id)
+      on delete restrict on update restrict
+);
@@ -1,5 +1,6 @@
 pub mod model;
 pub mod schema;
+pub mod view;
 
 use diesel::prelude::*;
 use diesel::r2d2::{ConnectionManager, Pool};
@@ -45,6 +45,15 @@ pub struct Major {
 pub struct Teacher {
     pub user_name: String,
     pub teacher_name: String,
+    pub teacher_title: i16,
+}
+
+#[derive(Identifiable, Queryable, Selectable, Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[diesel(primary_key(teacher_title))]
+#[diesel(table_name = crate::schema::supervisionquota)]
+pub struct SupervisionQuota {
+    pub teacher_title: i16,
+    pub quota_max_students: i32,
 }
 
 #[derive(
@@ -125,6 +134,300 @@ pub struct Topic {
     pub topic_max_students: i32,
     pub topic_type: i16,
     pub topic_review_status: i16,
+    pub topic_version: i32,
+    pub topic_review_comment: Option<String>,
+}
+
+#[derive(Identifiable, Queryable, Selectable, Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[diesel(primary_key(tag_id))]
+#[diesel(table_name = crate::schema::tag)]
+pub struct Tag {
+    pub tag_id: i32,
+    pub tag_name: String,
+}
+
+#[derive(
+    Identifiable,
+    Queryable,
+    Selectable,
+    Associations,
+    Debug,
+    PartialEq,
+    Clone,
+    Serialize,
+    Deserialize,
+)]
+#[diesel(primary_key(topic_id, tag_id))]
+#[diesel(belongs_to(Topic, foreign_key = topic_id))]
+#[diesel(belongs_to(Tag, foreign_key = tag_id))]
+#[diesel(table_name = crate::schema::topictag)]
+pub struct TopicTag {
+    pub topic_id: i32,
+    pub tag_id: i32,
+}
+
+#[derive(
+    Identifiable,
+    Queryable,
+    Selectable,
+    Associations,
+    Debug,
+    PartialEq,
+    Clone,
+    Serialize,
+    Deserialize,
+)]
+#[diesel(primary_key(topic_id, teacher_user_name))]
+#[diesel(belongs_to(Topic, foreign_key = topic_id))]
+#[diesel(belongs_to(Teacher, foreign_key = teacher_user_name))]
+#[diesel(table_name = crate::schema::topiccosupervisor)]
+pub struct TopicCoSupervisor {
+    pub topic_id: i32,
+    pub teacher_user_name: String,
+    pub cosup_can_approve: bool,
+}
+
+#[derive(
+    Identifiable,
+    Queryable,
+    Selectable,
+    Associations,
+    Debug,
+    PartialEq,
+    Clone,
+    Serialize,
+    Deserialize,
+)]
+#[diesel(primary_key(prop_id))]
+#[diesel(belongs_to(Student, foreign_key = student_user_name))]
+#[diesel(belongs_to(Teacher, foreign_key = teacher_user_name))]
+#[diesel(table_name = crate::schema::topicproposal)]
+pub struct TopicProposal {
+    pub prop_id: i32,
+    pub student_user_name: String,
+    pub teacher_user_name: String,
+    pub topic_id: Option<i32>,
+    pub prop_topic_name: String,
+    pub prop_topic_description: String,
+    pub prop_topic_type: i16,
+    pub prop_status: i16,
+    pub prop_time: DateTime<Utc>,
+    pub prop_decided_time: Option<DateTime<Utc>>,
+    pub prop_decline_reason: Option<String>,
+}
+
+#[derive(Identifiable, Queryable, Selectable, Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[diesel(primary_key(match_round_id))]
+#[diesel(table_name = crate::schema::matchinground)]
+pub struct MatchingRound {
+    pub match_round_id: i32,
+    pub match_round_name: String,
+    pub match_round_max_prefs: i16,
+    pub match_round_open_time: DateTime<Utc>,
+    pub match_round_close_time: DateTime<Utc>,
+    pub match_round_commit_time: Option<DateTime<Utc>>,
+}
+
+#[derive(
+    Identifiable,
+    Queryable,
+    Selectable,
+    Associations,
+    Debug,
+    PartialEq,
+    Clone,
+    Serialize,
+    Deserialize,
+)]
+#[diesel(primary_key(match_round_id, student_user_name, topic_id))]
+#[diesel(belongs_to(MatchingRound, foreign_key = match_round_id))]
+#[diesel(belongs_to(Student, foreign_key = student_user_name))]
+#[diesel(belongs_to(Topic, foreign_key = topic_id))]
+#[diesel(table_name = crate::schema::topicpreference)]
+pub struct TopicPreference {
+    pub match_round_id: i32,
+    pub student_user_name: String,
+    pub topic_id: i32,
+    pub pref_rank: i16,
+}
+
+#[derive(
+    Identifiable,
+    Queryable,
+    Selectable,
+    Associations,
+    Debug,
+    PartialEq,
+    Clone,
+    Serialize,
+    Deserialize,
+)]
+#[diesel(primary_key(match_round_id, topic_id, student_user_name))]
+#[diesel(belongs_to(MatchingRound, foreign_key = match_round_id))]
+#[diesel(belongs_to(Topic, foreign_key = topic_id))]
+#[diesel(belongs_to(Student, foreign_key = student_user_name))]
+#[diesel(table_name = crate::schema::applicantrank)]
+pub struct ApplicantRank {
+    pub match_round_id: i32,
+    pub topic_id: i32,
+    pub student_user_name: String,
+    pub appl_rank: i32,
+}
+
+#[derive(Identifiable, Queryable, Selectable, Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[diesel(primary_key(sel_round_id))]
+#[diesel(table_name = crate::schema::selectionround)]
+pub struct SelectionRound {
+    pub sel_round_id: i32,
+    pub sel_round_prev_id: Option<i32>,
+    pub sel_round_name: String,
+    pub sel_round_open_time: DateTime<Utc>,
+    pub sel_round_close_time: DateTime<Utc>,
+    pub sel_round_max_requests: i16,
+}
+
+#[derive(
+    Identifiable,
+    Queryable,
+    Selectable,
+    Associations,
+    Debug,
+    PartialEq,
+    Clone,
+    Serialize,
+    Deserialize,
+)]
+#[diesel(primary_key(sel_round_id, major_id))]
+#[diesel(belongs_to(SelectionRound, foreign_key = sel_round_id))]
+#[diesel(belongs_to(Major, foreign_key = major_id))]
+#[diesel(table_name = crate::schema::selectionroundmajor)]
+pub struct SelectionRoundMajor {
+    pub sel_round_id: i32,
+    pub major_id: i32,
+}
+
+#[derive(AsChangeset, Debug, Clone)]
+#[diesel(table_name = crate::schema::selectionround)]
+pub struct SelectionRoundChangeset {
+    pub sel_round_name: Option<String>,
+    pub sel_round_open_time: Option<DateTime<Utc>>,
+    pub sel_round_close_time: Option<DateTime<Utc>>,
+    pub sel_round_max_requests: Option<i16>,
+}
+
+#[derive(
+    Identifiable,
+    Queryable,
+    Selectable,
+    Associations,
+    Debug,
+    PartialEq,
+    Clone,
+    Serialize,
+    Deserialize,
+)]
+#[diesel(primary_key(notif_id))]
+#[diesel(belongs_to(SysUser, foreign_key = user_name))]
+#[diesel(table_name = crate::schema::notification)]
+pub struct Notification {
+    pub notif_id: i32,
+    pub user_name: String,
+    pub notif_content: String,
+    pub notif_time: DateTime<Utc>,
+    pub notif_read_time: Option<DateTime<Utc>>,
+}
+
+#[derive(
+    Identifiable,
+    Queryable,
+    Selectable,
+    Associations,
+    Debug,
+    PartialEq,
+    Clone,
+    Serialize,
+    Deserialize,
+)]
+#[diesel(primary_key(assn_change_id))]
+#[diesel(belongs_to(Student, foreign_key = student_user_name))]
+#[diesel(belongs_to(Office, foreign_key = office_user_name))]
+#[diesel(table_name = crate::schema::assignmentchange)]
+pub struct AssignmentChange {
+    pub assn_change_id: i32,
+    pub student_user_name: String,
+    pub office_user_name: String,
+    pub assn_change_old_topic_id: Option<i32>,
+    pub assn_change_new_topic_id: Option<i32>,
+    pub assn_change_reason: String,
+    pub assn_change_time: DateTime<Utc>,
+    pub assn_change_overrode_capacity: bool,
+    pub assn_change_moved_records: bool,
+}
+
+#[derive(
+    Identifiable,
+    Queryable,
+    Selectable,
+    Associations,
+    Debug,
+    PartialEq,
+    Clone,
+    Serialize,
+    Deserialize,
+)]
+#[diesel(primary_key(waitlist_id))]
+#[diesel(belongs_to(Topic, foreign_key = topic_id))]
+#[diesel(belongs_to(Student, foreign_key = student_user_name))]
+#[diesel(table_name = crate::schema::topicwaitlist)]
+pub struct TopicWaitlist {
+    pub waitlist_id: i32,
+    pub topic_id: i32,
+    pub student_user_name: String,
+    pub waitlist_time: DateTime<Utc>,
+}
+
+#[derive(
+    Identifiable,
+    Queryable,
+    Selectable,
+    Associations,
+    Debug,
+    PartialEq,
+    Clone,
+    Serialize,
+    Deserialize,
+)]
+#[diesel(primary_key(attach_id))]
+#[diesel(belongs_to(SysUser, foreign_key = uploader_user_name))]
+#[diesel(table_name = crate::schema::attachment)]
+pub struct Attachment {
+    pub attach_id: i32,
+    pub uploader_user_name: String,
+    pub attach_file_name: String,
+    pub attach_mime_type: String,
+    pub attach_size: i64,
+    pub attach_sha256: String,
+    pub attach_upload_time: DateTime<Utc>,
+    /// Contents imported from the former inline attachment columns, not yet in the store
+    pub attach_legacy_content: Option<Vec<u8>>,
+}
+
+#[derive(Queryable, Selectable, Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[diesel(table_name = crate::view::assignmentview)]
+pub struct AssignmentViewEntry {
+    pub assn_key: String,
+    pub student_user_name: String,
+    pub student_name: String,
+    pub major_id: i32,
+    pub major_name: String,
+    pub topic_id: i32,
+    pub topic_name: String,
+    pub teacher_user_name: String,
+    pub assn_request_time: DateTime<Utc>,
+    pub assn_status: i16,
+    pub assn_decided_time: Option<DateTime<Utc>>,
+    pub assn_decided_by: Option<String>,
+    pub assn_reason: Option<String>,
 }
 
 #[derive(AsChangeset, Debug, Clone)]
@@ -179,7 +482,7 @@ pub struct StudentAssignmentChangeset {
     Serialize,
     Deserialize,
 )]
-#[diesel(primary_key(student_user_name, topic_id))]
+#[diesel(primary_key(assn_req_id))]
 #[diesel(belongs_to(Student, foreign_key = student_user_name))]
 #[diesel(belongs_to(Topic, foreign_key = topic_id))]
 #[diesel(table_name = crate::schema::assignmentrequest)]
@@ -187,6 +490,40 @@ pub struct AssignmentRequest {
     pub student_user_name: String,
     pub topic_id: i32,
     pub assn_req_time: DateTime<Utc>,
+    pub sel_round_id: Option<i32>,
+    pub assn_req_id: i32,
+    pub assn_req_status: i16,
+    pub assn_req_decided_time: Option<DateTime<Utc>>,
+    pub assn_req_decided_by: Option<String>,
+    pub assn_req_reason: Option<String>,
+}
+
+#[derive(Identifiable, Queryable, Selectable, Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[diesel(primary_key(stage_id))]
+#[diesel(table_name = crate::schema::reportstage)]
+pub struct ReportStage {
+    pub stage_id: i16,
+    pub stage_name: String,
+    pub stage_order: i32,
+    pub stage_reviewer: i16,
+    pub stage_grade_required: bool,
+}
+
+#[derive(Identifiable, Queryable, Selectable, Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[diesel(primary_key(stage_id, prereq_stage_id))]
+#[diesel(table_name = crate::schema::reportstageprerequisite)]
+pub struct ReportStagePrerequisite {
+    pub stage_id: i16,
+    pub prereq_stage_id: i16,
+}
+
+#[derive(AsChangeset, Debug, Clone)]
+#[diesel(table_name = crate::schema::reportstage)]
+pub struct ReportStageChangeset {
+    pub stage_name: Option<String>,
+    pub stage_order: Option<i32>,
+    pub stage_reviewer: Option<i16>,
+    pub stage_grade_required: Option<bool>,
 }
 
 #[derive(
@@ -201,6 +538,7 @@ pub struct AssignmentRequest {
     Deserialize,
 )]
 #[diesel(primary_key(prog_report_id))]
+#[diesel(belongs_to(ReportStage, foreign_key = prog_report_stage_id))]
 #[diesel(belongs_to(Student, foreign_key = student_user_name))]
 #[diesel(belongs_to(Topic, foreign_key = topic_id))]
 #[diesel(table_name = crate::schema::progressreport)]
@@ -208,12 +546,13 @@ pub struct ProgressReport {
     pub prog_report_id: i32,
     pub topic_id: i32,
     pub student_user_name: String,
-    pub prog_report_type: i16,
+    pub prog_report_stage_id: i16,
     pub prog_report_time: DateTime<Utc>,
-    pub prog_report_attachment: String,
     pub prog_report_outcome: i16,
     pub prog_report_comment: Option<String>,
     pub prog_report_grade: Option<BigDecimal>,
+    pub prog_report_version: i32,
+    pub prog_report_due_time: Option<DateTime<Utc>>,
 }
 
 #[derive(
@@ -238,10 +577,219 @@ pub struct FinalDefense {
     pub student_user_name: String,
     pub def_board_user_name: Option<String>,
     pub final_def_time: DateTime<Utc>,
-    pub final_def_attachment: String,
     pub final_def_outcome: Option<bool>,
     pub final_def_comment: Option<String>,
     pub final_def_grade: Option<BigDecimal>,
+    pub final_def_version: i32,
+    pub final_def_due_time: Option<DateTime<Utc>>,
+}
+
+#[derive(
+    Identifiable,
+    Queryable,
+    Selectable,
+    Associations,
+    Debug,
+    PartialEq,
+    Clone,
+    Serialize,
+    Deserialize,
+)]
+#[diesel(primary_key(prog_report_id, attach_id))]
+#[diesel(belongs_to(ProgressReport, foreign_key = prog_report_id))]
+#[diesel(belongs_to(Attachment, foreign_key = attach_id))]
+#[diesel(table_name = crate::schema::progressreportfile)]
+pub struct ProgressReportFile {
+    pub prog_report_id: i32,
+    pub attach_id: i32,
+    pub prog_file_kind: i16,
+}
+
+#[derive(
+    Identifiable,
+    Queryable,
+    Selectable,
+    Associations,
+    Debug,
+    PartialEq,
+    Clone,
+    Serialize,
+    Deserialize,
+)]
+#[diesel(primary_key(final_def_id, attach_id))]
+#[diesel(belongs_to(FinalDefense, foreign_key = final_def_id))]
+#[diesel(belongs_to(Attachment, foreign_key = attach_id))]
+#[diesel(table_name = crate::schema::finaldefensefile)]
+pub struct FinalDefenseFile {
+    pub final_def_id: i32,
+    pub attach_id: i32,
+    pub def_file_kind: i16,
+}
+
+#[derive(
+    Identifiable,
+    Queryable,
+    Selectable,
+    Associations,
+    Debug,
+    PartialEq,
+    Clone,
+    Serialize,
+    Deserialize,
+)]
+#[diesel(primary_key(deadline_id))]
+#[diesel(belongs_to(Major, foreign_key = major_id))]
+#[diesel(table_name = crate::schema::submissiondeadline)]
+pub struct SubmissionDeadline {
+    pub deadline_id: i32,
+    pub major_id: i32,
+    pub stage_id: Option<i16>,
+    pub deadline_due_time: DateTime<Utc>,
+    pub deadline_is_hard: bool,
+}
+
+#[derive(AsChangeset, Debug, Clone)]
+#[diesel(table_name = crate::schema::submissiondeadline)]
+pub struct SubmissionDeadlineChangeset {
+    pub deadline_due_time: Option<DateTime<Utc>>,
+    pub deadline_is_hard: Option<bool>,
+}
+
+#[derive(
+    Identifiable,
+    Queryable,
+    Selectable,
+    Associations,
+    Debug,
+    PartialEq,
+    Clone,
+    Serialize,
+    Deserialize,
+)]
+#[diesel(primary_key(deadline_id, student_user_name))]
+#[diesel(belongs_to(SubmissionDeadline, foreign_key = deadline_id))]
+#[diesel(belongs_to(Student, foreign_key = student_user_name))]
+#[diesel(table_name = crate::schema::deadlineextension)]
+pub struct DeadlineExtension {
+    pub deadline_id: i32,
+    pub student_user_name: String,
+    pub ext_due_time: DateTime<Utc>,
+}
+
+#[derive(Identifiable, Queryable, Selectable, Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[diesel(primary_key(crit_id))]
+#[diesel(table_name = crate::schema::rubriccriterion)]
+pub struct RubricCriterion {
+    pub crit_id: i32,
+    pub stage_id: Option<i16>,
+    pub crit_name: String,
+    pub crit_order: i16,
+    pub crit_weight: BigDecimal,
+    pub crit_max_score: BigDecimal,
+}
+
+#[derive(
+    Identifiable,
+    Queryable,
+    Selectable,
+    Associations,
+    Debug,
+    PartialEq,
+    Clone,
+    Serialize,
+    Deserialize,
+)]
+#[diesel(primary_key(prog_report_id, crit_id))]
+#[diesel(belongs_to(ProgressReport, foreign_key = prog_report_id))]
+#[diesel(belongs_to(RubricCriterion, foreign_key = crit_id))]
+#[diesel(table_name = crate::schema::progressreportscore)]
+pub struct ProgressReportScore {
+    pub prog_report_id: i32,
+    pub crit_id: i32,
+    pub prog_score: BigDecimal,
+}
+
+#[derive(
+    Identifiable,
+    Queryable,
+    Selectable,
+    Associations,
+    Debug,
+    PartialEq,
+    Clone,
+    Serialize,
+    Deserialize,
+)]
+#[diesel(primary_key(final_def_id, crit_id))]
+#[diesel(belongs_to(FinalDefense, foreign_key = final_def_id))]
+#[diesel(belongs_to(RubricCriterion, foreign_key = crit_id))]
+#[diesel(table_name = crate::schema::finaldefensescore)]
+pub struct FinalDefenseScore {
+    pub final_def_id: i32,
+    pub crit_id: i32,
+    pub def_score: BigDecimal,
+}
+
+#[derive(Identifiable, Queryable, Selectable, Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[diesel(primary_key(comment_id))]
+#[diesel(table_name = crate::schema::submissioncomment)]
+pub struct SubmissionComment {
+    pub comment_id: i32,
+    pub prog_report_id: Option<i32>,
+    pub final_def_id: Option<i32>,
+    pub comment_parent_id: Option<i32>,
+    pub author_user_name: String,
+    pub comment_author_role: i16,
+    pub comment_content: String,
+    pub comment_time: DateTime<Utc>,
+    pub attach_id: Option<i32>,
+}
+
+#[derive(Identifiable, Queryable, Selectable, Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[diesel(primary_key(major_id))]
+#[diesel(table_name = crate::schema::revisionlock)]
+pub struct RevisionLock {
+    pub major_id: i32,
+    pub rev_lock_time: DateTime<Utc>,
+}
+
+#[derive(
+    Identifiable,
+    Queryable,
+    Selectable,
+    Associations,
+    Debug,
+    PartialEq,
+    Clone,
+    Serialize,
+    Deserialize,
+)]
+#[diesel(primary_key(attach_id))]
+#[diesel(belongs_to(Attachment, foreign_key = attach_id))]
+#[diesel(table_name = crate::schema::attachmenttext)]
+pub struct AttachmentText {
+    pub attach_id: i32,
+    /// Text extracted from the attachment, or `None` if it could not be read
+    pub attach_text: Option<String>,
+    pub attach_text_time: DateTime<Utc>,
+}
+
+#[derive(Identifiable, Queryable, Selectable, Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[diesel(primary_key(rev_id))]
+#[diesel(table_name = crate::schema::progressreportrevision)]
+pub struct ProgressReportRevision {
+    pub rev_id: i32,
+    pub prog_report_id: i32,
+    pub rev_user_name: String,
+    pub rev_cause_id: Option<i32>,
+    pub rev_reason: String,
+    pub rev_time: DateTime<Utc>,
+    pub rev_old_outcome: i16,
+    pub rev_old_comment: Option<String>,
+    pub rev_old_grade: Option<BigDecimal>,
+    pub rev_new_outcome: i16,
+    pub rev_new_comment: Option<String>,
+    pub rev_new_grade: Option<BigDecimal>,
 }
 
 // Insertable structs for creating new records
@@ -266,6 +814,14 @@ pub struct NewMajor<'a> {
 pub struct NewTeacher<'a> {
     pub user_name: &'a str,
     pub teacher_name: &'a str,
+    pub teacher_title: i16,
+}
+
+#[derive(Insertable, AsChangeset, Debug)]
+#[diesel(table_name = crate::schema::supervisionquota)]
+pub struct NewSupervisionQuota {
+    pub teacher_title: i16,
+    pub quota_max_students: i32,
 }
 
 #[derive(Insertable, Debug)]
@@ -298,6 +854,139 @@ pub struct NewTopic<'a> {
     pub topic_review_status: i16,
 }
 
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::tag)]
+pub struct NewTag<'a> {
+    pub tag_name: &'a str,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::topictag)]
+pub struct NewTopicTag {
+    pub topic_id: i32,
+    pub tag_id: i32,
+}
+
+#[derive(Insertable, AsChangeset, Debug)]
+#[diesel(table_name = crate::schema::topiccosupervisor)]
+pub struct NewTopicCoSupervisor<'a> {
+    pub topic_id: i32,
+    pub teacher_user_name: &'a str,
+    pub cosup_can_approve: bool,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::topicproposal)]
+pub struct NewTopicProposal<'a> {
+    pub student_user_name: &'a str,
+    pub teacher_user_name: &'a str,
+    pub prop_topic_name: &'a str,
+    pub prop_topic_description: &'a str,
+    pub prop_topic_type: i16,
+    pub prop_status: i16,
+    pub prop_time: DateTime<Utc>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::matchinground)]
+pub struct NewMatchingRound<'a> {
+    pub match_round_name: &'a str,
+    pub match_round_max_prefs: i16,
+    pub match_round_open_time: DateTime<Utc>,
+    pub match_round_close_time: DateTime<Utc>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::topicpreference)]
+pub struct NewTopicPreference<'a> {
+    pub match_round_id: i32,
+    pub student_user_name: &'a str,
+    pub topic_id: i32,
+    pub pref_rank: i16,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::applicantrank)]
+pub struct NewApplicantRank<'a> {
+    pub match_round_id: i32,
+    pub topic_id: i32,
+    pub student_user_name: &'a str,
+    pub appl_rank: i32,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::selectionround)]
+pub struct NewSelectionRound<'a> {
+    pub sel_round_prev_id: Option<i32>,
+    pub sel_round_name: &'a str,
+    pub sel_round_open_time: DateTime<Utc>,
+    pub sel_round_close_time: DateTime<Utc>,
+    pub sel_round_max_requests: i16,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::reportstage)]
+pub struct NewReportStage<'a> {
+    pub stage_name: &'a str,
+    pub stage_order: i32,
+    pub stage_reviewer: i16,
+    pub stage_grade_required: bool,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::reportstageprerequisite)]
+pub struct NewReportStagePrerequisite {
+    pub stage_id: i16,
+    pub prereq_stage_id: i16,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::selectionroundmajor)]
+pub struct NewSelectionRoundMajor {
+    pub sel_round_id: i32,
+    pub major_id: i32,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::notification)]
+pub struct NewNotification<'a> {
+    pub user_name: &'a str,
+    pub notif_content: String,
+    pub notif_time: DateTime<Utc>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::assignmentchange)]
+pub struct NewAssignmentChange<'a> {
+    pub student_user_name: &'a str,
+    pub office_user_name: &'a str,
+    pub assn_change_old_topic_id: Option<i32>,
+    pub assn_change_new_topic_id: Option<i32>,
+    pub assn_change_reason: &'a str,
+    pub assn_change_time: DateTime<Utc>,
+    pub assn_change_overrode_capacity: bool,
+    pub assn_change_moved_records: bool,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::attachment)]
+pub struct NewAttachment<'a> {
+    pub uploader_user_name: &'a str,
+    pub attach_file_name: &'a str,
+    pub attach_mime_type: &'a str,
+    pub attach_size: i64,
+    pub attach_sha256: &'a str,
+    pub attach_upload_time: DateTime<Utc>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::topicwaitlist)]
+pub struct NewTopicWaitlist<'a> {
+    pub topic_id: i32,
+    pub student_user_name: &'a str,
+    pub waitlist_time: DateTime<Utc>,
+}
+
 #[derive(Insertable, Debug)]
 #[diesel(table_name = crate::schema::student)]
 pub struct NewStudent<'a> {
@@ -314,6 +1003,7 @@ pub struct NewAssignmentRequest<'a> {
     pub student_user_name: &'a str,
     pub topic_id: i32,
     pub assn_req_time: DateTime<Utc>,
+    pub sel_round_id: Option<i32>,
 }
 
 #[derive(Insertable, Debug)]
@@ -321,12 +1011,13 @@ pub struct NewAssignmentRequest<'a> {
 pub struct NewProgressReport<'a> {
     pub topic_id: i32,
     pub student_user_name: &'a str,
-    pub prog_report_type: i16,
+    pub prog_report_stage_id: i16,
     pub prog_report_time: DateTime<Utc>,
-    pub prog_report_attachment: &'a str,
     pub prog_report_outcome: i16,
     pub prog_report_comment: Option<&'a str>,
     pub prog_report_grade: Option<BigDecimal>,
+    pub prog_report_version: i32,
+    pub prog_report_due_time: Option<DateTime<Utc>>,
 }
 
 #[derive(Insertable, Debug)]
@@ -336,8 +1027,112 @@ pub struct NewFinalDefense<'a> {
     pub student_user_name: &'a str,
     pub def_board_user_name: Option<&'a str>,
     pub final_def_time: DateTime<Utc>,
-    pub final_def_attachment: &'a str,
     pub final_def_outcome: Option<bool>,
     pub final_def_comment: Option<&'a str>,
     pub final_def_grade: Option<BigDecimal>,
+    pub final_def_version: i32,
+    pub final_def_due_time: Option<DateTime<Utc>>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::progressreportfile)]
+pub struct NewProgressReportFile {
+    pub prog_report_id: i32,
+    pub attach_id: i32,
+    pub prog_file_kind: i16,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::finaldefensefile)]
+pub struct NewFinalDefenseFile {
+    pub final_def_id: i32,
+    pub attach_id: i32,
+    pub def_file_kind: i16,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::submissiondeadline)]
+pub struct NewSubmissionDeadline {
+    pub major_id: i32,
+    pub stage_id: Option<i16>,
+    pub deadline_due_time: DateTime<Utc>,
+    pub deadline_is_hard: bool,
+}
+
+#[derive(Insertable, AsChangeset, Debug)]
+#[diesel(table_name = crate::schema::deadlineextension)]
+pub struct NewDeadlineExtension<'a> {
+    pub deadline_id: i32,
+    pub student_user_name: &'a str,
+    pub ext_due_time: DateTime<Utc>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::rubriccriterion)]
+pub struct NewRubricCriterion<'a> {
+    pub stage_id: Option<i16>,
+    pub crit_name: &'a str,
+    pub crit_order: i16,
+    pub crit_weight: BigDecimal,
+    pub crit_max_score: BigDecimal,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::progressreportscore)]
+pub struct NewProgressReportScore {
+    pub prog_report_id: i32,
+    pub crit_id: i32,
+    pub prog_score: BigDecimal,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::finaldefensescore)]
+pub struct NewFinalDefenseScore {
+    pub final_def_id: i32,
+    pub crit_id: i32,
+    pub def_score: BigDecimal,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::submissioncomment)]
+pub struct NewSubmissionComment<'a> {
+    pub prog_report_id: Option<i32>,
+    pub final_def_id: Option<i32>,
+    pub comment_parent_id: Option<i32>,
+    pub author_user_name: &'a str,
+    pub comment_author_role: i16,
+    pub comment_content: &'a str,
+    pub comment_time: DateTime<Utc>,
+    pub attach_id: Option<i32>,
+}
+
+#[derive(Insertable, AsChangeset, Debug)]
+#[diesel(table_name = crate::schema::revisionlock)]
+pub struct NewRevisionLock {
+    pub major_id: i32,
+    pub rev_lock_time: DateTime<Utc>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::progressreportrevision)]
+pub struct NewProgressReportRevision<'a> {
+    pub prog_report_id: i32,
+    pub rev_user_name: &'a str,
+    pub rev_cause_id: Option<i32>,
+    pub rev_reason: &'a str,
+    pub rev_time: DateTime<Utc>,
+    pub rev_old_outcome: i16,
+    pub rev_old_comment: Option<String>,
+    pub rev_old_grade: Option<BigDecimal>,
+    pub rev_new_outcome: i16,
+    pub rev_new_comment: Option<String>,
+    pub rev_new_grade: Option<BigDecimal>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::attachmenttext)]
+pub struct NewAttachmentText<'a> {
+    pub attach_id: i32,
+    pub attach_text: Option<&'a str>,
+    pub attach_text_time: DateTime<Utc>,
 }
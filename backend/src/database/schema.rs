@@ -1,11 +1,78 @@
 // @generated automatically by Diesel CLI.
 
 diesel::table! {
-    assignmentrequest (student_user_name, topic_id) {
+    applicantrank (match_round_id, topic_id, student_user_name) {
+        match_round_id -> Int4,
+        topic_id -> Int4,
+        #[max_length = 16]
+        student_user_name -> Varchar,
+        appl_rank -> Int4,
+    }
+}
+
+diesel::table! {
+    assignmentchange (assn_change_id) {
+        assn_change_id -> Int4,
+        #[max_length = 16]
+        student_user_name -> Varchar,
+        #[max_length = 16]
+        office_user_name -> Varchar,
+        assn_change_old_topic_id -> Nullable<Int4>,
+        assn_change_new_topic_id -> Nullable<Int4>,
+        assn_change_reason -> Text,
+        assn_change_time -> Timestamptz,
+        assn_change_overrode_capacity -> Bool,
+        assn_change_moved_records -> Bool,
+    }
+}
+
+diesel::table! {
+    assignmentrequest (assn_req_id) {
         #[max_length = 16]
         student_user_name -> Varchar,
         topic_id -> Int4,
         assn_req_time -> Timestamptz,
+        sel_round_id -> Nullable<Int4>,
+        assn_req_id -> Int4,
+        assn_req_status -> Int2,
+        assn_req_decided_time -> Nullable<Timestamptz>,
+        #[max_length = 16]
+        assn_req_decided_by -> Nullable<Varchar>,
+        assn_req_reason -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    attachment (attach_id) {
+        attach_id -> Int4,
+        #[max_length = 16]
+        uploader_user_name -> Varchar,
+        #[max_length = 255]
+        attach_file_name -> Varchar,
+        #[max_length = 128]
+        attach_mime_type -> Varchar,
+        attach_size -> Int8,
+        #[max_length = 64]
+        attach_sha256 -> Bpchar,
+        attach_upload_time -> Timestamptz,
+        attach_legacy_content -> Nullable<Bytea>,
+    }
+}
+
+diesel::table! {
+    attachmenttext (attach_id) {
+        attach_id -> Int4,
+        attach_text -> Nullable<Text>,
+        attach_text_time -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    deadlineextension (deadline_id, student_user_name) {
+        deadline_id -> Int4,
+        #[max_length = 16]
+        student_user_name -> Varchar,
+        ext_due_time -> Timestamptz,
     }
 }
 
@@ -25,10 +92,27 @@ diesel::table! {
         #[max_length = 16]
         def_board_user_name -> Nullable<Varchar>,
         final_def_time -> Timestamptz,
-        final_def_attachment -> Text,
         final_def_outcome -> Nullable<Bool>,
         final_def_comment -> Nullable<Text>,
         final_def_grade -> Nullable<Numeric>,
+        final_def_version -> Int4,
+        final_def_due_time -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    finaldefensefile (final_def_id, attach_id) {
+        final_def_id -> Int4,
+        attach_id -> Int4,
+        def_file_kind -> Int2,
+    }
+}
+
+diesel::table! {
+    finaldefensescore (final_def_id, crit_id) {
+        final_def_id -> Int4,
+        crit_id -> Int4,
+        def_score -> Numeric,
     }
 }
 
@@ -40,6 +124,29 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    matchinground (match_round_id) {
+        match_round_id -> Int4,
+        #[max_length = 64]
+        match_round_name -> Varchar,
+        match_round_max_prefs -> Int2,
+        match_round_open_time -> Timestamptz,
+        match_round_close_time -> Timestamptz,
+        match_round_commit_time -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    notification (notif_id) {
+        notif_id -> Int4,
+        #[max_length = 16]
+        user_name -> Varchar,
+        notif_content -> Text,
+        notif_time -> Timestamptz,
+        notif_read_time -> Nullable<Timestamptz>,
+    }
+}
+
 diesel::table! {
     office (user_name) {
         #[max_length = 16]
@@ -53,12 +160,103 @@ diesel::table! {
         topic_id -> Int4,
         #[max_length = 16]
         student_user_name -> Varchar,
-        prog_report_type -> Int2,
+        prog_report_stage_id -> Int2,
         prog_report_time -> Timestamptz,
-        prog_report_attachment -> Text,
         prog_report_outcome -> Int2,
         prog_report_comment -> Nullable<Text>,
         prog_report_grade -> Nullable<Numeric>,
+        prog_report_version -> Int4,
+        prog_report_due_time -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    progressreportfile (prog_report_id, attach_id) {
+        prog_report_id -> Int4,
+        attach_id -> Int4,
+        prog_file_kind -> Int2,
+    }
+}
+
+diesel::table! {
+    progressreportrevision (rev_id) {
+        rev_id -> Int4,
+        prog_report_id -> Int4,
+        #[max_length = 16]
+        rev_user_name -> Varchar,
+        rev_cause_id -> Nullable<Int4>,
+        rev_reason -> Text,
+        rev_time -> Timestamptz,
+        rev_old_outcome -> Int2,
+        rev_old_comment -> Nullable<Text>,
+        rev_old_grade -> Nullable<Numeric>,
+        rev_new_outcome -> Int2,
+        rev_new_comment -> Nullable<Text>,
+        rev_new_grade -> Nullable<Numeric>,
+    }
+}
+
+diesel::table! {
+    progressreportscore (prog_report_id, crit_id) {
+        prog_report_id -> Int4,
+        crit_id -> Int4,
+        prog_score -> Numeric,
+    }
+}
+
+diesel::table! {
+    reportstage (stage_id) {
+        stage_id -> Int2,
+        #[max_length = 32]
+        stage_name -> Varchar,
+        stage_order -> Int4,
+        stage_reviewer -> Int2,
+        stage_grade_required -> Bool,
+    }
+}
+
+diesel::table! {
+    reportstageprerequisite (stage_id, prereq_stage_id) {
+        stage_id -> Int2,
+        prereq_stage_id -> Int2,
+    }
+}
+
+diesel::table! {
+    revisionlock (major_id) {
+        major_id -> Int4,
+        rev_lock_time -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    rubriccriterion (crit_id) {
+        crit_id -> Int4,
+        stage_id -> Nullable<Int2>,
+        #[max_length = 64]
+        crit_name -> Varchar,
+        crit_order -> Int2,
+        crit_weight -> Numeric,
+        crit_max_score -> Numeric,
+    }
+}
+
+diesel::table! {
+    selectionround (sel_round_id) {
+        sel_round_id -> Int4,
+        sel_round_prev_id -> Nullable<Int4>,
+        #[max_length = 64]
+        sel_round_name -> Varchar,
+        sel_round_open_time -> Timestamptz,
+        sel_round_close_time -> Timestamptz,
+        sel_round_max_requests -> Int2,
+    }
+}
+
+diesel::table! {
+    selectionroundmajor (sel_round_id, major_id) {
+        sel_round_id -> Int4,
+        major_id -> Int4,
     }
 }
 
@@ -74,6 +272,38 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    submissioncomment (comment_id) {
+        comment_id -> Int4,
+        prog_report_id -> Nullable<Int4>,
+        final_def_id -> Nullable<Int4>,
+        comment_parent_id -> Nullable<Int4>,
+        #[max_length = 16]
+        author_user_name -> Varchar,
+        comment_author_role -> Int2,
+        comment_content -> Text,
+        comment_time -> Timestamptz,
+        attach_id -> Nullable<Int4>,
+    }
+}
+
+diesel::table! {
+    submissiondeadline (deadline_id) {
+        deadline_id -> Int4,
+        major_id -> Int4,
+        stage_id -> Nullable<Int2>,
+        deadline_due_time -> Timestamptz,
+        deadline_is_hard -> Bool,
+    }
+}
+
+diesel::table! {
+    supervisionquota (teacher_title) {
+        teacher_title -> Int2,
+        quota_max_students -> Int4,
+    }
+}
+
 diesel::table! {
     sysadmin (user_name) {
         #[max_length = 16]
@@ -91,12 +321,21 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    tag (tag_id) {
+        tag_id -> Int4,
+        #[max_length = 32]
+        tag_name -> Varchar,
+    }
+}
+
 diesel::table! {
     teacher (user_name) {
         #[max_length = 16]
         user_name -> Varchar,
         #[max_length = 16]
         teacher_name -> Varchar,
+        teacher_title -> Int2,
     }
 }
 
@@ -112,36 +351,165 @@ diesel::table! {
         topic_max_students -> Int4,
         topic_type -> Int2,
         topic_review_status -> Int2,
+        topic_version -> Int4,
+        topic_review_comment -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    topiccosupervisor (topic_id, teacher_user_name) {
+        topic_id -> Int4,
+        #[max_length = 16]
+        teacher_user_name -> Varchar,
+        cosup_can_approve -> Bool,
+    }
+}
+
+diesel::table! {
+    topicpreference (match_round_id, student_user_name, topic_id) {
+        match_round_id -> Int4,
+        #[max_length = 16]
+        student_user_name -> Varchar,
+        topic_id -> Int4,
+        pref_rank -> Int2,
+    }
+}
+
+diesel::table! {
+    topicproposal (prop_id) {
+        prop_id -> Int4,
+        #[max_length = 16]
+        student_user_name -> Varchar,
+        #[max_length = 16]
+        teacher_user_name -> Varchar,
+        topic_id -> Nullable<Int4>,
+        #[max_length = 128]
+        prop_topic_name -> Varchar,
+        prop_topic_description -> Text,
+        prop_topic_type -> Int2,
+        prop_status -> Int2,
+        prop_time -> Timestamptz,
+        prop_decided_time -> Nullable<Timestamptz>,
+        prop_decline_reason -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    topictag (topic_id, tag_id) {
+        topic_id -> Int4,
+        tag_id -> Int4,
+    }
+}
+
+diesel::table! {
+    topicwaitlist (waitlist_id) {
+        waitlist_id -> Int4,
+        topic_id -> Int4,
+        #[max_length = 16]
+        student_user_name -> Varchar,
+        waitlist_time -> Timestamptz,
     }
 }
 
+diesel::joinable!(applicantrank -> matchinground (match_round_id));
+diesel::joinable!(applicantrank -> student (student_user_name));
+diesel::joinable!(applicantrank -> topic (topic_id));
+diesel::joinable!(assignmentchange -> office (office_user_name));
+diesel::joinable!(assignmentchange -> student (student_user_name));
+diesel::joinable!(assignmentrequest -> selectionround (sel_round_id));
 diesel::joinable!(assignmentrequest -> student (student_user_name));
+diesel::joinable!(assignmentrequest -> teacher (assn_req_decided_by));
 diesel::joinable!(assignmentrequest -> topic (topic_id));
+diesel::joinable!(attachment -> sysuser (uploader_user_name));
+diesel::joinable!(attachmenttext -> attachment (attach_id));
+diesel::joinable!(deadlineextension -> student (student_user_name));
+diesel::joinable!(deadlineextension -> submissiondeadline (deadline_id));
 diesel::joinable!(defenseboard -> sysuser (user_name));
 diesel::joinable!(finaldefense -> defenseboard (def_board_user_name));
 diesel::joinable!(finaldefense -> student (student_user_name));
 diesel::joinable!(finaldefense -> topic (topic_id));
+diesel::joinable!(finaldefensefile -> attachment (attach_id));
+diesel::joinable!(finaldefensefile -> finaldefense (final_def_id));
+diesel::joinable!(finaldefensescore -> finaldefense (final_def_id));
+diesel::joinable!(finaldefensescore -> rubriccriterion (crit_id));
+diesel::joinable!(notification -> sysuser (user_name));
 diesel::joinable!(office -> sysuser (user_name));
+diesel::joinable!(progressreport -> reportstage (prog_report_stage_id));
 diesel::joinable!(progressreport -> student (student_user_name));
 diesel::joinable!(progressreport -> topic (topic_id));
+diesel::joinable!(progressreportfile -> attachment (attach_id));
+diesel::joinable!(progressreportfile -> progressreport (prog_report_id));
+diesel::joinable!(progressreportrevision -> progressreport (prog_report_id));
+diesel::joinable!(progressreportrevision -> sysuser (rev_user_name));
+diesel::joinable!(progressreportscore -> progressreport (prog_report_id));
+diesel::joinable!(progressreportscore -> rubriccriterion (crit_id));
+diesel::joinable!(revisionlock -> major (major_id));
+diesel::joinable!(rubriccriterion -> reportstage (stage_id));
+diesel::joinable!(selectionroundmajor -> major (major_id));
+diesel::joinable!(selectionroundmajor -> selectionround (sel_round_id));
 diesel::joinable!(student -> major (major_id));
 diesel::joinable!(student -> sysuser (user_name));
 diesel::joinable!(student -> topic (topic_id));
+diesel::joinable!(submissioncomment -> attachment (attach_id));
+diesel::joinable!(submissioncomment -> finaldefense (final_def_id));
+diesel::joinable!(submissioncomment -> progressreport (prog_report_id));
+diesel::joinable!(submissioncomment -> sysuser (author_user_name));
+diesel::joinable!(submissiondeadline -> major (major_id));
+diesel::joinable!(submissiondeadline -> reportstage (stage_id));
 diesel::joinable!(sysadmin -> sysuser (user_name));
 diesel::joinable!(teacher -> sysuser (user_name));
 diesel::joinable!(topic -> major (major_id));
 diesel::joinable!(topic -> teacher (teacher_user_name));
+diesel::joinable!(topiccosupervisor -> teacher (teacher_user_name));
+diesel::joinable!(topiccosupervisor -> topic (topic_id));
+diesel::joinable!(topicpreference -> matchinground (match_round_id));
+diesel::joinable!(topicpreference -> student (student_user_name));
+diesel::joinable!(topicpreference -> topic (topic_id));
+diesel::joinable!(topicproposal -> student (student_user_name));
+diesel::joinable!(topicproposal -> teacher (teacher_user_name));
+diesel::joinable!(topicproposal -> topic (topic_id));
+diesel::joinable!(topictag -> tag (tag_id));
+diesel::joinable!(topictag -> topic (topic_id));
+diesel::joinable!(topicwaitlist -> student (student_user_name));
+diesel::joinable!(topicwaitlist -> topic (topic_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
+    applicantrank,
+    assignmentchange,
     assignmentrequest,
+    attachment,
+    attachmenttext,
+    deadlineextension,
     defenseboard,
     finaldefense,
+    finaldefensefile,
+    finaldefensescore,
     major,
+    matchinground,
+    notification,
     office,
     progressreport,
+    progressreportfile,
+    progressreportrevision,
+    progressreportscore,
+    reportstage,
+    reportstageprerequisite,
+    revisionlock,
+    rubriccriterion,
+    selectionround,
+    selectionroundmajor,
     student,
+    submissioncomment,
+    submissiondeadline,
+    supervisionquota,
     sysadmin,
     sysuser,
+    tag,
     teacher,
     topic,
+    topiccosupervisor,
+    topicpreference,
+    topicproposal,
+    topictag,
+    topicwaitlist,
 );
@@ -0,0 +1,30 @@
+//! Views defined in migrations. Diesel CLI does not print views, so they are kept by hand here.
+
+use crate::schema::topiccosupervisor;
+
+diesel::table! {
+    /// Assignment requests that are not approved, together with current student assignments.
+    assignmentview (assn_key) {
+        assn_key -> Text,
+        #[max_length = 16]
+        student_user_name -> Varchar,
+        #[max_length = 16]
+        student_name -> Varchar,
+        major_id -> Int4,
+        #[max_length = 16]
+        major_name -> Varchar,
+        topic_id -> Int4,
+        #[max_length = 128]
+        topic_name -> Varchar,
+        #[max_length = 16]
+        teacher_user_name -> Varchar,
+        assn_request_time -> Timestamptz,
+        assn_status -> Int2,
+        assn_decided_time -> Nullable<Timestamptz>,
+        #[max_length = 16]
+        assn_decided_by -> Nullable<Varchar>,
+        assn_reason -> Nullable<Text>,
+    }
+}
+
+diesel::allow_tables_to_appear_in_same_query!(assignmentview, topiccosupervisor);
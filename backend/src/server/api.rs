@@ -1,16 +1,23 @@
+use actix_files::NamedFile;
+use actix_multipart::form::MultipartForm;
 use actix_session::Session;
-use actix_web::{HttpResponse, get, patch, post, web};
+use actix_web::http::header::{
+    Charset, ContentDisposition, DispositionParam, DispositionType, ExtendedValue,
+};
+use actix_web::{HttpRequest, HttpResponse, delete, get, patch, post, put, web};
 use backend_database::DbPool;
 use backend_database::model::*;
-use chrono::Utc;
+use bigdecimal::{BigDecimal, RoundingMode};
+use chrono::{DateTime, Utc};
 use diesel::prelude::*;
 use serde::Deserialize;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use str_macro::str;
 
 use crate::auth::*;
 use crate::dto::*;
 use crate::map_schema_role;
+use crate::matching::deferred_acceptance;
 
 #[derive(Debug, Deserialize)]
 struct PaginationQuery {
@@ -19,624 +26,7472 @@ struct PaginationQuery {
 }
 
 #[derive(Debug, Deserialize)]
-struct SearchQuery {
-    pub keyword: Option<String>,
+struct TopicsQuery {
+    /// Comma-separated tag names; only topics carrying all of them are returned
+    pub tags: Option<String>,
     pub page: Option<i64>,
     pub page_size: Option<i64>,
 }
 
-#[get("/ping")]
-pub async fn ping() -> HttpResponse {
-    HttpResponse::Ok().json(str!("pong"))
+#[derive(Debug, Deserialize)]
+struct AssignmentsQuery {
+    pub page: Option<i64>,
+    pub page_size: Option<i64>,
+    pub status: Option<AssignmentStatus>,
+    pub topic_id: Option<i32>,
+    pub major_id: Option<i32>,
+    /// Part of the student's name, matched case-insensitively
+    pub student_name: Option<String>,
+    /// `asc` or `desc` by request time; newest first by default
+    pub order: Option<String>,
 }
 
-#[post("/login")]
-pub async fn login(
-    pool: web::Data<DbPool>,
-    session: Session,
-    req: web::Json<LoginRequest>,
-) -> Result<HttpResponse, ApiError> {
-    use backend_database::schema::*;
+#[derive(Debug, Deserialize)]
+struct ProgressReportsQuery {
+    pub page: Option<i64>,
+    pub page_size: Option<i64>,
+    pub major_id: Option<i32>,
+    /// Supervisor of the report's topic
+    pub teacher_user_name: Option<String>,
+    pub stage_id: Option<i16>,
+    pub outcome: Option<ProgressOutcome>,
+    /// Only reports still awaiting review if true, or only concluded ones if false
+    pub pending: Option<bool>,
+    /// Part of the student's name or username, matched case-insensitively
+    pub student: Option<String>,
+    /// `asc` or `desc` by submission time; newest first by default
+    pub order: Option<String>,
+}
 
-    if is_session_authed(&session) {
-        return Err(ApiError::BadRequest(str!("Already logged in")));
-    }
+#[derive(Debug, Deserialize)]
+struct FinalDefensesQuery {
+    pub page: Option<i64>,
+    pub page_size: Option<i64>,
+    /// Only passed defenses if true, or only failed ones if false
+    pub outcome: Option<bool>,
+    /// Only defenses still awaiting a decision if true, or only decided ones if false
+    pub pending: Option<bool>,
+    /// Part of the student's name or username, matched case-insensitively
+    pub student: Option<String>,
+    /// `asc` or `desc` by submission time; newest first by default
+    pub order: Option<String>,
+}
 
-    let mut conn = pool
-        .get()
-        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
-    let user = sysuser::dsl::sysuser
-        .filter(sysuser::columns::user_name.eq(&req.username))
-        .first::<SysUser>(&mut conn)
-        .map_err(|_| ApiError::Unauthorized)?;
+#[derive(Debug, Deserialize)]
+struct SimilarityQuery {
+    /// Report stage to check; final defenses are checked when absent
+    pub stage_id: Option<i16>,
+    pub major_id: Option<i32>,
+    /// Smallest similarity reported, from 0 to 1
+    pub threshold: Option<f64>,
+}
 
-    let password_good = verify_password(
-        &req.password,
-        &user.user_password_hash,
-        &user.user_password_salt,
-    )
-    .map_err(|_| ApiError::InternalServerError(str!("Failed to verify password")))?;
+#[derive(Debug, Deserialize)]
+struct DeadlinesQuery {
+    pub major_id: Option<i32>,
+}
 
-    if password_good {
-        let auth_info = map_schema_role!(
-            &mut conn, &user.user_name, Err(ApiError::InternalServerError(str!("User not in any role"))),
-            sysadmin::dsl::sysadmin => SysAdmin => Ok(AuthInfo::SysAdmin {
-                username: user.user_name,
-                impersonating: None,
-            });
-            student::dsl::student => Student => Ok(AuthInfo::User {
-                username: user.user_name,
-                role: AuthInfoUserRole::Student,
-            });
-            teacher::dsl::teacher => Teacher => Ok(AuthInfo::User {
-                username: user.user_name,
-                role: AuthInfoUserRole::Teacher,
-            });
-            defenseboard::dsl::defenseboard => DefenseBoard => Ok(AuthInfo::User {
-                username: user.user_name,
-                role: AuthInfoUserRole::DefenseBoard,
-            });
-            office::dsl::office => Office => Ok(AuthInfo::User {
-                username: user.user_name,
-                role: AuthInfoUserRole::Office,
-            });
-        )?;
+#[derive(Debug, Deserialize)]
+struct SearchQuery {
+    pub keyword: Option<String>,
+    /// Comma-separated tag names; only topics carrying all of them are returned
+    pub tags: Option<String>,
+    pub page: Option<i64>,
+    pub page_size: Option<i64>,
+}
 
-        session
-            .insert(AUTH_INFO_SESSION_KEY, &auth_info)
-            .map_err(|_| {
-                ApiError::InternalServerError(str!("Failed to store session information"))
-            })?;
+/// Maximum number of tags a single topic can carry.
+const MAX_TAGS_PER_TOPIC: usize = 10;
 
-        Ok(HttpResponse::Ok().finish())
-    } else {
-        Err(ApiError::Unauthorized)
-    }
-}
+/// Maximum length of a tag name, in characters.
+const MAX_TAG_NAME_LEN: usize = 32;
 
-#[post("/logout")]
-pub async fn logout(session: Session) -> Result<HttpResponse, ApiError> {
-    if session.contains_key(AUTH_INFO_SESSION_KEY) {
-        session.purge();
-        Ok(HttpResponse::Ok().finish())
-    } else {
-        Err(ApiError::Unauthorized)
-    }
-}
+/// Maximum number of decisions accepted by a single batch topic review.
+const MAX_TOPIC_REVIEW_BATCH: usize = 500;
 
-#[get("/user")]
-pub async fn get_current_user(
-    pool: web::Data<DbPool>,
-    session: Session,
-) -> Result<HttpResponse, ApiError> {
-    use backend_database::schema::*;
+/// Maximum number of criteria in a grading rubric.
+const MAX_RUBRIC_CRITERIA: usize = 20;
 
-    if !is_session_authed(&session) {
-        return Err(ApiError::Unauthorized);
-    }
+/// Maximum length of a rubric criterion name, in characters.
+const MAX_CRITERION_NAME_LEN: usize = 64;
 
-    let username = get_session_username(&session)
-        .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
+/// Maximum length of a comment on a submission, in characters.
+const MAX_COMMENT_LEN: usize = 2000;
 
-    let mut conn = pool
-        .get()
-        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+/// Maximum length of a proposed topic name, in characters, as stored by the database.
+const MAX_PROPOSAL_NAME_LEN: usize = 128;
 
-    let sys_user = sysuser::dsl::sysuser
-        .find(&username)
-        .first::<SysUser>(&mut conn)
-        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user information")))?;
+/// Maximum length of a proposed topic description, in characters.
+const MAX_PROPOSAL_DESCRIPTION_LEN: usize = 5000;
 
-    // Get user role and name based on role
-    let (role, name) = map_schema_role!(
-        &mut conn, &username, Err(ApiError::InternalServerError(str!("User not in any role"))),
-        sysadmin::dsl::sysadmin => SysAdmin => {
-            Ok((UserRole::Admin, None))
-        };
-        student::dsl::student => Student => {
-            let student = student::dsl::student
-                .find(&username)
-                .first::<Student>(&mut conn)
-                .map_err(|_| ApiError::InternalServerError(str!("Failed to get student information")))?;
-            Ok((UserRole::Student, Some(student.student_name)))
-        };
-        teacher::dsl::teacher => Teacher => {
-            let teacher = teacher::dsl::teacher
-                .find(&username)
-                .first::<Teacher>(&mut conn)
-                .map_err(|_| ApiError::InternalServerError(str!("Failed to get teacher information")))?;
-            Ok((UserRole::Teacher, Some(teacher.teacher_name)))
-        };
-        defenseboard::dsl::defenseboard => DefenseBoard => {
-            Ok((UserRole::DefenseBoard, None))
-        };
-        office::dsl::office => Office => {
-            Ok((UserRole::Office, None))
-        };
-    )?;
+/// Maximum length of a report stage name, in characters, as stored by the database.
+const MAX_STAGE_NAME_CHARS: usize = 32;
 
-    Ok(HttpResponse::Ok().json(UserGetResponse {
-        username: sys_user.user_name.clone(),
-        role,
-        name,
-        avatar: sys_user.user_avatar,
-    }))
+/// Smallest similarity reported by a similarity check unless requested otherwise.
+const DEFAULT_SIMILARITY_THRESHOLD: f64 = 0.3;
+
+type TopicWithTeacherQuery<'a> = diesel::dsl::IntoBoxed<
+    'a,
+    diesel::dsl::InnerJoin<
+        backend_database::schema::topic::table,
+        backend_database::schema::teacher::table,
+    >,
+    diesel::pg::Pg,
+>;
+
+type CoSupervisedTopicIds<'a> = diesel::dsl::Select<
+    diesel::dsl::Filter<
+        backend_database::schema::topiccosupervisor::table,
+        diesel::dsl::Eq<
+            backend_database::schema::topiccosupervisor::columns::teacher_user_name,
+            &'a str,
+        >,
+    >,
+    backend_database::schema::topiccosupervisor::columns::topic_id,
+>;
+
+/// A grade together with the score given to each rubric criterion behind it.
+type RubricGrade = (Option<BigDecimal>, Vec<(i32, BigDecimal)>);
+
+/// The submission a comment thread belongs to.
+#[derive(Debug, Clone, Copy)]
+enum CommentSubject {
+    ProgressReport(i32),
+    FinalDefense(i32),
 }
 
-#[patch("/user")]
-pub async fn update_current_user(
-    pool: web::Data<DbPool>,
-    session: Session,
-    req: web::Json<UserPatchRequest>,
-) -> Result<HttpResponse, ApiError> {
-    use backend_database::schema::*;
+/// Set of topics a user is allowed to see.
+enum TopicScope<'a> {
+    /// Approved topics open to a major, as seen by students.
+    ApprovedForMajor(i32),
+    /// Topics supervised or co-supervised by a teacher.
+    SupervisedBy(&'a str),
+    /// All topics, as seen by office and defense boards.
+    All,
+}
 
-    if !is_session_authed(&session) {
-        return Err(ApiError::Unauthorized);
+/// Parses the `order` of a listing sorted by time, returning whether it is newest first.
+fn is_descending_order(order: Option<&str>) -> Result<bool, ApiError> {
+    match order {
+        None | Some("desc") => Ok(true),
+        Some("asc") => Ok(false),
+        Some(_) => Err(ApiError::BadRequest(str!(
+            "Order must be either asc or desc"
+        ))),
     }
+}
 
-    let username = get_session_username(&session)
-        .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
-
-    let mut conn = pool
-        .get()
-        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+/// Builds the `ILIKE` pattern matching a search term anywhere, or `None` if the term is blank.
+fn search_pattern(term: Option<&str>) -> Option<String> {
+    term.map(str::trim)
+        .filter(|term| !term.is_empty())
+        .map(|term| format!("%{}%", term))
+}
 
-    conn.build_transaction().read_write().run(|conn| {
-        if let Some(ref password) = req.password {
-            let (hash, salt) = hash_password(password)
-                .map_err(|_| ApiError::InternalServerError(str!("Failed to hash password")))?;
+fn get_topic_scope<'a>(
+    conn: &mut PgConnection,
+    user_role: &AuthInfoUserRole,
+    username: &'a str,
+) -> Result<TopicScope<'a>, ApiError> {
+    use backend_database::schema::*;
 
-            diesel::update(sysuser::dsl::sysuser.find(&username))
-                .set(SysUserPasswordChangeset {
-                    user_password_hash: Some(hash),
-                    user_password_salt: Some(salt),
-                })
-                .execute(conn)
+    match user_role {
+        AuthInfoUserRole::Student => {
+            let major_id = student::dsl::student
+                .find(username)
+                .select(student::columns::major_id)
+                .first::<i32>(conn)
                 .map_err(|_| {
-                    ApiError::InternalServerError(str!("Failed to update user information"))
+                    ApiError::InternalServerError(str!("Failed to get student information"))
                 })?;
+            Ok(TopicScope::ApprovedForMajor(major_id))
         }
+        AuthInfoUserRole::Teacher => Ok(TopicScope::SupervisedBy(username)),
+        AuthInfoUserRole::Office | AuthInfoUserRole::DefenseBoard => Ok(TopicScope::All),
+    }
+}
 
-        if let Some(ref avatar) = req.avatar {
-            diesel::update(sysuser::dsl::sysuser.find(&username))
-                .set(sysuser::columns::user_avatar.eq(avatar))
-                .execute(conn)
-                .map_err(|_| ApiError::InternalServerError(str!("Failed to update avatar")))?;
-        }
+/// Builds the query of topics within `scope` that carry all of `tags`, joined with their teachers.
+///
+/// Boxed queries are not `Copy`, so callers build one query for counting and another for loading.
+fn visible_topics_query<'a>(
+    scope: &TopicScope<'a>,
+    tags: &'a [String],
+) -> TopicWithTeacherQuery<'a> {
+    use backend_database::schema::*;
 
-        if let Some(ref name) = req.name {
-            // Is student?
-            if diesel::select(diesel::dsl::exists(student::dsl::student.find(&username)))
-                .get_result::<bool>(conn)
-                .map_err(|_| ApiError::InternalServerError(str!("Failed to check user role")))?
-            {
-                diesel::update(student::dsl::student.find(&username))
-                    .set(student::columns::student_name.eq(name))
-                    .execute(conn)
-                    .map_err(|_| {
-                        ApiError::InternalServerError(str!("Failed to update student name"))
-                    })?;
-            }
-            // Is teacher?
-            else if diesel::select(diesel::dsl::exists(teacher::dsl::teacher.find(&username)))
-                .get_result::<bool>(conn)
-                .map_err(|_| ApiError::InternalServerError(str!("Failed to check user role")))?
-            {
-                diesel::update(teacher::dsl::teacher.find(&username))
-                    .set(teacher::columns::teacher_name.eq(name))
-                    .execute(conn)
-                    .map_err(|_| {
-                        ApiError::InternalServerError(str!("Failed to update teacher name"))
-                    })?;
-            }
+    let mut query = topic::table.inner_join(teacher::table).into_boxed();
+    match *scope {
+        TopicScope::ApprovedForMajor(major_id) => {
+            query = query
+                .filter(topic::columns::major_id.eq(major_id))
+                .filter(topic::columns::topic_review_status.eq(TopicReviewStatus::Approved as i16));
         }
-
-        Ok::<(), ApiError>(())
-    })?;
-
-    Ok(HttpResponse::Ok().finish())
+        TopicScope::SupervisedBy(teacher_user_name) => {
+            query =
+                query.filter(topic::columns::teacher_user_name.eq(teacher_user_name).or(
+                    topic::columns::topic_id.eq_any(cosupervised_topic_ids(teacher_user_name)),
+                ));
+        }
+        TopicScope::All => {}
+    }
+    for tag_name in tags {
+        query = query.filter(
+            topic::columns::topic_id.eq_any(
+                topictag::table
+                    .inner_join(tag::table)
+                    .filter(tag::columns::tag_name.eq(tag_name))
+                    .select(topictag::columns::topic_id),
+            ),
+        );
+    }
+    query
 }
 
-#[post("/user")]
-pub async fn create_user(
-    pool: web::Data<DbPool>,
-    session: Session,
-    req: web::Json<UserPostRequest>,
-) -> Result<HttpResponse, ApiError> {
+/// Loads everything shown alongside a topic and assembles its details.
+fn build_topic_details(conn: &mut PgConnection, topic: Topic) -> Result<TopicDetails, ApiError> {
     use backend_database::schema::*;
 
-    if !is_session_authed(&session) {
-        return Err(ApiError::Unauthorized);
+    let teacher = teacher::dsl::teacher
+        .find(&topic.teacher_user_name)
+        .first::<Teacher>(conn)
+        .map_err(|e| {
+            ApiError::InternalServerError(format!("Failed to load topic teacher: {}", e))
+        })?;
+    let major = major::dsl::major
+        .find(topic.major_id)
+        .first::<Major>(conn)
+        .map_err(|e| ApiError::InternalServerError(format!("Failed to load topic major: {}", e)))?;
+
+    let current_student_count = count_students_by_topic(conn, &[topic.topic_id])?
+        .remove(&topic.topic_id)
+        .unwrap_or(0);
+    let tags = load_topic_tags(conn, &[topic.topic_id])?
+        .remove(&topic.topic_id)
+        .unwrap_or_default();
+    let cosupervisors = load_topic_cosupervisors(conn, topic.topic_id)?;
+
+    Ok(TopicDetails {
+        topic_id: topic.topic_id,
+        major_id: topic.major_id,
+        major_name: major.major_name,
+        teacher_user_name: topic.teacher_user_name,
+        teacher_name: teacher.teacher_name,
+        topic_name: topic.topic_name,
+        topic_description: topic.topic_description,
+        topic_max_students: topic.topic_max_students,
+        topic_type: TopicType::try_from(topic.topic_type)
+            .map_err(|_| ApiError::InternalServerError(str!("Invalid topic type")))?,
+        topic_review_status: TopicReviewStatus::try_from(topic.topic_review_status)
+            .map_err(|_| ApiError::InternalServerError(str!("Invalid topic review status")))?,
+        current_student_count: current_student_count as i32,
+        tags,
+        cosupervisors,
+        topic_version: topic.topic_version,
+        topic_review_comment: topic.topic_review_comment,
+    })
+}
+
+/// Normalizes a tag name by trimming, collapsing inner whitespace and lowercasing it.
+fn normalize_tag(tag_name: &str) -> Result<String, ApiError> {
+    let normalized = tag_name
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase();
+    if normalized.is_empty() {
+        return Err(ApiError::BadRequest(str!("Tag must not be empty")));
     }
-    if !is_session_admin(&session)
-        .map_err(|_| ApiError::InternalServerError(str!("Can't deserialize auth info")))?
-    {
-        return Err(ApiError::Forbidden);
+    if normalized.chars().count() > MAX_TAG_NAME_LEN {
+        return Err(ApiError::BadRequest(format!(
+            "Tag must not be longer than {} characters",
+            MAX_TAG_NAME_LEN
+        )));
     }
+    Ok(normalized)
+}
 
-    let (hash, salt) = hash_password(&req.password)
-        .map_err(|_| ApiError::InternalServerError(str!("Failed to hash password")))?;
+/// Normalizes a list of tag names, dropping duplicates while keeping the original order.
+fn normalize_tags<S: AsRef<str>>(tags: &[S]) -> Result<Vec<String>, ApiError> {
+    let mut normalized: Vec<String> = Vec::with_capacity(tags.len());
+    for tag_name in tags {
+        let tag_name = normalize_tag(tag_name.as_ref())?;
+        if !normalized.contains(&tag_name) {
+            normalized.push(tag_name);
+        }
+    }
+    Ok(normalized)
+}
 
-    let mut conn = pool
-        .get()
-        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
-    let (new_sys_user, name) = conn.build_transaction().read_write().run(|conn| {
-        let name = match req.role {
-            UserRole::Student | UserRole::Teacher => req.name.clone(),
-            _ => None,
-        };
-
-        let new_sys_user = diesel::insert_into(sysuser::dsl::sysuser)
-            .values(NewSysUser {
-                user_name: &req.username,
-                user_password_hash: &hash,
-                user_password_salt: &salt,
-                user_avatar: req.avatar.as_deref(),
-            })
-            .get_result::<SysUser>(conn)
-            .map_err(|_| ApiError::Conflict(str!("Failed to create new user")))?;
-        match req.role {
-            UserRole::Admin => diesel::insert_into(sysadmin::dsl::sysadmin)
-                .values(NewSysAdmin {
-                    user_name: &new_sys_user.user_name,
-                })
-                .execute(conn),
-            UserRole::Student => diesel::insert_into(student::dsl::student)
-                .values(NewStudent {
-                    user_name: &new_sys_user.user_name,
-                    topic_id: None,
-                    major_id: req.major_id.ok_or(ApiError::BadRequest(str!(
-                        "Major ID is required for student role"
-                    )))?,
-                    student_name: name.as_deref().ok_or(ApiError::BadRequest(str!(
-                        "Name is required for student role"
-                    )))?,
-                    assn_time: Utc::now(),
-                })
-                .execute(conn),
-            UserRole::Teacher => diesel::insert_into(teacher::dsl::teacher)
-                .values(NewTeacher {
-                    user_name: &new_sys_user.user_name,
-                    teacher_name: name.as_deref().ok_or(ApiError::BadRequest(str!(
-                        "Name is required for teacher role"
-                    )))?,
-                })
-                .execute(conn),
-            UserRole::DefenseBoard => diesel::insert_into(defenseboard::dsl::defenseboard)
-                .values(NewDefenseBoard {
-                    user_name: &new_sys_user.user_name,
-                })
-                .execute(conn),
-            UserRole::Office => diesel::insert_into(office::dsl::office)
-                .values(NewOffice {
-                    user_name: &new_sys_user.user_name,
-                })
-                .execute(conn),
-        }
-        .map_err(|_| ApiError::InternalServerError(str!("Failed to assign role")))?;
-        Ok::<_, ApiError>((new_sys_user, name))
-    })?;
-
-    Ok(HttpResponse::Ok().json(UserGetResponse {
-        username: new_sys_user.user_name,
-        role: req.role,
-        name,
-        avatar: req.avatar.clone(),
-    }))
+/// Parses a comma-separated tag filter from the query string.
+fn parse_tag_filter(tags: Option<&str>) -> Result<Vec<String>, ApiError> {
+    let tags = tags
+        .unwrap_or("")
+        .split(',')
+        .filter(|t| !t.trim().is_empty())
+        .collect::<Vec<_>>();
+    normalize_tags(&tags)
 }
 
-#[get("/topics")]
-pub async fn get_topics(
-    pool: web::Data<DbPool>,
-    session: Session,
-    query: web::Query<PaginationQuery>,
-) -> Result<HttpResponse, ApiError> {
+/// Loads tag names of the given topics, sorted by name.
+fn load_topic_tags(
+    conn: &mut PgConnection,
+    topic_ids: &[i32],
+) -> Result<HashMap<i32, Vec<String>>, ApiError> {
     use backend_database::schema::*;
 
-    if !is_session_authed(&session) {
-        return Err(ApiError::Unauthorized);
+    let rows = topictag::table
+        .inner_join(tag::table)
+        .filter(topictag::columns::topic_id.eq_any(topic_ids))
+        .order((
+            topictag::columns::topic_id.asc(),
+            tag::columns::tag_name.asc(),
+        ))
+        .select((topictag::columns::topic_id, tag::columns::tag_name))
+        .load::<(i32, String)>(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load topic tags")))?;
+
+    let mut tags_by_topic: HashMap<i32, Vec<String>> = HashMap::new();
+    for (topic_id, tag_name) in rows {
+        tags_by_topic.entry(topic_id).or_default().push(tag_name);
     }
+    Ok(tags_by_topic)
+}
 
-    let username = get_session_username(&session)
-        .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
-    let user_role = get_session_user_role(&session)
-        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+/// Counts students assigned to each of the given topics in a single query.
+///
+/// Topics without students are absent from the returned map.
+fn count_students_by_topic(
+    conn: &mut PgConnection,
+    topic_ids: &[i32],
+) -> Result<HashMap<i32, i64>, ApiError> {
+    use backend_database::schema::*;
 
-    let page = query.page.unwrap_or(1);
-    let page_size = query.page_size.unwrap_or(20);
-    // FIXME: Negative checks
-    let offset = (page - 1) * page_size;
+    let rows = student::table
+        .filter(student::columns::topic_id.eq_any(topic_ids))
+        .group_by(student::columns::topic_id)
+        .select((student::columns::topic_id, diesel::dsl::count_star()))
+        .load::<(Option<i32>, i64)>(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to count students for topics")))?;
 
-    let mut conn = pool
-        .get()
-        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|(topic_id, count)| topic_id.map(|topic_id| (topic_id, count)))
+        .collect())
+}
 
-    let (total, topics_with_teacher) = match user_role {
-        AuthInfoUserRole::Student => {
-            // Student: Get topics approved for their major
-            let student_info = student::dsl::student
-                .find(&username)
-                .first::<Student>(&mut conn)
-                .map_err(|_| {
-                    ApiError::InternalServerError(str!("Failed to get student information"))
-                })?;
+/// Replaces all tags of a topic, creating missing tags on the fly.
+fn replace_topic_tags(
+    conn: &mut PgConnection,
+    topic_id: i32,
+    tags: &[String],
+) -> Result<(), ApiError> {
+    use backend_database::schema::*;
 
-            let query = topic::table
-                .inner_join(teacher::table)
-                .filter(topic::columns::major_id.eq(student_info.major_id))
-                .filter(topic::columns::topic_review_status.eq(TopicReviewStatus::Approved as i16));
-            let total = query
-                .count()
-                .get_result::<i64>(&mut conn)
-                .map_err(|_| ApiError::InternalServerError(str!("Failed to count topics")))?;
-            let topics_with_teacher = query
-                .offset(offset)
-                .limit(page_size)
-                .order_by(topic::columns::topic_id.desc())
-                .load::<(Topic, Teacher)>(&mut conn)
-                .map_err(|_| ApiError::InternalServerError(str!("Failed to load topics")))?;
-            (total, topics_with_teacher)
-        }
-        AuthInfoUserRole::Teacher => {
-            // Teacher: Get their own topics
-            let query = topic::table
-                .inner_join(teacher::table)
-                .filter(topic::columns::teacher_user_name.eq(&username));
-            let total = query
-                .count()
-                .get_result::<i64>(&mut conn)
-                .map_err(|_| ApiError::InternalServerError(str!("Failed to count topics")))?;
-            let topics_with_teacher = query
-                .offset(offset)
-                .limit(page_size)
-                .order_by(topic::columns::topic_id.desc())
-                .load::<(Topic, Teacher)>(&mut conn)
-                .map_err(|_| ApiError::InternalServerError(str!("Failed to load topics")))?;
-            (total, topics_with_teacher)
-        }
-        AuthInfoUserRole::Office | AuthInfoUserRole::DefenseBoard => {
-            // Office and DefenseBoard: Get all topics
-            // No additional filtering needed.
-            let query = topic::table.inner_join(teacher::table);
-            let total = query
-                .count()
-                .get_result::<i64>(&mut conn)
-                .map_err(|_| ApiError::InternalServerError(str!("Failed to count topics")))?;
-            let topics_with_teacher = query
-                .offset(offset)
-                .limit(page_size)
-                .order_by(topic::columns::topic_id.desc())
-                .load::<(Topic, Teacher)>(&mut conn)
-                .map_err(|_| ApiError::InternalServerError(str!("Failed to load topics")))?;
-            (total, topics_with_teacher)
-        }
-    };
+    if tags.len() > MAX_TAGS_PER_TOPIC {
+        return Err(ApiError::BadRequest(format!(
+            "A topic can carry at most {} tags",
+            MAX_TAGS_PER_TOPIC
+        )));
+    }
 
-    let mut topic_briefs = Vec::new();
-    for (topic, teacher) in topics_with_teacher {
-        let current_student_count = student::dsl::student
-            .filter(student::columns::topic_id.eq(topic.topic_id))
-            .count()
-            .get_result::<i64>(&mut conn)
-            .map_err(|_| {
-                ApiError::InternalServerError(str!("Failed to count students for topic"))
-            })?;
+    diesel::delete(topictag::dsl::topictag.filter(topictag::columns::topic_id.eq(topic_id)))
+        .execute(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to clear topic tags")))?;
 
-        topic_briefs.push(TopicBrief {
-            topic_id: topic.topic_id,
-            teacher_name: teacher.teacher_name,
-            topic_name: topic.topic_name,
-            topic_max_students: topic.topic_max_students,
-            topic_type: TopicType::try_from(topic.topic_type)
-                .map_err(|_| ApiError::InternalServerError(str!("Invalid topic type")))?,
-            topic_review_status: TopicReviewStatus::try_from(topic.topic_review_status)
-                .map_err(|_| ApiError::InternalServerError(str!("Invalid topic review status")))?,
-            current_student_count: current_student_count as i32,
-        });
+    for tag_name in tags {
+        diesel::insert_into(tag::dsl::tag)
+            .values(NewTag { tag_name })
+            .on_conflict(tag::columns::tag_name)
+            .do_nothing()
+            .execute(conn)
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to create tag")))?;
+        let tag_id = tag::dsl::tag
+            .filter(tag::columns::tag_name.eq(tag_name))
+            .select(tag::columns::tag_id)
+            .first::<i32>(conn)
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to load tag")))?;
+        diesel::insert_into(topictag::dsl::topictag)
+            .values(NewTopicTag { topic_id, tag_id })
+            .execute(conn)
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to tag topic")))?;
     }
 
-    Ok(HttpResponse::Ok().json(TopicsGetResponse {
-        total,
-        page,
-        page_size,
-        topics: topic_briefs,
-    }))
+    Ok(())
 }
 
-#[post("/topics")]
-pub async fn create_topic(
-    pool: web::Data<DbPool>,
-    session: Session,
-    req: web::Json<TopicsPostRequest>,
-) -> Result<HttpResponse, ApiError> {
+/// Gets the maximum number of students a teacher may supervise across all topics.
+///
+/// Returns `None` if no quota is configured for the teacher's title.
+fn get_teacher_quota(
+    conn: &mut PgConnection,
+    teacher_user_name: &str,
+) -> Result<Option<i32>, ApiError> {
     use backend_database::schema::*;
 
-    if !is_session_authed(&session) {
-        return Err(ApiError::Unauthorized);
-    }
+    teacher::table
+        .inner_join(
+            supervisionquota::table
+                .on(supervisionquota::columns::teacher_title.eq(teacher::columns::teacher_title)),
+        )
+        .filter(teacher::columns::user_name.eq(teacher_user_name))
+        .select(supervisionquota::columns::quota_max_students)
+        .first::<i32>(conn)
+        .optional()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load supervision quota")))
+}
 
-    let user_role = get_session_user_role(&session)
-        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
-    if !matches!(user_role, AuthInfoUserRole::Teacher) {
-        // Only teachers can create topics
-        return Err(ApiError::Forbidden);
+/// Counts the places a teacher has committed to across their topics that are not rejected,
+/// optionally excluding one topic.
+///
+/// Each topic commits its capacity, or its assigned students if the office placed more than
+/// that, so the count bounds both what is offered and who is supervised.
+fn count_teacher_places(
+    conn: &mut PgConnection,
+    teacher_user_name: &str,
+    excluded_topic_id: Option<i32>,
+) -> Result<i64, ApiError> {
+    use backend_database::schema::*;
+
+    let mut query = topic::table
+        .filter(topic::columns::teacher_user_name.eq(teacher_user_name))
+        .filter(topic::columns::topic_review_status.ne(TopicReviewStatus::Rejected as i16))
+        .into_boxed();
+    if let Some(excluded_topic_id) = excluded_topic_id {
+        query = query.filter(topic::columns::topic_id.ne(excluded_topic_id));
     }
+    let capacities = query
+        .select((topic::columns::topic_id, topic::columns::topic_max_students))
+        .load::<(i32, i32)>(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load topic capacity")))?;
+
+    let topic_ids = capacities.iter().map(|(id, _)| *id).collect::<Vec<_>>();
+    let assigned = student::table
+        .filter(student::columns::topic_id.eq_any(&topic_ids))
+        .group_by(student::columns::topic_id)
+        .select((student::columns::topic_id, diesel::dsl::count_star()))
+        .load::<(Option<i32>, i64)>(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to count supervised students")))?
+        .into_iter()
+        .filter_map(|(topic_id, count)| Some((topic_id?, count)))
+        .collect::<HashMap<_, _>>();
 
-    let username = get_session_username(&session)
-        .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
+    Ok(capacities
+        .into_iter()
+        .map(|(topic_id, capacity)| {
+            (capacity as i64).max(assigned.get(&topic_id).copied().unwrap_or(0))
+        })
+        .sum())
+}
 
-    let mut conn = pool
-        .get()
-        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+/// Checks that a teacher stays within the supervision quota of their title after committing
+/// `extra_places` more places, optionally leaving one of their topics out of the count.
+///
+/// Topic creation and updates pass the places they add; placements pass none, and fail only
+/// once the quota has been lowered below what the teacher already committed to.
+fn check_teacher_quota(
+    conn: &mut PgConnection,
+    teacher_user_name: &str,
+    extra_places: i64,
+    excluded_topic_id: Option<i32>,
+) -> Result<(), ApiError> {
+    let Some(quota) = get_teacher_quota(conn, teacher_user_name)? else {
+        return Ok(());
+    };
 
-    let has_such_teacher: i64 = teacher::dsl::teacher
-        .find(&username)
-        .count()
-        .get_result(&mut conn)
-        .map_err(|_| ApiError::InternalServerError(str!("Failed to check teacher existence")))?;
-    if has_such_teacher == 0 {
-        return Err(ApiError::InternalServerError(str!(
-            "Teacher record not found for user"
+    let places = count_teacher_places(conn, teacher_user_name, excluded_topic_id)?;
+    if places + extra_places > quota as i64 {
+        return Err(ApiError::Conflict(format!(
+            "Teacher supervision quota exceeded: {} of {} places already committed",
+            places, quota
         )));
     }
 
-    let new_topic = conn.build_transaction().read_write().run(|conn| {
-        let new_topic = NewTopic {
-            major_id: req.major_id,
-            teacher_user_name: &username,
-            topic_name: &req.topic_name,
-            topic_description: &req.topic_description,
-            topic_max_students: req.topic_max_students,
-            topic_type: req.topic_type as i16,
-            topic_review_status: TopicReviewStatus::Pending as i16,
-        };
-
-        let inserted_topic = diesel::insert_into(topic::dsl::topic)
-            .values(&new_topic)
-            .get_result::<Topic>(conn)
-            .map_err(|e| {
-                if e.to_string().contains("duplicate key") {
-                    ApiError::Conflict(str!("Topic with similar name already exists"))
-                } else {
-                    ApiError::InternalServerError(str!("Failed to create topic"))
-                }
-            })?;
+    Ok(())
+}
 
-        Ok::<_, ApiError>(inserted_topic)
-    })?;
+/// Builds the subquery of ids of topics a teacher co-supervises.
+fn cosupervised_topic_ids(teacher_user_name: &str) -> CoSupervisedTopicIds<'_> {
+    use backend_database::schema::*;
 
-    Ok(HttpResponse::Created().json(TopicCreateResponse {
-        topic_id: new_topic.topic_id,
-    }))
+    topiccosupervisor::table
+        .filter(topiccosupervisor::columns::teacher_user_name.eq(teacher_user_name))
+        .select(topiccosupervisor::columns::topic_id)
 }
 
-#[get("/topics/search")]
-pub async fn search_topics(
-    pool: web::Data<DbPool>,
-    session: Session,
-    query: web::Query<SearchQuery>,
-) -> Result<HttpResponse, ApiError> {
+/// Checks whether a teacher may make approval decisions for a topic.
+///
+/// The primary supervisor always may; co-supervisors only if granted approval rights.
+fn can_approve_for_topic(
+    conn: &mut PgConnection,
+    topic_id: i32,
+    primary_teacher_user_name: &str,
+    teacher_user_name: &str,
+) -> Result<bool, ApiError> {
     use backend_database::schema::*;
 
-    if !is_session_authed(&session) {
-        return Err(ApiError::Unauthorized);
+    if primary_teacher_user_name == teacher_user_name {
+        return Ok(true);
     }
 
-    let username = get_session_username(&session)
-        .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
-    let user_role = get_session_user_role(&session)
-        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+    topiccosupervisor::dsl::topiccosupervisor
+        .find((topic_id, teacher_user_name))
+        .select(topiccosupervisor::columns::cosup_can_approve)
+        .first::<bool>(conn)
+        .optional()
+        .map(|can_approve| can_approve.unwrap_or(false))
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to check co-supervisor rights")))
+}
 
-    let page = query.page.unwrap_or(1);
-    let page_size = query.page_size.unwrap_or(20);
-    // FIXME: Negative checks
-    let offset = (page - 1) * page_size;
-    let search_pattern = format!("%{}%", query.keyword.as_deref().unwrap_or(""));
+/// Checks whether a teacher supervises or co-supervises a topic.
+fn supervises_topic(
+    conn: &mut PgConnection,
+    topic_id: i32,
+    teacher_user_name: &str,
+) -> Result<bool, ApiError> {
+    use backend_database::schema::*;
 
-    let mut conn = pool
-        .get()
-        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+    diesel::select(diesel::dsl::exists(
+        topic::table
+            .filter(topic::columns::topic_id.eq(topic_id))
+            .filter(
+                topic::columns::teacher_user_name
+                    .eq(teacher_user_name)
+                    .or(topic::columns::topic_id.eq_any(cosupervised_topic_ids(teacher_user_name))),
+            ),
+    ))
+    .get_result::<bool>(conn)
+    .map_err(|_| ApiError::InternalServerError(str!("Failed to check topic supervisors")))
+}
 
-    let (total, topics_with_teacher) = match user_role {
-        AuthInfoUserRole::Student => {
-            // Student: Get topics approved for their major with keyword search
-            let student_info = student::dsl::student
-                .find(&username)
-                .first::<Student>(&mut conn)
-                .map_err(|_| {
-                    ApiError::InternalServerError(str!("Failed to get student information"))
-                })?;
+/// Releases students pre-assigned to a topic through an accepted proposal, so that they can
+/// look for another topic once the office rejects it.
+fn release_proposing_students(conn: &mut PgConnection, topic_id: i32) -> Result<(), ApiError> {
+    use backend_database::schema::*;
 
-            let query = topic::table
-                .inner_join(teacher::table)
-                .filter(topic::columns::major_id.eq(student_info.major_id))
-                .filter(topic::columns::topic_review_status.eq(TopicReviewStatus::Approved as i16))
-                .filter(topic::columns::topic_name.like(&search_pattern));
-            let total = query
-                .count()
-                .get_result::<i64>(&mut conn)
-                .map_err(|_| ApiError::InternalServerError(str!("Failed to count topics")))?;
-            let topics_with_teacher = query
-                .offset(offset)
-                .limit(page_size)
-                .order_by(topic::columns::topic_id.desc())
-                .load::<(Topic, Teacher)>(&mut conn)
-                .map_err(|_| ApiError::InternalServerError(str!("Failed to load topics")))?;
-            (total, topics_with_teacher)
-        }
-        AuthInfoUserRole::Teacher => {
-            // Teacher: Get their own topics with keyword search
-            let query = topic::table
-                .inner_join(teacher::table)
-                .filter(topic::columns::teacher_user_name.eq(&username))
-                .filter(topic::columns::topic_name.like(&search_pattern));
-            let total = query
-                .count()
-                .get_result::<i64>(&mut conn)
-                .map_err(|_| ApiError::InternalServerError(str!("Failed to count topics")))?;
-            let topics_with_teacher = query
-                .offset(offset)
-                .limit(page_size)
-                .order_by(topic::columns::topic_id.desc())
-                .load::<(Topic, Teacher)>(&mut conn)
-                .map_err(|_| ApiError::InternalServerError(str!("Failed to load topics")))?;
-            (total, topics_with_teacher)
-        }
-        AuthInfoUserRole::Office | AuthInfoUserRole::DefenseBoard => {
-            // Office and DefenseBoard: Get all topics with keyword search
-            let query = topic::table
-                .inner_join(teacher::table)
-                .filter(topic::columns::topic_name.like(&search_pattern));
-            let total = query
-                .count()
-                .get_result::<i64>(&mut conn)
-                .map_err(|_| ApiError::InternalServerError(str!("Failed to count topics")))?;
-            let topics_with_teacher = query
-                .offset(offset)
-                .limit(page_size)
-                .order_by(topic::columns::topic_id.desc())
-                .load::<(Topic, Teacher)>(&mut conn)
-                .map_err(|_| ApiError::InternalServerError(str!("Failed to load topics")))?;
-            (total, topics_with_teacher)
-        }
-    };
+    diesel::update(
+        student::table
+            .filter(student::columns::topic_id.eq(topic_id))
+            .filter(
+                student::columns::user_name.eq_any(
+                    topicproposal::table
+                        .filter(topicproposal::columns::topic_id.eq(topic_id))
+                        .select(topicproposal::columns::student_user_name),
+                ),
+            ),
+    )
+    .set(student::columns::topic_id.eq(None::<i32>))
+    .execute(conn)
+    .map_err(|_| ApiError::InternalServerError(str!("Failed to release proposing students")))?;
 
-    let mut topic_briefs = Vec::new();
-    for (topic, teacher) in topics_with_teacher {
-        let current_student_count = student::dsl::student
-            .filter(student::columns::topic_id.eq(topic.topic_id))
-            .count()
-            .get_result::<i64>(&mut conn)
-            .map_err(|_| {
-                ApiError::InternalServerError(str!("Failed to count students for topic"))
-            })?;
-        topic_briefs.push(TopicBrief {
-            topic_id: topic.topic_id,
-            teacher_name: teacher.teacher_name,
-            topic_name: topic.topic_name,
-            topic_max_students: topic.topic_max_students,
-            topic_type: TopicType::try_from(topic.topic_type)
-                .map_err(|_| ApiError::InternalServerError(str!("Invalid topic type")))?,
-            topic_review_status: TopicReviewStatus::try_from(topic.topic_review_status)
-                .map_err(|_| ApiError::InternalServerError(str!("Invalid topic review status")))?,
-            current_student_count: current_student_count as i32,
+    Ok(())
+}
+
+/// Converts a proposal row, along with the names of its student and teacher, into its details.
+fn build_topic_proposal_details(
+    proposal: TopicProposal,
+    student_name: String,
+    teacher_name: String,
+) -> Result<TopicProposalDetails, ApiError> {
+    Ok(TopicProposalDetails {
+        prop_id: proposal.prop_id,
+        student_user_name: proposal.student_user_name,
+        student_name,
+        teacher_user_name: proposal.teacher_user_name,
+        teacher_name,
+        topic_name: proposal.prop_topic_name,
+        topic_description: proposal.prop_topic_description,
+        topic_type: TopicType::try_from(proposal.prop_topic_type)
+            .map_err(|_| ApiError::InternalServerError(str!("Invalid topic type")))?,
+        prop_status: TopicProposalStatus::try_from(proposal.prop_status)
+            .map_err(|_| ApiError::InternalServerError(str!("Invalid topic proposal status")))?,
+        prop_time: proposal.prop_time,
+        prop_decided_time: proposal.prop_decided_time,
+        prop_decline_reason: proposal.prop_decline_reason,
+        topic_id: proposal.topic_id,
+    })
+}
+
+/// Loads a proposal along with the names of its student and teacher.
+fn load_topic_proposal_details(
+    conn: &mut PgConnection,
+    prop_id: i32,
+) -> Result<TopicProposalDetails, ApiError> {
+    use backend_database::schema::*;
+
+    let (proposal, student_name, teacher_name) = topicproposal::table
+        .inner_join(student::table)
+        .inner_join(teacher::table)
+        .filter(topicproposal::columns::prop_id.eq(prop_id))
+        .select((
+            topicproposal::all_columns,
+            student::columns::student_name,
+            teacher::columns::teacher_name,
+        ))
+        .first::<(TopicProposal, String, String)>(conn)
+        .map_err(|_| ApiError::NotFound)?;
+
+    build_topic_proposal_details(proposal, student_name, teacher_name)
+}
+
+/// Loads the co-supervisors of a topic, ordered by user name.
+fn load_topic_cosupervisors(
+    conn: &mut PgConnection,
+    topic_id: i32,
+) -> Result<Vec<CoSupervisorDetails>, ApiError> {
+    use backend_database::schema::*;
+
+    let rows = topiccosupervisor::table
+        .inner_join(teacher::table)
+        .filter(topiccosupervisor::columns::topic_id.eq(topic_id))
+        .order(topiccosupervisor::columns::teacher_user_name.asc())
+        .select((
+            topiccosupervisor::columns::teacher_user_name,
+            teacher::columns::teacher_name,
+            topiccosupervisor::columns::cosup_can_approve,
+        ))
+        .load::<(String, String, bool)>(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load co-supervisors")))?;
+
+    Ok(rows
+        .into_iter()
+        .map(
+            |(teacher_user_name, teacher_name, can_approve)| CoSupervisorDetails {
+                teacher_user_name,
+                teacher_name,
+                can_approve,
+            },
+        )
+        .collect())
+}
+
+/// Builds the details of selection rounds, loading their eligible majors in one query.
+fn build_selection_round_details(
+    conn: &mut PgConnection,
+    rounds: Vec<SelectionRound>,
+) -> Result<Vec<SelectionRoundDetails>, ApiError> {
+    use backend_database::schema::*;
+
+    let round_ids = rounds.iter().map(|r| r.sel_round_id).collect::<Vec<_>>();
+    let mut majors_by_round: HashMap<i32, Vec<i32>> = HashMap::new();
+    for (sel_round_id, major_id) in selectionroundmajor::table
+        .filter(selectionroundmajor::columns::sel_round_id.eq_any(&round_ids))
+        .order(selectionroundmajor::columns::major_id.asc())
+        .select((
+            selectionroundmajor::columns::sel_round_id,
+            selectionroundmajor::columns::major_id,
+        ))
+        .load::<(i32, i32)>(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load round majors")))?
+    {
+        majors_by_round
+            .entry(sel_round_id)
+            .or_default()
+            .push(major_id);
+    }
+
+    Ok(rounds
+        .into_iter()
+        .map(|round| SelectionRoundDetails {
+            major_ids: majors_by_round
+                .remove(&round.sel_round_id)
+                .unwrap_or_default(),
+            sel_round_id: round.sel_round_id,
+            sel_round_name: round.sel_round_name,
+            open_time: round.sel_round_open_time,
+            close_time: round.sel_round_close_time,
+            max_requests: round.sel_round_max_requests,
+            carried_over_from: round.sel_round_prev_id,
+        })
+        .collect())
+}
+
+/// Rejects a selection window that overlaps another round sharing one of its majors, so
+/// that at most one round is open for any major at a time.
+fn check_selection_round_overlap(
+    conn: &mut PgConnection,
+    sel_round_id: Option<i32>,
+    major_ids: &[i32],
+    open_time: DateTime<Utc>,
+    close_time: DateTime<Utc>,
+) -> Result<(), ApiError> {
+    use backend_database::schema::*;
+
+    let overlapping = selectionround::table
+        .inner_join(selectionroundmajor::table)
+        .filter(selectionroundmajor::columns::major_id.eq_any(major_ids))
+        .filter(selectionround::columns::sel_round_id.ne(sel_round_id.unwrap_or(0)))
+        .filter(selectionround::columns::sel_round_open_time.lt(close_time))
+        .filter(selectionround::columns::sel_round_close_time.gt(open_time))
+        .select(selectionround::columns::sel_round_id)
+        .first::<i32>(conn)
+        .optional()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to check round overlap")))?;
+
+    match overlapping {
+        Some(other) => Err(ApiError::Conflict(format!(
+            "Selection window overlaps round {} for a shared major",
+            other
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Closes a student's remaining pending requests once they hold a topic, and notifies the
+/// teachers of the affected topics with the given reason. The student also leaves every
+/// waitlist, and the slots their requests held go to the next waitlisted students.
+fn close_sibling_requests(
+    conn: &mut PgConnection,
+    student_user_name: &str,
+    reason: &str,
+) -> Result<(), ApiError> {
+    use backend_database::schema::*;
+
+    diesel::delete(
+        topicwaitlist::table
+            .filter(topicwaitlist::columns::student_user_name.eq(student_user_name)),
+    )
+    .execute(conn)
+    .map_err(|_| ApiError::InternalServerError(str!("Failed to update waitlist")))?;
+
+    let now = Utc::now();
+    let closed_topic_ids = diesel::update(
+        assignmentrequest::table
+            .filter(assignmentrequest::columns::student_user_name.eq(student_user_name))
+            .filter(
+                assignmentrequest::columns::assn_req_status.eq(AssignmentStatus::Pending as i16),
+            ),
+    )
+    .set((
+        assignmentrequest::columns::assn_req_status.eq(AssignmentStatus::Rejected as i16),
+        assignmentrequest::columns::assn_req_decided_time.eq(Some(now)),
+        assignmentrequest::columns::assn_req_reason.eq(Some(reason)),
+    ))
+    .returning(assignmentrequest::columns::topic_id)
+    .get_results::<i32>(conn)
+    .map_err(|_| ApiError::InternalServerError(str!("Failed to close assignment requests")))?;
+    if closed_topic_ids.is_empty() {
+        return Ok(());
+    }
+
+    let student_name = student::table
+        .find(student_user_name)
+        .select(student::columns::student_name)
+        .first::<String>(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load student")))?;
+    let topics = topic::table
+        .filter(topic::columns::topic_id.eq_any(&closed_topic_ids))
+        .select((
+            topic::columns::topic_name,
+            topic::columns::teacher_user_name,
+        ))
+        .load::<(String, String)>(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load topics")))?;
+
+    let notifications = topics
+        .iter()
+        .map(|(topic_name, teacher_user_name)| NewNotification {
+            user_name: teacher_user_name,
+            notif_content: format!(
+                "Request from {} ({}) for \"{}\" was closed. Reason: {}",
+                student_name, student_user_name, topic_name, reason
+            ),
+            notif_time: now,
+        })
+        .collect::<Vec<_>>();
+    diesel::insert_into(notification::table)
+        .values(&notifications)
+        .execute(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to send notifications")))?;
+
+    for topic_id in closed_topic_ids {
+        promote_waitlist(conn, topic_id)?;
+    }
+
+    Ok(())
+}
+
+/// Loads a topic's waitlist in promotion order, numbering entries from 1.
+fn load_topic_waitlist(
+    conn: &mut PgConnection,
+    topic_id: i32,
+) -> Result<Vec<WaitlistEntryDetails>, ApiError> {
+    use backend_database::schema::*;
+
+    let entries = topicwaitlist::table
+        .inner_join(student::table)
+        .filter(topicwaitlist::columns::topic_id.eq(topic_id))
+        .order((
+            topicwaitlist::columns::waitlist_time.asc(),
+            topicwaitlist::columns::waitlist_id.asc(),
+        ))
+        .select((
+            topicwaitlist::columns::student_user_name,
+            student::columns::student_name,
+            topicwaitlist::columns::waitlist_time,
+        ))
+        .load::<(String, String, DateTime<Utc>)>(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load waitlist")))?;
+
+    Ok(entries
+        .into_iter()
+        .zip(1..)
+        .map(
+            |((student_user_name, student_name, join_time), position)| WaitlistEntryDetails {
+                student_user_name,
+                student_name,
+                position,
+                join_time,
+            },
+        )
+        .collect())
+}
+
+/// Counts the places of a topic that are neither taken by assigned students nor held by pending
+/// requests. Waitlisted students are promoted as places open up.
+fn count_free_places(conn: &mut PgConnection, topic: &Topic) -> Result<i64, ApiError> {
+    use backend_database::schema::*;
+
+    let assigned_count: i64 = student::table
+        .filter(student::columns::topic_id.eq(topic.topic_id))
+        .count()
+        .get_result(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to count students")))?;
+    let pending_count: i64 = assignmentrequest::table
+        .filter(assignmentrequest::columns::topic_id.eq(topic.topic_id))
+        .filter(assignmentrequest::columns::assn_req_status.eq(AssignmentStatus::Pending as i16))
+        .count()
+        .get_result(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to count requests")))?;
+
+    Ok(topic.topic_max_students as i64 - assigned_count - pending_count)
+}
+
+/// Checks whether a student has to join the waitlist of a topic instead of requesting it
+/// directly: when no places are free, or when other students are waiting ahead of them.
+fn must_wait_for_topic(
+    conn: &mut PgConnection,
+    topic: &Topic,
+    student_user_name: &str,
+) -> Result<bool, ApiError> {
+    use backend_database::schema::*;
+
+    if count_free_places(conn, topic)? <= 0 {
+        return Ok(true);
+    }
+
+    let own_time = topicwaitlist::table
+        .filter(topicwaitlist::columns::topic_id.eq(topic.topic_id))
+        .filter(topicwaitlist::columns::student_user_name.eq(student_user_name))
+        .select(topicwaitlist::columns::waitlist_time)
+        .first::<DateTime<Utc>>(conn)
+        .optional()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load waitlist")))?;
+    let mut ahead = topicwaitlist::table
+        .filter(topicwaitlist::columns::topic_id.eq(topic.topic_id))
+        .filter(topicwaitlist::columns::student_user_name.ne(student_user_name))
+        .into_boxed();
+    if let Some(own_time) = own_time {
+        ahead = ahead.filter(topicwaitlist::columns::waitlist_time.lt(own_time));
+    }
+
+    diesel::select(diesel::dsl::exists(ahead))
+        .get_result::<bool>(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load waitlist")))
+}
+
+/// Promotes students waitlisted on a topic to pending requests, first come first served,
+/// while the topic has free places. Students are only promoted while a selection round is open
+/// for their major and they have requests left in it, and the request is filed in that round;
+/// the others keep their place. Each promoted student is notified.
+fn promote_waitlist(conn: &mut PgConnection, topic_id: i32) -> Result<(), ApiError> {
+    use backend_database::schema::*;
+
+    let topic = topic::table
+        .find(topic_id)
+        .for_update()
+        .first::<Topic>(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to lock topic")))?;
+    if topic.topic_review_status != TopicReviewStatus::Approved as i16 {
+        return Ok(());
+    }
+
+    let mut free_slots = count_free_places(conn, &topic)?;
+    if free_slots <= 0 {
+        return Ok(());
+    }
+
+    let entries = topicwaitlist::table
+        .filter(topicwaitlist::columns::topic_id.eq(topic_id))
+        .order((
+            topicwaitlist::columns::waitlist_time.asc(),
+            topicwaitlist::columns::waitlist_id.asc(),
+        ))
+        .for_update()
+        .load::<TopicWaitlist>(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load waitlist")))?;
+
+    let now = Utc::now();
+    for entry in entries {
+        if free_slots <= 0 {
+            break;
+        }
+
+        let student = student::table
+            .find(&entry.student_user_name)
+            .first::<Student>(conn)
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to load student")))?;
+        let sel_round_id = if student.topic_id.is_some() {
+            None
+        } else {
+            let round = selectionround::table
+                .inner_join(selectionroundmajor::table)
+                .filter(selectionroundmajor::columns::major_id.eq(student.major_id))
+                .filter(selectionround::columns::sel_round_open_time.le(now))
+                .filter(selectionround::columns::sel_round_close_time.gt(now))
+                .select(SelectionRound::as_select())
+                .first::<SelectionRound>(conn)
+                .optional()
+                .map_err(|_| {
+                    ApiError::InternalServerError(str!("Failed to load selection round"))
+                })?;
+            let Some(round) = round else {
+                continue;
+            };
+            // The request counts against the round's limit as a direct one would
+            let round_request_count: i64 = assignmentrequest::table
+                .filter(assignmentrequest::columns::student_user_name.eq(&student.user_name))
+                .filter(assignmentrequest::columns::sel_round_id.eq(round.sel_round_id))
+                .count()
+                .get_result(conn)
+                .map_err(|_| ApiError::InternalServerError(str!("Failed to count requests")))?;
+            if round_request_count >= round.sel_round_max_requests as i64 {
+                continue;
+            }
+            Some(round.sel_round_id)
+        };
+
+        diesel::delete(&entry)
+            .execute(conn)
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to update waitlist")))?;
+        let Some(sel_round_id) = sel_round_id else {
+            continue;
+        };
+
+        diesel::insert_into(assignmentrequest::table)
+            .values(NewAssignmentRequest {
+                student_user_name: &student.user_name,
+                topic_id,
+                assn_req_time: now,
+                sel_round_id: Some(sel_round_id),
+            })
+            .execute(conn)
+            .map_err(|_| {
+                ApiError::InternalServerError(str!("Failed to create assignment request"))
+            })?;
+        diesel::insert_into(notification::table)
+            .values(NewNotification {
+                user_name: &student.user_name,
+                notif_content: format!(
+                    "A slot opened on \"{}\". Your waitlist entry was turned into a pending request.",
+                    topic.topic_name
+                ),
+                notif_time: now,
+            })
+            .execute(conn)
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to send notifications")))?;
+
+        free_slots -= 1;
+    }
+
+    Ok(())
+}
+
+/// Locks, in ascending id order, the given topics and every topic the given students have a
+/// pending request for. Placing a student closes their other requests and promotes waitlisted
+/// students onto those topics, so placements take all their topic locks up front in this one
+/// order, before any teacher lock, and cannot deadlock on each other.
+fn lock_placement_topics(
+    conn: &mut PgConnection,
+    student_user_names: &[&str],
+    topic_ids: &[i32],
+) -> Result<Vec<Topic>, ApiError> {
+    use backend_database::schema::*;
+
+    let mut locked_topic_ids = assignmentrequest::table
+        .filter(assignmentrequest::columns::student_user_name.eq_any(student_user_names))
+        .filter(assignmentrequest::columns::assn_req_status.eq(AssignmentStatus::Pending as i16))
+        .select(assignmentrequest::columns::topic_id)
+        .load::<i32>(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load assignment requests")))?;
+    locked_topic_ids.extend_from_slice(topic_ids);
+
+    topic::table
+        .filter(topic::columns::topic_id.eq_any(&locked_topic_ids))
+        .order(topic::columns::topic_id.asc())
+        .for_update()
+        .load::<Topic>(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to lock topics")))
+}
+
+/// Locks a topic and its teacher before a student is placed on it, so that concurrent
+/// placements count each other's students when checking the topic capacity and the teacher
+/// quota. The student's current topic and requested topics are locked along with it, see
+/// [`lock_placement_topics`]. Callers lock the student row beforehand with
+/// `for_no_key_update`, which still lets promotions on other topics file requests for them.
+fn lock_topic_for_placement(
+    conn: &mut PgConnection,
+    student: &Student,
+    topic_id: i32,
+) -> Result<Topic, ApiError> {
+    use backend_database::schema::*;
+
+    let mut topic_ids = vec![topic_id];
+    topic_ids.extend(student.topic_id);
+    let topic = lock_placement_topics(conn, &[&student.user_name], &topic_ids)?
+        .into_iter()
+        .find(|topic| topic.topic_id == topic_id)
+        .ok_or(ApiError::NotFound)?;
+    teacher::table
+        .find(&topic.teacher_user_name)
+        .select(teacher::columns::user_name)
+        .for_update()
+        .first::<String>(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to lock teacher")))?;
+
+    Ok(topic)
+}
+
+/// Maps a failed update of `Student.topic_id`, reporting the database capacity check as a
+/// conflict.
+fn map_placement_error(e: diesel::result::Error) -> ApiError {
+    match e {
+        diesel::result::Error::DatabaseError(
+            diesel::result::DatabaseErrorKind::CheckViolation,
+            _,
+        ) => ApiError::Conflict(str!("Topic is full")),
+        _ => ApiError::InternalServerError(str!("Failed to assign topic")),
+    }
+}
+
+fn build_assignment_change_details(change: AssignmentChange) -> AssignmentChangeDetails {
+    AssignmentChangeDetails {
+        assn_change_id: change.assn_change_id,
+        student_user_name: change.student_user_name,
+        office_user_name: change.office_user_name,
+        old_topic_id: change.assn_change_old_topic_id,
+        new_topic_id: change.assn_change_new_topic_id,
+        reason: change.assn_change_reason,
+        time: change.assn_change_time,
+        overrode_capacity: change.assn_change_overrode_capacity,
+        moved_records: change.assn_change_moved_records,
+    }
+}
+
+/// Settles a student's progress reports and final defenses on a topic they are leaving.
+///
+/// With `move_to` set, every record follows the student to that topic. Otherwise concluded
+/// records stay on the old topic as history and unreviewed submissions are withdrawn, since
+/// nobody is left to review them.
+fn settle_topic_records(
+    conn: &mut PgConnection,
+    student_user_name: &str,
+    old_topic_id: i32,
+    move_to: Option<i32>,
+) -> Result<(), ApiError> {
+    use backend_database::schema::*;
+
+    let reports = progressreport::table
+        .filter(progressreport::columns::student_user_name.eq(student_user_name))
+        .filter(progressreport::columns::topic_id.eq(old_topic_id));
+    let defenses = finaldefense::table
+        .filter(finaldefense::columns::student_user_name.eq(student_user_name))
+        .filter(finaldefense::columns::topic_id.eq(old_topic_id));
+
+    match move_to {
+        Some(new_topic_id) => {
+            diesel::update(reports)
+                .set(progressreport::columns::topic_id.eq(new_topic_id))
+                .execute(conn)
+                .map_err(|_| {
+                    ApiError::InternalServerError(str!("Failed to move progress reports"))
+                })?;
+            diesel::update(defenses)
+                .set(finaldefense::columns::topic_id.eq(new_topic_id))
+                .execute(conn)
+                .map_err(|_| {
+                    ApiError::InternalServerError(str!("Failed to move final defenses"))
+                })?;
+        }
+        None => {
+            let pending_reports = reports.filter(
+                progressreport::columns::prog_report_outcome
+                    .eq(ProgressOutcome::NoConclusion as i16),
+            );
+            let pending_defenses =
+                defenses.filter(finaldefense::columns::final_def_outcome.is_null());
+
+            diesel::delete(
+                progressreportfile::table.filter(
+                    progressreportfile::columns::prog_report_id
+                        .eq_any(pending_reports.select(progressreport::columns::prog_report_id)),
+                ),
+            )
+            .execute(conn)
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to withdraw report files")))?;
+            diesel::delete(
+                progressreportscore::table.filter(
+                    progressreportscore::columns::prog_report_id
+                        .eq_any(pending_reports.select(progressreport::columns::prog_report_id)),
+                ),
+            )
+            .execute(conn)
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to withdraw report scores")))?;
+            let pending_report_ids = pending_reports
+                .select(progressreport::columns::prog_report_id)
+                .load::<i32>(conn)
+                .map_err(|_| {
+                    ApiError::InternalServerError(str!("Failed to load progress reports"))
+                })?;
+            let pending_defense_ids = pending_defenses
+                .select(finaldefense::columns::final_def_id)
+                .load::<i32>(conn)
+                .map_err(|_| {
+                    ApiError::InternalServerError(str!("Failed to load final defenses"))
+                })?;
+            delete_comments(conn, &pending_report_ids, &pending_defense_ids)?;
+            diesel::delete(pending_reports).execute(conn).map_err(|_| {
+                ApiError::InternalServerError(str!("Failed to withdraw progress reports"))
+            })?;
+            diesel::delete(
+                finaldefensefile::table.filter(
+                    finaldefensefile::columns::final_def_id
+                        .eq_any(pending_defenses.select(finaldefense::columns::final_def_id)),
+                ),
+            )
+            .execute(conn)
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to withdraw defense files")))?;
+            diesel::delete(pending_defenses)
+                .execute(conn)
+                .map_err(|_| {
+                    ApiError::InternalServerError(str!("Failed to withdraw final defenses"))
+                })?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Deletes the discussions of the given progress reports and final defenses, replies first.
+fn delete_comments(
+    conn: &mut PgConnection,
+    report_ids: &[i32],
+    defense_ids: &[i32],
+) -> Result<(), ApiError> {
+    use backend_database::schema::*;
+
+    let in_threads = || {
+        submissioncomment::columns::prog_report_id
+            .eq_any(report_ids.to_vec())
+            .or(submissioncomment::columns::final_def_id.eq_any(defense_ids.to_vec()))
+    };
+    diesel::delete(
+        submissioncomment::table
+            .filter(in_threads())
+            .filter(submissioncomment::columns::comment_parent_id.is_not_null()),
+    )
+    .execute(conn)
+    .map_err(|_| ApiError::InternalServerError(str!("Failed to delete comments")))?;
+    diesel::delete(submissioncomment::table.filter(in_threads()))
+        .execute(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to delete comments")))?;
+
+    Ok(())
+}
+
+/// Tells a student and the teachers of the topics involved about an office placement change.
+fn notify_assignment_change(
+    conn: &mut PgConnection,
+    student_user_name: &str,
+    topic_ids: &[i32],
+    content: &str,
+) -> Result<(), ApiError> {
+    use backend_database::schema::*;
+
+    let mut recipients = topic::table
+        .filter(topic::columns::topic_id.eq_any(topic_ids))
+        .select(topic::columns::teacher_user_name)
+        .load::<String>(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load topics")))?;
+    recipients.push(student_user_name.to_owned());
+    recipients.sort_unstable();
+    recipients.dedup();
+
+    let now = Utc::now();
+    diesel::insert_into(notification::table)
+        .values(
+            recipients
+                .iter()
+                .map(|user_name| NewNotification {
+                    user_name,
+                    notif_content: content.to_owned(),
+                    notif_time: now,
+                })
+                .collect::<Vec<_>>(),
+        )
+        .execute(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to send notifications")))?;
+
+    Ok(())
+}
+
+fn build_attachment_details(attach: Attachment) -> AttachmentDetails {
+    AttachmentDetails {
+        attach_id: attach.attach_id,
+        file_name: attach.attach_file_name,
+        mime_type: attach.attach_mime_type,
+        size: attach.attach_size,
+        sha256: attach.attach_sha256,
+        upload_time: attach.attach_upload_time,
+    }
+}
+
+/// Checks that an attachment exists and was uploaded by the given user, so that they may
+/// submit it.
+fn check_own_attachment(
+    conn: &mut PgConnection,
+    attach_id: i32,
+    user_name: &str,
+) -> Result<(), ApiError> {
+    use backend_database::schema::*;
+
+    let is_own = diesel::select(diesel::dsl::exists(
+        attachment::table
+            .filter(attachment::columns::attach_id.eq(attach_id))
+            .filter(attachment::columns::uploader_user_name.eq(user_name)),
+    ))
+    .get_result::<bool>(conn)
+    .map_err(|_| ApiError::InternalServerError(str!("Failed to check attachment")))?;
+    if !is_own {
+        return Err(ApiError::BadRequest(str!("Unknown attachment")));
+    }
+
+    Ok(())
+}
+
+fn build_report_stage_details(
+    conn: &mut PgConnection,
+    stages: Vec<ReportStage>,
+) -> Result<Vec<ReportStageDetails>, ApiError> {
+    use backend_database::schema::*;
+
+    let stage_ids = stages
+        .iter()
+        .map(|stage| stage.stage_id)
+        .collect::<Vec<_>>();
+    let prerequisites = reportstageprerequisite::table
+        .filter(reportstageprerequisite::columns::stage_id.eq_any(&stage_ids))
+        .order(reportstageprerequisite::columns::prereq_stage_id.asc())
+        .load::<ReportStagePrerequisite>(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load stage prerequisites")))?;
+
+    let mut prereqs_by_stage: HashMap<i16, Vec<i16>> = HashMap::new();
+    for prereq in prerequisites {
+        prereqs_by_stage
+            .entry(prereq.stage_id)
+            .or_default()
+            .push(prereq.prereq_stage_id);
+    }
+
+    stages
+        .into_iter()
+        .map(|stage| {
+            Ok(ReportStageDetails {
+                stage_id: stage.stage_id,
+                stage_name: stage.stage_name,
+                order: stage.stage_order,
+                reviewer: StageReviewer::try_from(stage.stage_reviewer)
+                    .map_err(|_| ApiError::InternalServerError(str!("Invalid stage reviewer")))?,
+                grade_required: stage.stage_grade_required,
+                prerequisites: prereqs_by_stage.remove(&stage.stage_id).unwrap_or_default(),
+            })
+        })
+        .collect()
+}
+
+/// Replaces the prerequisites of a stage, after checking that they exist and that no stage
+/// would end up depending on itself.
+fn replace_stage_prerequisites(
+    conn: &mut PgConnection,
+    stage_id: i16,
+    prerequisites: &[i16],
+) -> Result<(), ApiError> {
+    use backend_database::schema::*;
+
+    let mut prerequisites = prerequisites.to_vec();
+    prerequisites.sort_unstable();
+    prerequisites.dedup();
+
+    let known_stages: i64 = reportstage::table
+        .filter(reportstage::columns::stage_id.eq_any(&prerequisites))
+        .count()
+        .get_result(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to check stages")))?;
+    if known_stages != prerequisites.len() as i64 {
+        return Err(ApiError::BadRequest(str!("Unknown prerequisite stage")));
+    }
+
+    // Walk down from the new prerequisites; reaching this stage would close a cycle.
+    let edges = reportstageprerequisite::table
+        .filter(reportstageprerequisite::columns::stage_id.ne(stage_id))
+        .load::<ReportStagePrerequisite>(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load stage prerequisites")))?;
+    let mut pending = prerequisites.clone();
+    let mut seen = HashSet::new();
+    while let Some(current) = pending.pop() {
+        if current == stage_id {
+            return Err(ApiError::BadRequest(str!(
+                "A stage cannot depend on itself, directly or through other stages"
+            )));
+        }
+        if seen.insert(current) {
+            pending.extend(
+                edges
+                    .iter()
+                    .filter(|edge| edge.stage_id == current)
+                    .map(|edge| edge.prereq_stage_id),
+            );
+        }
+    }
+
+    diesel::delete(
+        reportstageprerequisite::table
+            .filter(reportstageprerequisite::columns::stage_id.eq(stage_id)),
+    )
+    .execute(conn)
+    .map_err(|_| ApiError::InternalServerError(str!("Failed to clear stage prerequisites")))?;
+    diesel::insert_into(reportstageprerequisite::table)
+        .values(
+            prerequisites
+                .iter()
+                .map(|&prereq_stage_id| NewReportStagePrerequisite {
+                    stage_id,
+                    prereq_stage_id,
+                })
+                .collect::<Vec<_>>(),
+        )
+        .execute(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to save stage prerequisites")))?;
+
+    Ok(())
+}
+
+fn check_stage_name(
+    conn: &mut PgConnection,
+    stage_id: Option<i16>,
+    name: &str,
+) -> Result<(), ApiError> {
+    use backend_database::schema::*;
+
+    if name.is_empty() {
+        return Err(ApiError::BadRequest(str!("Stage name must not be empty")));
+    }
+    if name.chars().count() > MAX_STAGE_NAME_CHARS {
+        return Err(ApiError::BadRequest(format!(
+            "Stage name must be at most {} characters",
+            MAX_STAGE_NAME_CHARS
+        )));
+    }
+
+    let mut same_name = reportstage::table
+        .filter(reportstage::columns::stage_name.eq(name))
+        .into_boxed();
+    if let Some(stage_id) = stage_id {
+        same_name = same_name.filter(reportstage::columns::stage_id.ne(stage_id));
+    }
+    let taken = diesel::select(diesel::dsl::exists(same_name))
+        .get_result::<bool>(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to check stage names")))?;
+    if taken {
+        return Err(ApiError::Conflict(str!(
+            "A stage with this name already exists"
+        )));
+    }
+
+    Ok(())
+}
+
+/// Returns the stages a student has passed on a topic.
+fn load_passed_stage_ids(
+    conn: &mut PgConnection,
+    student_user_name: &str,
+    topic_id: i32,
+) -> Result<HashSet<i16>, ApiError> {
+    use backend_database::schema::*;
+
+    progressreport::table
+        .filter(progressreport::columns::student_user_name.eq(student_user_name))
+        .filter(progressreport::columns::topic_id.eq(topic_id))
+        .filter(progressreport::columns::prog_report_outcome.eq(ProgressOutcome::Passed as i16))
+        .select(progressreport::columns::prog_report_stage_id)
+        .load::<i16>(conn)
+        .map(|stage_ids| stage_ids.into_iter().collect())
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load passed stages")))
+}
+
+/// Returns the stages that require a stage to be passed, directly or through other stages.
+fn load_dependent_stage_ids(
+    conn: &mut PgConnection,
+    stage_id: i16,
+) -> Result<HashSet<i16>, ApiError> {
+    use backend_database::schema::*;
+
+    let edges = reportstageprerequisite::table
+        .load::<ReportStagePrerequisite>(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load stage prerequisites")))?;
+    let mut pending = vec![stage_id];
+    let mut dependents = HashSet::new();
+    while let Some(current) = pending.pop() {
+        for edge in edges.iter().filter(|edge| edge.prereq_stage_id == current) {
+            if dependents.insert(edge.stage_id) {
+                pending.push(edge.stage_id);
+            }
+        }
+    }
+
+    Ok(dependents)
+}
+
+/// Returns the stages a final defense builds on: the last stages of the pipeline, which no other
+/// stage requires.
+fn load_defense_stage_ids(conn: &mut PgConnection) -> Result<HashSet<i16>, ApiError> {
+    use backend_database::schema::*;
+
+    let prereq_stage_ids = reportstageprerequisite::table
+        .select(reportstageprerequisite::columns::prereq_stage_id)
+        .load::<i16>(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load stage prerequisites")))?;
+    reportstage::table
+        .select(reportstage::columns::stage_id)
+        .load::<i16>(conn)
+        .map(|stage_ids| {
+            stage_ids
+                .into_iter()
+                .filter(|stage_id| !prereq_stage_ids.contains(stage_id))
+                .collect()
+        })
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load report stages")))
+}
+
+/// Refuses to revise a student's concluded outcomes once the student's major is locked.
+fn check_revision_lock(conn: &mut PgConnection, student_user_name: &str) -> Result<(), ApiError> {
+    use backend_database::schema::*;
+
+    let lock_time = student::table
+        .inner_join(
+            revisionlock::table.on(revisionlock::columns::major_id.eq(student::columns::major_id)),
+        )
+        .filter(student::columns::user_name.eq(student_user_name))
+        .select(revisionlock::columns::rev_lock_time)
+        .first::<DateTime<Utc>>(conn)
+        .optional()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load revision lock")))?;
+    if lock_time.is_some_and(|lock_time| Utc::now() >= lock_time) {
+        return Err(ApiError::Conflict(str!(
+            "Outcomes of this student's cohort are locked"
+        )));
+    }
+
+    Ok(())
+}
+
+/// Checks whether a user reviews a progress report, as decided by the reviewer of its stage.
+fn can_review_progress_report(
+    conn: &mut PgConnection,
+    report: &ProgressReport,
+    stage: &ReportStage,
+    teacher_user_name: &str,
+    username: &str,
+    user_role: &AuthInfoUserRole,
+) -> Result<bool, ApiError> {
+    match StageReviewer::try_from(stage.stage_reviewer)
+        .map_err(|_| ApiError::InternalServerError(str!("Invalid stage reviewer")))?
+    {
+        StageReviewer::Supervisor => Ok(matches!(user_role, AuthInfoUserRole::Teacher)
+            && can_approve_for_topic(conn, report.topic_id, teacher_user_name, username)?),
+        StageReviewer::Office => Ok(matches!(user_role, AuthInfoUserRole::Office)),
+    }
+}
+
+fn build_progress_report_details(
+    conn: &mut PgConnection,
+    report: ProgressReport,
+    student_name: String,
+    stage_name: String,
+) -> Result<ProgressReportDetailResponse, ApiError> {
+    Ok(ProgressReportDetailResponse {
+        prog_report_id: report.prog_report_id,
+        topic_id: report.topic_id,
+        student_user_name: report.student_user_name,
+        student_name,
+        prog_report_stage_id: report.prog_report_stage_id,
+        prog_report_stage_name: stage_name,
+        prog_report_time: report.prog_report_time,
+        prog_report_version: report.prog_report_version,
+        prog_report_files: load_progress_report_files(conn, &[report.prog_report_id])?
+            .remove(&report.prog_report_id)
+            .unwrap_or_default(),
+        prog_report_due_time: report.prog_report_due_time,
+        prog_report_late: report
+            .prog_report_due_time
+            .is_some_and(|due_time| report.prog_report_time > due_time),
+        prog_report_outcome: ProgressOutcome::try_from(report.prog_report_outcome)
+            .map_err(|_| ApiError::InternalServerError(str!("Invalid progress outcome")))?,
+        prog_report_comment: report.prog_report_comment,
+        prog_report_grade: report.prog_report_grade,
+        prog_report_scores: load_progress_report_scores(conn, &[report.prog_report_id])?
+            .remove(&report.prog_report_id)
+            .unwrap_or_default(),
+    })
+}
+
+/// Replaces the rubric scores recorded for a progress report.
+fn replace_progress_report_scores(
+    conn: &mut PgConnection,
+    report_id: i32,
+    scores: Vec<(i32, BigDecimal)>,
+) -> Result<(), ApiError> {
+    use backend_database::schema::*;
+
+    diesel::delete(
+        progressreportscore::table
+            .filter(progressreportscore::columns::prog_report_id.eq(report_id)),
+    )
+    .execute(conn)
+    .map_err(|_| ApiError::InternalServerError(str!("Failed to clear report scores")))?;
+    diesel::insert_into(progressreportscore::table)
+        .values(
+            scores
+                .into_iter()
+                .map(|(crit_id, score)| NewProgressReportScore {
+                    prog_report_id: report_id,
+                    crit_id,
+                    prog_score: score,
+                })
+                .collect::<Vec<_>>(),
+        )
+        .execute(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to record report scores")))?;
+
+    Ok(())
+}
+
+/// Returns a student's deadline for a report stage, or for the final defense when `stage_id`
+/// is `None`, together with whether it is hard. An extension can only push the deadline back.
+fn load_effective_deadline(
+    conn: &mut PgConnection,
+    student: &Student,
+    stage_id: Option<i16>,
+) -> Result<Option<(DateTime<Utc>, bool)>, ApiError> {
+    use backend_database::schema::*;
+
+    let mut query = submissiondeadline::table
+        .filter(submissiondeadline::columns::major_id.eq(student.major_id))
+        .into_boxed();
+    query = match stage_id {
+        Some(stage_id) => query.filter(submissiondeadline::columns::stage_id.eq(stage_id)),
+        None => query.filter(submissiondeadline::columns::stage_id.is_null()),
+    };
+    let Some(deadline) = query
+        .first::<SubmissionDeadline>(conn)
+        .optional()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load deadline")))?
+    else {
+        return Ok(None);
+    };
+
+    let extension = deadlineextension::table
+        .find((deadline.deadline_id, &student.user_name))
+        .select(deadlineextension::columns::ext_due_time)
+        .first::<DateTime<Utc>>(conn)
+        .optional()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load deadline extension")))?;
+    let due_time = extension.map_or(deadline.deadline_due_time, |ext_due_time| {
+        ext_due_time.max(deadline.deadline_due_time)
+    });
+
+    Ok(Some((due_time, deadline.deadline_is_hard)))
+}
+
+/// Returns the rubric of a report stage, or of the final defense when `stage_id` is `None`.
+fn load_rubric_criteria(
+    conn: &mut PgConnection,
+    stage_id: Option<i16>,
+) -> Result<Vec<RubricCriterion>, ApiError> {
+    use backend_database::schema::*;
+
+    let mut query = rubriccriterion::table.into_boxed();
+    query = match stage_id {
+        Some(stage_id) => query.filter(rubriccriterion::columns::stage_id.eq(stage_id)),
+        None => query.filter(rubriccriterion::columns::stage_id.is_null()),
+    };
+    query
+        .order((
+            rubriccriterion::columns::crit_order.asc(),
+            rubriccriterion::columns::crit_id.asc(),
+        ))
+        .load::<RubricCriterion>(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load rubric")))
+}
+
+/// Checks a review's grade against the rubric of a report stage, or of the final defense when
+/// `stage_id` is `None`, and returns the grade to store along with the score of each criterion.
+///
+/// With a rubric, either every criterion is scored within its range, or none is and no grade is
+/// given; the grade is the weighted sum of the scores, out of 100. Without a rubric, a grade may
+/// be given directly, out of 100.
+fn check_rubric_scores(
+    conn: &mut PgConnection,
+    stage_id: Option<i16>,
+    grade: Option<&BigDecimal>,
+    scores: Option<&[CriterionScoreRequest]>,
+) -> Result<RubricGrade, ApiError> {
+    let criteria = load_rubric_criteria(conn, stage_id)?;
+    let scores = scores.unwrap_or_default();
+    let zero = BigDecimal::from(0);
+    let hundred = BigDecimal::from(100);
+
+    if criteria.is_empty() {
+        if !scores.is_empty() {
+            return Err(ApiError::BadRequest(str!(
+                "There is no rubric to score against"
+            )));
+        }
+        if grade.is_some_and(|grade| *grade < zero || *grade > hundred) {
+            return Err(ApiError::BadRequest(str!(
+                "Grade must be between 0 and 100"
+            )));
+        }
+        return Ok((grade.cloned(), Vec::new()));
+    }
+
+    if grade.is_some() {
+        return Err(ApiError::BadRequest(str!(
+            "The grade is computed from the rubric scores"
+        )));
+    }
+    if scores.is_empty() {
+        return Ok((None, Vec::new()));
+    }
+
+    let mut score_by_criterion = HashMap::new();
+    for score in scores {
+        if score_by_criterion
+            .insert(score.criterion_id, &score.score)
+            .is_some()
+        {
+            return Err(ApiError::BadRequest(str!(
+                "A rubric criterion may only be scored once"
+            )));
+        }
+    }
+    if score_by_criterion.len() != criteria.len()
+        || criteria
+            .iter()
+            .any(|criterion| !score_by_criterion.contains_key(&criterion.crit_id))
+    {
+        return Err(ApiError::BadRequest(str!(
+            "Every criterion of the rubric must be scored"
+        )));
+    }
+
+    let mut weighted = BigDecimal::from(0);
+    let mut total_weight = BigDecimal::from(0);
+    let mut checked = Vec::with_capacity(criteria.len());
+    for criterion in criteria {
+        let score = score_by_criterion[&criterion.crit_id];
+        if *score < zero || *score > criterion.crit_max_score {
+            return Err(ApiError::BadRequest(format!(
+                "Score for \"{}\" must be between 0 and {}",
+                criterion.crit_name, criterion.crit_max_score
+            )));
+        }
+        weighted += &criterion.crit_weight * score / &criterion.crit_max_score;
+        total_weight += &criterion.crit_weight;
+        checked.push((criterion.crit_id, score.clone()));
+    }
+    let grade = (weighted * hundred / total_weight).with_scale_round(2, RoundingMode::HalfUp);
+
+    Ok((Some(grade), checked))
+}
+
+fn build_rubric_criterion_details(criterion: RubricCriterion) -> RubricCriterionDetails {
+    RubricCriterionDetails {
+        criterion_id: criterion.crit_id,
+        name: criterion.crit_name,
+        weight: criterion.crit_weight,
+        max_score: criterion.crit_max_score,
+    }
+}
+
+fn load_progress_report_scores(
+    conn: &mut PgConnection,
+    report_ids: &[i32],
+) -> Result<HashMap<i32, Vec<CriterionScoreDetails>>, ApiError> {
+    use backend_database::schema::*;
+
+    let rows = progressreportscore::table
+        .inner_join(rubriccriterion::table)
+        .filter(progressreportscore::columns::prog_report_id.eq_any(report_ids))
+        .order((
+            rubriccriterion::columns::crit_order.asc(),
+            rubriccriterion::columns::crit_id.asc(),
+        ))
+        .select((
+            progressreportscore::all_columns,
+            rubriccriterion::all_columns,
+        ))
+        .load::<(ProgressReportScore, RubricCriterion)>(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load report scores")))?;
+
+    let mut scores_by_report: HashMap<i32, Vec<CriterionScoreDetails>> = HashMap::new();
+    for (score, criterion) in rows {
+        scores_by_report
+            .entry(score.prog_report_id)
+            .or_default()
+            .push(CriterionScoreDetails {
+                criterion_id: criterion.crit_id,
+                criterion_name: criterion.crit_name,
+                max_score: criterion.crit_max_score,
+                score: score.prog_score,
+            });
+    }
+
+    Ok(scores_by_report)
+}
+
+fn load_final_defense_scores(
+    conn: &mut PgConnection,
+    defense_ids: &[i32],
+) -> Result<HashMap<i32, Vec<CriterionScoreDetails>>, ApiError> {
+    use backend_database::schema::*;
+
+    let rows = finaldefensescore::table
+        .inner_join(rubriccriterion::table)
+        .filter(finaldefensescore::columns::final_def_id.eq_any(defense_ids))
+        .order((
+            rubriccriterion::columns::crit_order.asc(),
+            rubriccriterion::columns::crit_id.asc(),
+        ))
+        .select((finaldefensescore::all_columns, rubriccriterion::all_columns))
+        .load::<(FinalDefenseScore, RubricCriterion)>(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load defense scores")))?;
+
+    let mut scores_by_defense: HashMap<i32, Vec<CriterionScoreDetails>> = HashMap::new();
+    for (score, criterion) in rows {
+        scores_by_defense
+            .entry(score.final_def_id)
+            .or_default()
+            .push(CriterionScoreDetails {
+                criterion_id: criterion.crit_id,
+                criterion_name: criterion.crit_name,
+                max_score: criterion.crit_max_score,
+                score: score.def_score,
+            });
+    }
+
+    Ok(scores_by_defense)
+}
+
+/// Builds deadline details, with the extensions of all students or of a single one.
+fn build_deadline_details(
+    conn: &mut PgConnection,
+    deadlines: Vec<SubmissionDeadline>,
+    only_student: Option<&str>,
+) -> Result<Vec<DeadlineDetails>, ApiError> {
+    use backend_database::schema::*;
+
+    let deadline_ids = deadlines
+        .iter()
+        .map(|deadline| deadline.deadline_id)
+        .collect::<Vec<_>>();
+    let mut query = deadlineextension::table
+        .inner_join(student::table)
+        .filter(deadlineextension::columns::deadline_id.eq_any(&deadline_ids))
+        .into_boxed();
+    if let Some(user_name) = only_student {
+        query = query.filter(deadlineextension::columns::student_user_name.eq(user_name));
+    }
+    let extensions = query
+        .order(deadlineextension::columns::student_user_name.asc())
+        .select((
+            deadlineextension::all_columns,
+            student::columns::student_name,
+        ))
+        .load::<(DeadlineExtension, String)>(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load deadline extensions")))?;
+
+    let mut extensions_by_deadline: HashMap<i32, Vec<DeadlineExtensionDetails>> = HashMap::new();
+    for (ext, student_name) in extensions {
+        extensions_by_deadline
+            .entry(ext.deadline_id)
+            .or_default()
+            .push(DeadlineExtensionDetails {
+                student_user_name: ext.student_user_name,
+                student_name,
+                due_time: ext.ext_due_time,
+            });
+    }
+
+    Ok(deadlines
+        .into_iter()
+        .map(|deadline| DeadlineDetails {
+            deadline_id: deadline.deadline_id,
+            major_id: deadline.major_id,
+            stage_id: deadline.stage_id,
+            due_time: deadline.deadline_due_time,
+            is_hard: deadline.deadline_is_hard,
+            extensions: extensions_by_deadline
+                .remove(&deadline.deadline_id)
+                .unwrap_or_default(),
+        })
+        .collect())
+}
+
+/// Checks the files of a submission: exactly one thesis, at most one slide deck and one
+/// source archive, and any number of other files, all uploaded by the submitting student.
+fn check_submission_files(
+    conn: &mut PgConnection,
+    files: &[SubmissionFileRequest],
+    user_name: &str,
+) -> Result<(), ApiError> {
+    let count_kind = |kind| files.iter().filter(|file| file.kind == kind).count();
+    if count_kind(SubmissionFileKind::Thesis) != 1 {
+        return Err(ApiError::BadRequest(str!(
+            "A submission must contain exactly one thesis file"
+        )));
+    }
+    if count_kind(SubmissionFileKind::Slides) > 1 || count_kind(SubmissionFileKind::Source) > 1 {
+        return Err(ApiError::BadRequest(str!(
+            "A submission may contain at most one slide deck and one source archive"
+        )));
+    }
+
+    let mut seen = HashSet::new();
+    for file in files {
+        if !seen.insert(file.attachment_id) {
+            return Err(ApiError::BadRequest(str!(
+                "An attachment may only be submitted once"
+            )));
+        }
+        check_own_attachment(conn, file.attachment_id, user_name)?;
+    }
+
+    Ok(())
+}
+
+fn load_progress_report_files(
+    conn: &mut PgConnection,
+    report_ids: &[i32],
+) -> Result<HashMap<i32, Vec<SubmissionFileDetails>>, ApiError> {
+    use backend_database::schema::*;
+
+    let rows = progressreportfile::table
+        .inner_join(attachment::table)
+        .filter(progressreportfile::columns::prog_report_id.eq_any(report_ids))
+        .order((
+            progressreportfile::columns::prog_file_kind.asc(),
+            progressreportfile::columns::attach_id.asc(),
+        ))
+        .select((progressreportfile::all_columns, attachment::all_columns))
+        .load::<(ProgressReportFile, Attachment)>(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load report files")))?;
+
+    let mut files_by_report: HashMap<i32, Vec<SubmissionFileDetails>> = HashMap::new();
+    for (file, attach) in rows {
+        files_by_report
+            .entry(file.prog_report_id)
+            .or_default()
+            .push(SubmissionFileDetails {
+                kind: SubmissionFileKind::try_from(file.prog_file_kind)
+                    .map_err(|_| ApiError::InternalServerError(str!("Invalid file kind")))?,
+                attachment: build_attachment_details(attach),
+            });
+    }
+
+    Ok(files_by_report)
+}
+
+fn load_final_defense_files(
+    conn: &mut PgConnection,
+    defense_ids: &[i32],
+) -> Result<HashMap<i32, Vec<SubmissionFileDetails>>, ApiError> {
+    use backend_database::schema::*;
+
+    let rows = finaldefensefile::table
+        .inner_join(attachment::table)
+        .filter(finaldefensefile::columns::final_def_id.eq_any(defense_ids))
+        .order((
+            finaldefensefile::columns::def_file_kind.asc(),
+            finaldefensefile::columns::attach_id.asc(),
+        ))
+        .select((finaldefensefile::all_columns, attachment::all_columns))
+        .load::<(FinalDefenseFile, Attachment)>(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load defense files")))?;
+
+    let mut files_by_defense: HashMap<i32, Vec<SubmissionFileDetails>> = HashMap::new();
+    for (file, attach) in rows {
+        files_by_defense
+            .entry(file.final_def_id)
+            .or_default()
+            .push(SubmissionFileDetails {
+                kind: SubmissionFileKind::try_from(file.def_file_kind)
+                    .map_err(|_| ApiError::InternalServerError(str!("Invalid file kind")))?,
+                attachment: build_attachment_details(attach),
+            });
+    }
+
+    Ok(files_by_defense)
+}
+
+/// Loads the cached texts of attachments for the similarity check, by attachment ID. An
+/// attachment from which no text could be extracted maps to `None`; one not extracted yet is
+/// missing.
+fn load_attachment_texts(
+    conn: &mut PgConnection,
+    attach_ids: &[i32],
+) -> Result<HashMap<i32, Option<String>>, ApiError> {
+    use backend_database::schema::*;
+
+    attachmenttext::table
+        .filter(attachmenttext::columns::attach_id.eq_any(attach_ids))
+        .select((
+            attachmenttext::columns::attach_id,
+            attachmenttext::columns::attach_text,
+        ))
+        .load::<(i32, Option<String>)>(conn)
+        .map(|texts| texts.into_iter().collect())
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load attachment texts")))
+}
+
+/// Extracts the text of an attachment for the similarity check. Returns `None` if no text could
+/// be extracted.
+fn extract_attachment_text(attach: &Attachment) -> Result<Option<String>, ApiError> {
+    let content = match &attach.attach_legacy_content {
+        Some(content) => content.clone(),
+        None => std::fs::read(crate::attachment::content_path(&attach.attach_sha256))
+            .map_err(|_| ApiError::InternalServerError(str!("Attachment content is missing")))?,
+    };
+
+    Ok(crate::similarity::extract_text(
+        &content,
+        &attach.attach_mime_type,
+    ))
+}
+
+/// Caches extracted attachment texts, keeping any stored in the meantime.
+fn store_attachment_texts(
+    conn: &mut PgConnection,
+    texts: &[(i32, Option<String>)],
+) -> Result<(), ApiError> {
+    use backend_database::schema::*;
+
+    let now = Utc::now();
+    diesel::insert_into(attachmenttext::table)
+        .values(
+            texts
+                .iter()
+                .map(|(attach_id, text)| NewAttachmentText {
+                    attach_id: *attach_id,
+                    attach_text: text.as_deref(),
+                    attach_text_time: now,
+                })
+                .collect::<Vec<_>>(),
+        )
+        .on_conflict_do_nothing()
+        .execute(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to store attachment texts")))?;
+
+    Ok(())
+}
+
+/// Checks whether a user may download an attachment. Its uploader may, and so may anyone who
+/// can see a progress report or final defense it was submitted with.
+fn can_view_attachment(
+    conn: &mut PgConnection,
+    attach: &Attachment,
+    username: &str,
+    user_role: &AuthInfoUserRole,
+) -> Result<bool, ApiError> {
+    use backend_database::schema::*;
+
+    if attach.uploader_user_name == username {
+        return Ok(true);
+    }
+
+    let mut reports = progressreportfile::table
+        .inner_join(
+            progressreport::table
+                .inner_join(topic::table)
+                .inner_join(reportstage::table),
+        )
+        .filter(progressreportfile::columns::attach_id.eq(attach.attach_id))
+        .select(progressreport::columns::prog_report_id)
+        .into_boxed();
+    let mut defenses = finaldefensefile::table
+        .inner_join(finaldefense::table.inner_join(topic::table))
+        .filter(finaldefensefile::columns::attach_id.eq(attach.attach_id))
+        .select(finaldefense::columns::final_def_id)
+        .into_boxed();
+
+    match user_role {
+        AuthInfoUserRole::Student => {
+            reports = reports.filter(progressreport::columns::student_user_name.eq(username));
+            defenses = defenses.filter(finaldefense::columns::student_user_name.eq(username));
+        }
+        AuthInfoUserRole::Teacher => {
+            reports = reports.filter(
+                topic::columns::teacher_user_name
+                    .eq(username)
+                    .or(topic::columns::topic_id.eq_any(cosupervised_topic_ids(username))),
+            );
+            defenses = defenses.filter(
+                topic::columns::teacher_user_name
+                    .eq(username)
+                    .or(topic::columns::topic_id.eq_any(cosupervised_topic_ids(username))),
+            );
+        }
+        AuthInfoUserRole::DefenseBoard => {
+            defenses = defenses.filter(finaldefense::columns::def_board_user_name.eq(username));
+        }
+        AuthInfoUserRole::Office => {}
+    }
+
+    // Defense boards only see the final defenses assigned to them, and office only the
+    // progress reports it oversees
+    let in_reports = !matches!(user_role, AuthInfoUserRole::DefenseBoard)
+        && diesel::select(diesel::dsl::exists(reports))
+            .get_result::<bool>(conn)
+            .map_err(|_| {
+                ApiError::InternalServerError(str!("Failed to check attachment access"))
+            })?;
+    let in_defenses = !matches!(user_role, AuthInfoUserRole::Office)
+        && diesel::select(diesel::dsl::exists(defenses))
+            .get_result::<bool>(conn)
+            .map_err(|_| {
+                ApiError::InternalServerError(str!("Failed to check attachment access"))
+            })?;
+
+    if in_reports || in_defenses {
+        return Ok(true);
+    }
+
+    // Attachments referenced in a discussion are visible to everyone taking part in it
+    let comments = submissioncomment::table
+        .filter(submissioncomment::columns::attach_id.eq(attach.attach_id))
+        .load::<SubmissionComment>(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to check attachment access")))?;
+    for comment in comments {
+        let subject = match (comment.prog_report_id, comment.final_def_id) {
+            (Some(report_id), _) => CommentSubject::ProgressReport(report_id),
+            (None, Some(defense_id)) => CommentSubject::FinalDefense(defense_id),
+            (None, None) => continue,
+        };
+        if can_view_comment_subject(conn, subject, username, user_role)? {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+/// Checks whether a user may see a progress report and take part in its discussion: its
+/// student, the topic's supervisors, and office, which oversees every report.
+fn can_view_progress_report(
+    conn: &mut PgConnection,
+    report: &ProgressReport,
+    username: &str,
+    user_role: &AuthInfoUserRole,
+) -> Result<bool, ApiError> {
+    match user_role {
+        AuthInfoUserRole::Student => Ok(report.student_user_name == username),
+        AuthInfoUserRole::Teacher => supervises_topic(conn, report.topic_id, username),
+        AuthInfoUserRole::Office => Ok(true),
+        AuthInfoUserRole::DefenseBoard => Ok(false),
+    }
+}
+
+/// Checks whether a user may see a final defense and take part in its discussion: its
+/// student, the topic's supervisors, and the defense board assigned to it.
+fn can_view_final_defense(
+    conn: &mut PgConnection,
+    defense: &FinalDefense,
+    username: &str,
+    user_role: &AuthInfoUserRole,
+) -> Result<bool, ApiError> {
+    match user_role {
+        AuthInfoUserRole::Student => Ok(defense.student_user_name == username),
+        AuthInfoUserRole::Teacher => supervises_topic(conn, defense.topic_id, username),
+        AuthInfoUserRole::DefenseBoard => {
+            Ok(defense.def_board_user_name.as_deref() == Some(username))
+        }
+        AuthInfoUserRole::Office => Ok(false),
+    }
+}
+
+fn can_view_comment_subject(
+    conn: &mut PgConnection,
+    subject: CommentSubject,
+    username: &str,
+    user_role: &AuthInfoUserRole,
+) -> Result<bool, ApiError> {
+    use backend_database::schema::*;
+
+    match subject {
+        CommentSubject::ProgressReport(report_id) => {
+            let report = progressreport::table
+                .find(report_id)
+                .first::<ProgressReport>(conn)
+                .map_err(|_| {
+                    ApiError::InternalServerError(str!("Failed to load progress report"))
+                })?;
+            can_view_progress_report(conn, &report, username, user_role)
+        }
+        CommentSubject::FinalDefense(defense_id) => {
+            let defense = finaldefense::table
+                .find(defense_id)
+                .first::<FinalDefense>(conn)
+                .map_err(|_| ApiError::InternalServerError(str!("Failed to load final defense")))?;
+            can_view_final_defense(conn, &defense, username, user_role)
+        }
+    }
+}
+
+/// Loads the comments on a submission, oldest first.
+fn load_comment_details(
+    conn: &mut PgConnection,
+    subject: CommentSubject,
+) -> Result<Vec<CommentDetails>, ApiError> {
+    use backend_database::schema::*;
+
+    let mut query = submissioncomment::table
+        .left_join(attachment::table)
+        .into_boxed();
+    query = match subject {
+        CommentSubject::ProgressReport(report_id) => {
+            query.filter(submissioncomment::columns::prog_report_id.eq(report_id))
+        }
+        CommentSubject::FinalDefense(defense_id) => {
+            query.filter(submissioncomment::columns::final_def_id.eq(defense_id))
+        }
+    };
+    let rows = query
+        .order((
+            submissioncomment::columns::comment_time.asc(),
+            submissioncomment::columns::comment_id.asc(),
+        ))
+        .select((
+            submissioncomment::all_columns,
+            attachment::all_columns.nullable(),
+        ))
+        .load::<(SubmissionComment, Option<Attachment>)>(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load comments")))?;
+
+    build_comment_details(conn, rows)
+}
+
+fn build_comment_details(
+    conn: &mut PgConnection,
+    rows: Vec<(SubmissionComment, Option<Attachment>)>,
+) -> Result<Vec<CommentDetails>, ApiError> {
+    use backend_database::schema::*;
+
+    let authors = rows
+        .iter()
+        .map(|(comment, _)| comment.author_user_name.as_str())
+        .collect::<HashSet<_>>();
+    let mut author_names = student::table
+        .filter(student::columns::user_name.eq_any(&authors))
+        .select((student::columns::user_name, student::columns::student_name))
+        .load::<(String, String)>(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load comment authors")))?
+        .into_iter()
+        .collect::<HashMap<_, _>>();
+    author_names.extend(
+        teacher::table
+            .filter(teacher::columns::user_name.eq_any(&authors))
+            .select((teacher::columns::user_name, teacher::columns::teacher_name))
+            .load::<(String, String)>(conn)
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to load comment authors")))?,
+    );
+
+    rows.into_iter()
+        .map(|(comment, attach)| {
+            Ok(CommentDetails {
+                comment_id: comment.comment_id,
+                parent_id: comment.comment_parent_id,
+                author_name: author_names.get(&comment.author_user_name).cloned(),
+                author_user_name: comment.author_user_name,
+                author_role: u8::try_from(comment.comment_author_role)
+                    .ok()
+                    .and_then(|role| UserRole::try_from(role).ok())
+                    .ok_or_else(|| ApiError::InternalServerError(str!("Invalid author role")))?,
+                content: comment.comment_content,
+                time: comment.comment_time,
+                attachment: attach.map(build_attachment_details),
+            })
+        })
+        .collect()
+}
+
+/// Adds a comment to a submission and notifies the other people taking part in it.
+fn create_comment(
+    conn: &mut PgConnection,
+    subject: CommentSubject,
+    req: &CommentsPostRequest,
+    username: &str,
+    user_role: &AuthInfoUserRole,
+    participants: &[&str],
+    notif_content: String,
+) -> Result<CommentDetails, ApiError> {
+    use backend_database::schema::*;
+
+    let content = req.content.trim();
+    if content.is_empty() || content.chars().count() > MAX_COMMENT_LEN {
+        return Err(ApiError::BadRequest(format!(
+            "Comments must be 1 to {MAX_COMMENT_LEN} characters"
+        )));
+    }
+    let (prog_report_id, final_def_id) = match subject {
+        CommentSubject::ProgressReport(report_id) => (Some(report_id), None),
+        CommentSubject::FinalDefense(defense_id) => (None, Some(defense_id)),
+    };
+    if let Some(parent_id) = req.parent_id {
+        let parent = submissioncomment::table
+            .find(parent_id)
+            .first::<SubmissionComment>(conn)
+            .optional()
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to load comment")))?;
+        // Threads are one level deep: replies go under a top-level comment of the same thread
+        if !parent.is_some_and(|parent| {
+            parent.prog_report_id == prog_report_id
+                && parent.final_def_id == final_def_id
+                && parent.comment_parent_id.is_none()
+        }) {
+            return Err(ApiError::BadRequest(str!(
+                "Replies must answer a top-level comment of the same thread"
+            )));
+        }
+    }
+    if let Some(attach_id) = req.attachment_id {
+        check_own_attachment(conn, attach_id, username)?;
+    }
+
+    let author_role = match user_role {
+        AuthInfoUserRole::Student => UserRole::Student,
+        AuthInfoUserRole::Teacher => UserRole::Teacher,
+        AuthInfoUserRole::DefenseBoard => UserRole::DefenseBoard,
+        AuthInfoUserRole::Office => UserRole::Office,
+    };
+    let now = Utc::now();
+    let comment = diesel::insert_into(submissioncomment::table)
+        .values(NewSubmissionComment {
+            prog_report_id,
+            final_def_id,
+            comment_parent_id: req.parent_id,
+            author_user_name: username,
+            comment_author_role: u8::from(author_role) as i16,
+            comment_content: content,
+            comment_time: now,
+            attach_id: req.attachment_id,
+        })
+        .get_result::<SubmissionComment>(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to create comment")))?;
+
+    let recipients = participants
+        .iter()
+        .copied()
+        .filter(|&user_name| user_name != username)
+        .collect::<HashSet<_>>();
+    diesel::insert_into(notification::table)
+        .values(
+            recipients
+                .into_iter()
+                .map(|user_name| NewNotification {
+                    user_name,
+                    notif_content: notif_content.clone(),
+                    notif_time: now,
+                })
+                .collect::<Vec<_>>(),
+        )
+        .execute(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to send notifications")))?;
+
+    let attach = comment
+        .attach_id
+        .map(|attach_id| attachment::table.find(attach_id).first::<Attachment>(conn))
+        .transpose()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load attachment")))?;
+    let mut details = build_comment_details(conn, vec![(comment, attach)])?;
+    Ok(details.remove(0))
+}
+
+fn build_matching_round_details(round: MatchingRound) -> MatchingRoundDetails {
+    MatchingRoundDetails {
+        match_round_id: round.match_round_id,
+        match_round_name: round.match_round_name,
+        max_preferences: round.match_round_max_prefs,
+        open_time: round.match_round_open_time,
+        close_time: round.match_round_close_time,
+        commit_time: round.match_round_commit_time,
+    }
+}
+
+/// Loads a matching round whose result has not been committed yet.
+fn load_uncommitted_matching_round(
+    conn: &mut PgConnection,
+    match_round_id: i32,
+) -> Result<MatchingRound, ApiError> {
+    use backend_database::schema::*;
+
+    let round = matchinground::dsl::matchinground
+        .find(match_round_id)
+        .for_update()
+        .first::<MatchingRound>(conn)
+        .map_err(|_| ApiError::NotFound)?;
+    if round.match_round_commit_time.is_some() {
+        return Err(ApiError::Conflict(str!(
+            "Matching round has already been committed"
+        )));
+    }
+    Ok(round)
+}
+
+/// Loads a student's preferences in a matching round, most preferred first.
+fn load_student_preferences(
+    conn: &mut PgConnection,
+    match_round_id: i32,
+    student_user_name: &str,
+) -> Result<Vec<PreferenceDetails>, ApiError> {
+    use backend_database::schema::*;
+
+    let rows = topicpreference::table
+        .inner_join(topic::table)
+        .filter(topicpreference::columns::match_round_id.eq(match_round_id))
+        .filter(topicpreference::columns::student_user_name.eq(student_user_name))
+        .order(topicpreference::columns::pref_rank.asc())
+        .select((
+            topicpreference::columns::pref_rank,
+            topicpreference::columns::topic_id,
+            topic::columns::topic_name,
+        ))
+        .load::<(i16, i32, String)>(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load preferences")))?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(pref_rank, topic_id, topic_name)| PreferenceDetails {
+            pref_rank,
+            topic_id,
+            topic_name,
+        })
+        .collect())
+}
+
+/// Loads the applicants of topics in a matching round.
+///
+/// Applicants ranked by the teacher come first in rank order, followed by unranked ones.
+fn load_topic_applicants(
+    conn: &mut PgConnection,
+    match_round_id: i32,
+    topic_ids: &[i32],
+) -> Result<Vec<TopicApplicants>, ApiError> {
+    use backend_database::schema::*;
+
+    let topics = topic::table
+        .filter(topic::columns::topic_id.eq_any(topic_ids))
+        .order(topic::columns::topic_id.asc())
+        .select((
+            topic::columns::topic_id,
+            topic::columns::topic_name,
+            topic::columns::topic_max_students,
+        ))
+        .load::<(i32, String, i32)>(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load topics")))?;
+
+    let preferences = topicpreference::table
+        .inner_join(student::table)
+        .filter(topicpreference::columns::match_round_id.eq(match_round_id))
+        .filter(topicpreference::columns::topic_id.eq_any(topic_ids))
+        .select((
+            topicpreference::columns::topic_id,
+            topicpreference::columns::student_user_name,
+            student::columns::student_name,
+            topicpreference::columns::pref_rank,
+        ))
+        .load::<(i32, String, String, i16)>(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load applicants")))?;
+
+    let ranks: HashMap<(i32, String), i32> = applicantrank::table
+        .filter(applicantrank::columns::match_round_id.eq(match_round_id))
+        .filter(applicantrank::columns::topic_id.eq_any(topic_ids))
+        .select((
+            applicantrank::columns::topic_id,
+            applicantrank::columns::student_user_name,
+            applicantrank::columns::appl_rank,
+        ))
+        .load::<(i32, String, i32)>(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load applicant ranks")))?
+        .into_iter()
+        .map(|(topic_id, student_user_name, appl_rank)| ((topic_id, student_user_name), appl_rank))
+        .collect();
+
+    let mut applicants_by_topic: HashMap<i32, Vec<ApplicantDetails>> = HashMap::new();
+    for (topic_id, student_user_name, student_name, pref_rank) in preferences {
+        let appl_rank = ranks.get(&(topic_id, student_user_name.clone())).copied();
+        applicants_by_topic
+            .entry(topic_id)
+            .or_default()
+            .push(ApplicantDetails {
+                student_user_name,
+                student_name,
+                pref_rank,
+                appl_rank,
+            });
+    }
+
+    Ok(topics
+        .into_iter()
+        .map(|(topic_id, topic_name, topic_max_students)| {
+            let mut applicants = applicants_by_topic.remove(&topic_id).unwrap_or_default();
+            applicants.sort_by(|a, b| {
+                (
+                    a.appl_rank.unwrap_or(i32::MAX),
+                    a.pref_rank,
+                    &a.student_user_name,
+                )
+                    .cmp(&(
+                        b.appl_rank.unwrap_or(i32::MAX),
+                        b.pref_rank,
+                        &b.student_user_name,
+                    ))
+            });
+            TopicApplicants {
+                topic_id,
+                topic_name,
+                topic_max_students,
+                applicants,
+            }
+        })
+        .collect())
+}
+
+/// Runs the matching of a round on current data, without writing anything.
+///
+/// Students who obtained a topic since submitting preferences do not take part, and only places
+/// still open on approved topics are handed out.
+fn compute_matching(
+    conn: &mut PgConnection,
+    match_round_id: i32,
+) -> Result<Vec<MatchingEntry>, ApiError> {
+    use backend_database::schema::*;
+
+    let rows = topicpreference::table
+        .inner_join(student::table)
+        .filter(topicpreference::columns::match_round_id.eq(match_round_id))
+        .filter(student::columns::topic_id.is_null())
+        .order((
+            topicpreference::columns::student_user_name.asc(),
+            topicpreference::columns::pref_rank.asc(),
+        ))
+        .select((
+            topicpreference::columns::student_user_name,
+            student::columns::student_name,
+            topicpreference::columns::topic_id,
+            topicpreference::columns::pref_rank,
+        ))
+        .load::<(String, String, i32, i16)>(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load preferences")))?;
+
+    let mut preferences: BTreeMap<String, Vec<i32>> = BTreeMap::new();
+    let mut student_names: HashMap<String, String> = HashMap::new();
+    let mut pref_ranks: HashMap<(String, i32), i16> = HashMap::new();
+    for (student_user_name, student_name, topic_id, pref_rank) in rows {
+        preferences
+            .entry(student_user_name.clone())
+            .or_default()
+            .push(topic_id);
+        pref_ranks.insert((student_user_name.clone(), topic_id), pref_rank);
+        student_names.insert(student_user_name, student_name);
+    }
+
+    let topic_ids: Vec<i32> = pref_ranks
+        .keys()
+        .map(|(_, topic_id)| *topic_id)
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    let topics = topic::table
+        .filter(topic::columns::topic_id.eq_any(&topic_ids))
+        .filter(topic::columns::topic_review_status.eq(TopicReviewStatus::Approved as i16))
+        .select((
+            topic::columns::topic_id,
+            topic::columns::topic_name,
+            topic::columns::topic_max_students,
+        ))
+        .load::<(i32, String, i32)>(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load topics")))?;
+    let student_counts = count_students_by_topic(conn, &topic_ids)?;
+
+    let mut capacities: HashMap<i32, usize> = HashMap::new();
+    let mut topic_names: HashMap<i32, String> = HashMap::new();
+    for (topic_id, topic_name, topic_max_students) in topics {
+        let taken = student_counts.get(&topic_id).copied().unwrap_or(0);
+        let open = (topic_max_students as i64 - taken).max(0);
+        capacities.insert(topic_id, open as usize);
+        topic_names.insert(topic_id, topic_name);
+    }
+
+    let mut rankings: HashMap<i32, Vec<String>> = HashMap::new();
+    let ranked = applicantrank::table
+        .filter(applicantrank::columns::match_round_id.eq(match_round_id))
+        .order((
+            applicantrank::columns::topic_id.asc(),
+            applicantrank::columns::appl_rank.asc(),
+        ))
+        .select((
+            applicantrank::columns::topic_id,
+            applicantrank::columns::student_user_name,
+        ))
+        .load::<(i32, String)>(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load applicant ranks")))?;
+    for (topic_id, student_user_name) in ranked {
+        rankings
+            .entry(topic_id)
+            .or_default()
+            .push(student_user_name);
+    }
+
+    let matched = deferred_acceptance(&preferences, &capacities, &rankings);
+
+    Ok(preferences
+        .into_keys()
+        .map(|student_user_name| {
+            let topic_id = matched.get(&student_user_name).copied();
+            MatchingEntry {
+                student_name: student_names.remove(&student_user_name).unwrap_or_default(),
+                topic_name: topic_id.and_then(|topic_id| topic_names.get(&topic_id).cloned()),
+                pref_rank: topic_id.and_then(|topic_id| {
+                    pref_ranks
+                        .get(&(student_user_name.clone(), topic_id))
+                        .copied()
+                }),
+                topic_id,
+                student_user_name,
+            }
+        })
+        .collect())
+}
+
+#[get("/ping")]
+pub async fn ping() -> HttpResponse {
+    HttpResponse::Ok().json(str!("pong"))
+}
+
+#[post("/login")]
+pub async fn login(
+    pool: web::Data<DbPool>,
+    session: Session,
+    req: web::Json<LoginRequest>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if is_session_authed(&session) {
+        return Err(ApiError::BadRequest(str!("Already logged in")));
+    }
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+    let user = sysuser::dsl::sysuser
+        .filter(sysuser::columns::user_name.eq(&req.username))
+        .first::<SysUser>(&mut conn)
+        .map_err(|_| ApiError::Unauthorized)?;
+
+    let password_good = verify_password(
+        &req.password,
+        &user.user_password_hash,
+        &user.user_password_salt,
+    )
+    .map_err(|_| ApiError::InternalServerError(str!("Failed to verify password")))?;
+
+    if password_good {
+        let auth_info = map_schema_role!(
+            &mut conn, &user.user_name, Err(ApiError::InternalServerError(str!("User not in any role"))),
+            sysadmin::dsl::sysadmin => SysAdmin => Ok(AuthInfo::SysAdmin {
+                username: user.user_name,
+                impersonating: None,
+            });
+            student::dsl::student => Student => Ok(AuthInfo::User {
+                username: user.user_name,
+                role: AuthInfoUserRole::Student,
+            });
+            teacher::dsl::teacher => Teacher => Ok(AuthInfo::User {
+                username: user.user_name,
+                role: AuthInfoUserRole::Teacher,
+            });
+            defenseboard::dsl::defenseboard => DefenseBoard => Ok(AuthInfo::User {
+                username: user.user_name,
+                role: AuthInfoUserRole::DefenseBoard,
+            });
+            office::dsl::office => Office => Ok(AuthInfo::User {
+                username: user.user_name,
+                role: AuthInfoUserRole::Office,
+            });
+        )?;
+
+        session
+            .insert(AUTH_INFO_SESSION_KEY, &auth_info)
+            .map_err(|_| {
+                ApiError::InternalServerError(str!("Failed to store session information"))
+            })?;
+
+        Ok(HttpResponse::Ok().finish())
+    } else {
+        Err(ApiError::Unauthorized)
+    }
+}
+
+#[post("/logout")]
+pub async fn logout(session: Session) -> Result<HttpResponse, ApiError> {
+    if session.contains_key(AUTH_INFO_SESSION_KEY) {
+        session.purge();
+        Ok(HttpResponse::Ok().finish())
+    } else {
+        Err(ApiError::Unauthorized)
+    }
+}
+
+#[get("/user")]
+pub async fn get_current_user(
+    pool: web::Data<DbPool>,
+    session: Session,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let username = get_session_username(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    let sys_user = sysuser::dsl::sysuser
+        .find(&username)
+        .first::<SysUser>(&mut conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user information")))?;
+
+    // Get user role and name based on role
+    let (role, name, title) = map_schema_role!(
+        &mut conn, &username, Err(ApiError::InternalServerError(str!("User not in any role"))),
+        sysadmin::dsl::sysadmin => SysAdmin => {
+            Ok((UserRole::Admin, None, None))
+        };
+        student::dsl::student => Student => {
+            let student = student::dsl::student
+                .find(&username)
+                .first::<Student>(&mut conn)
+                .map_err(|_| ApiError::InternalServerError(str!("Failed to get student information")))?;
+            Ok((UserRole::Student, Some(student.student_name), None))
+        };
+        teacher::dsl::teacher => Teacher => {
+            let teacher = teacher::dsl::teacher
+                .find(&username)
+                .first::<Teacher>(&mut conn)
+                .map_err(|_| ApiError::InternalServerError(str!("Failed to get teacher information")))?;
+            let title = TeacherTitle::try_from(teacher.teacher_title)
+                .map_err(|_| ApiError::InternalServerError(str!("Invalid teacher title")))?;
+            Ok((UserRole::Teacher, Some(teacher.teacher_name), Some(title)))
+        };
+        defenseboard::dsl::defenseboard => DefenseBoard => {
+            Ok((UserRole::DefenseBoard, None, None))
+        };
+        office::dsl::office => Office => {
+            Ok((UserRole::Office, None, None))
+        };
+    )?;
+
+    Ok(HttpResponse::Ok().json(UserGetResponse {
+        username: sys_user.user_name.clone(),
+        role,
+        name,
+        avatar: sys_user.user_avatar,
+        title,
+    }))
+}
+
+#[patch("/user")]
+pub async fn update_current_user(
+    pool: web::Data<DbPool>,
+    session: Session,
+    req: web::Json<UserPatchRequest>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let username = get_session_username(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    conn.build_transaction().read_write().run(|conn| {
+        if let Some(ref password) = req.password {
+            let (hash, salt) = hash_password(password)
+                .map_err(|_| ApiError::InternalServerError(str!("Failed to hash password")))?;
+
+            diesel::update(sysuser::dsl::sysuser.find(&username))
+                .set(SysUserPasswordChangeset {
+                    user_password_hash: Some(hash),
+                    user_password_salt: Some(salt),
+                })
+                .execute(conn)
+                .map_err(|_| {
+                    ApiError::InternalServerError(str!("Failed to update user information"))
+                })?;
+        }
+
+        if let Some(ref avatar) = req.avatar {
+            diesel::update(sysuser::dsl::sysuser.find(&username))
+                .set(sysuser::columns::user_avatar.eq(avatar))
+                .execute(conn)
+                .map_err(|_| ApiError::InternalServerError(str!("Failed to update avatar")))?;
+        }
+
+        if let Some(ref name) = req.name {
+            // Is student?
+            if diesel::select(diesel::dsl::exists(student::dsl::student.find(&username)))
+                .get_result::<bool>(conn)
+                .map_err(|_| ApiError::InternalServerError(str!("Failed to check user role")))?
+            {
+                diesel::update(student::dsl::student.find(&username))
+                    .set(student::columns::student_name.eq(name))
+                    .execute(conn)
+                    .map_err(|_| {
+                        ApiError::InternalServerError(str!("Failed to update student name"))
+                    })?;
+            }
+            // Is teacher?
+            else if diesel::select(diesel::dsl::exists(teacher::dsl::teacher.find(&username)))
+                .get_result::<bool>(conn)
+                .map_err(|_| ApiError::InternalServerError(str!("Failed to check user role")))?
+            {
+                diesel::update(teacher::dsl::teacher.find(&username))
+                    .set(teacher::columns::teacher_name.eq(name))
+                    .execute(conn)
+                    .map_err(|_| {
+                        ApiError::InternalServerError(str!("Failed to update teacher name"))
+                    })?;
+            }
+        }
+
+        Ok::<(), ApiError>(())
+    })?;
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+#[post("/user")]
+pub async fn create_user(
+    pool: web::Data<DbPool>,
+    session: Session,
+    req: web::Json<UserPostRequest>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+    if !is_session_admin(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Can't deserialize auth info")))?
+    {
+        return Err(ApiError::Forbidden);
+    }
+
+    let (hash, salt) = hash_password(&req.password)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to hash password")))?;
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+    let (new_sys_user, name, title) = conn.build_transaction().read_write().run(|conn| {
+        let name = match req.role {
+            UserRole::Student | UserRole::Teacher => req.name.clone(),
+            _ => None,
+        };
+        let title = match req.role {
+            UserRole::Teacher => Some(req.title.unwrap_or(TeacherTitle::Other)),
+            _ => None,
+        };
+
+        let new_sys_user = diesel::insert_into(sysuser::dsl::sysuser)
+            .values(NewSysUser {
+                user_name: &req.username,
+                user_password_hash: &hash,
+                user_password_salt: &salt,
+                user_avatar: req.avatar.as_deref(),
+            })
+            .get_result::<SysUser>(conn)
+            .map_err(|_| ApiError::Conflict(str!("Failed to create new user")))?;
+        match req.role {
+            UserRole::Admin => diesel::insert_into(sysadmin::dsl::sysadmin)
+                .values(NewSysAdmin {
+                    user_name: &new_sys_user.user_name,
+                })
+                .execute(conn),
+            UserRole::Student => diesel::insert_into(student::dsl::student)
+                .values(NewStudent {
+                    user_name: &new_sys_user.user_name,
+                    topic_id: None,
+                    major_id: req.major_id.ok_or(ApiError::BadRequest(str!(
+                        "Major ID is required for student role"
+                    )))?,
+                    student_name: name.as_deref().ok_or(ApiError::BadRequest(str!(
+                        "Name is required for student role"
+                    )))?,
+                    assn_time: Utc::now(),
+                })
+                .execute(conn),
+            UserRole::Teacher => diesel::insert_into(teacher::dsl::teacher)
+                .values(NewTeacher {
+                    user_name: &new_sys_user.user_name,
+                    teacher_name: name.as_deref().ok_or(ApiError::BadRequest(str!(
+                        "Name is required for teacher role"
+                    )))?,
+                    teacher_title: title.unwrap_or(TeacherTitle::Other) as i16,
+                })
+                .execute(conn),
+            UserRole::DefenseBoard => diesel::insert_into(defenseboard::dsl::defenseboard)
+                .values(NewDefenseBoard {
+                    user_name: &new_sys_user.user_name,
+                })
+                .execute(conn),
+            UserRole::Office => diesel::insert_into(office::dsl::office)
+                .values(NewOffice {
+                    user_name: &new_sys_user.user_name,
+                })
+                .execute(conn),
+        }
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to assign role")))?;
+        Ok::<_, ApiError>((new_sys_user, name, title))
+    })?;
+
+    Ok(HttpResponse::Ok().json(UserGetResponse {
+        username: new_sys_user.user_name,
+        role: req.role,
+        name,
+        avatar: req.avatar.clone(),
+        title,
+    }))
+}
+
+#[get("/topics")]
+pub async fn get_topics(
+    pool: web::Data<DbPool>,
+    session: Session,
+    query: web::Query<TopicsQuery>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let username = get_session_username(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
+    let user_role = get_session_user_role(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+
+    let page = query.page.unwrap_or(1);
+    let page_size = query.page_size.unwrap_or(20);
+    // FIXME: Negative checks
+    let offset = (page - 1) * page_size;
+    let tags = parse_tag_filter(query.tags.as_deref())?;
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    // Student: Get topics approved for their major
+    // Teacher: Get their own topics
+    // Office and DefenseBoard: Get all topics
+    let scope = get_topic_scope(&mut conn, &user_role, &username)?;
+
+    let total = visible_topics_query(&scope, &tags)
+        .count()
+        .get_result::<i64>(&mut conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to count topics")))?;
+    let topics_with_teacher = visible_topics_query(&scope, &tags)
+        .offset(offset)
+        .limit(page_size)
+        .order_by(topic::columns::topic_id.desc())
+        .load::<(Topic, Teacher)>(&mut conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load topics")))?;
+
+    let topic_ids = topics_with_teacher
+        .iter()
+        .map(|(topic, _)| topic.topic_id)
+        .collect::<Vec<_>>();
+    let mut tags_by_topic = load_topic_tags(&mut conn, &topic_ids)?;
+    let student_counts = count_students_by_topic(&mut conn, &topic_ids)?;
+
+    let mut topic_briefs = Vec::new();
+    for (topic, teacher) in topics_with_teacher {
+        let current_student_count = student_counts.get(&topic.topic_id).copied().unwrap_or(0);
+
+        topic_briefs.push(TopicBrief {
+            topic_id: topic.topic_id,
+            teacher_name: teacher.teacher_name,
+            topic_name: topic.topic_name,
+            topic_max_students: topic.topic_max_students,
+            topic_type: TopicType::try_from(topic.topic_type)
+                .map_err(|_| ApiError::InternalServerError(str!("Invalid topic type")))?,
+            topic_review_status: TopicReviewStatus::try_from(topic.topic_review_status)
+                .map_err(|_| ApiError::InternalServerError(str!("Invalid topic review status")))?,
+            current_student_count: current_student_count as i32,
+            tags: tags_by_topic.remove(&topic.topic_id).unwrap_or_default(),
+            topic_version: topic.topic_version,
+        });
+    }
+
+    Ok(HttpResponse::Ok().json(TopicsGetResponse {
+        total,
+        page,
+        page_size,
+        topics: topic_briefs,
+    }))
+}
+
+#[post("/topics")]
+pub async fn create_topic(
+    pool: web::Data<DbPool>,
+    session: Session,
+    req: web::Json<TopicsPostRequest>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let user_role = get_session_user_role(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+    if !matches!(user_role, AuthInfoUserRole::Teacher) {
+        // Only teachers can create topics
+        return Err(ApiError::Forbidden);
+    }
+
+    let username = get_session_username(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    let has_such_teacher: i64 = teacher::dsl::teacher
+        .find(&username)
+        .count()
+        .get_result(&mut conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to check teacher existence")))?;
+    if has_such_teacher == 0 {
+        return Err(ApiError::InternalServerError(str!(
+            "Teacher record not found for user"
+        )));
+    }
+
+    if req.topic_max_students < 1 {
+        return Err(ApiError::BadRequest(str!(
+            "Topic must accept at least one student"
+        )));
+    }
+
+    let tags = normalize_tags(req.tags.as_deref().unwrap_or_default())?;
+
+    let new_topic = conn.build_transaction().read_write().run(|conn| {
+        // Parallel creations wait on the teacher before counting the places
+        teacher::table
+            .find(&username)
+            .select(teacher::columns::user_name)
+            .for_update()
+            .first::<String>(conn)
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to lock teacher")))?;
+        check_teacher_quota(conn, &username, req.topic_max_students as i64, None)?;
+
+        let new_topic = NewTopic {
+            major_id: req.major_id,
+            teacher_user_name: &username,
+            topic_name: &req.topic_name,
+            topic_description: &req.topic_description,
+            topic_max_students: req.topic_max_students,
+            topic_type: req.topic_type as i16,
+            topic_review_status: TopicReviewStatus::Pending as i16,
+        };
+
+        let inserted_topic = diesel::insert_into(topic::dsl::topic)
+            .values(&new_topic)
+            .get_result::<Topic>(conn)
+            .map_err(|e| {
+                if e.to_string().contains("duplicate key") {
+                    ApiError::Conflict(str!("Topic with similar name already exists"))
+                } else {
+                    ApiError::InternalServerError(str!("Failed to create topic"))
+                }
+            })?;
+
+        replace_topic_tags(conn, inserted_topic.topic_id, &tags)?;
+
+        Ok::<_, ApiError>(inserted_topic)
+    })?;
+
+    Ok(HttpResponse::Created().json(TopicCreateResponse {
+        topic_id: new_topic.topic_id,
+    }))
+}
+
+#[get("/topics/search")]
+pub async fn search_topics(
+    pool: web::Data<DbPool>,
+    session: Session,
+    query: web::Query<SearchQuery>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let username = get_session_username(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
+    let user_role = get_session_user_role(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+
+    let page = query.page.unwrap_or(1);
+    let page_size = query.page_size.unwrap_or(20);
+    // FIXME: Negative checks
+    let offset = (page - 1) * page_size;
+    let search_pattern = format!("%{}%", query.keyword.as_deref().unwrap_or(""));
+    let tags = parse_tag_filter(query.tags.as_deref())?;
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    // Student: Get topics approved for their major with keyword search
+    // Teacher: Get their own topics with keyword search
+    // Office and DefenseBoard: Get all topics with keyword search
+    let scope = get_topic_scope(&mut conn, &user_role, &username)?;
+
+    let total = visible_topics_query(&scope, &tags)
+        .filter(topic::columns::topic_name.like(&search_pattern))
+        .count()
+        .get_result::<i64>(&mut conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to count topics")))?;
+    let topics_with_teacher = visible_topics_query(&scope, &tags)
+        .filter(topic::columns::topic_name.like(&search_pattern))
+        .offset(offset)
+        .limit(page_size)
+        .order_by(topic::columns::topic_id.desc())
+        .load::<(Topic, Teacher)>(&mut conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load topics")))?;
+
+    let topic_ids = topics_with_teacher
+        .iter()
+        .map(|(topic, _)| topic.topic_id)
+        .collect::<Vec<_>>();
+    let mut tags_by_topic = load_topic_tags(&mut conn, &topic_ids)?;
+    let student_counts = count_students_by_topic(&mut conn, &topic_ids)?;
+
+    let mut topic_briefs = Vec::new();
+    for (topic, teacher) in topics_with_teacher {
+        let current_student_count = student_counts.get(&topic.topic_id).copied().unwrap_or(0);
+        topic_briefs.push(TopicBrief {
+            topic_id: topic.topic_id,
+            teacher_name: teacher.teacher_name,
+            topic_name: topic.topic_name,
+            topic_max_students: topic.topic_max_students,
+            topic_type: TopicType::try_from(topic.topic_type)
+                .map_err(|_| ApiError::InternalServerError(str!("Invalid topic type")))?,
+            topic_review_status: TopicReviewStatus::try_from(topic.topic_review_status)
+                .map_err(|_| ApiError::InternalServerError(str!("Invalid topic review status")))?,
+            current_student_count: current_student_count as i32,
+            tags: tags_by_topic.remove(&topic.topic_id).unwrap_or_default(),
+            topic_version: topic.topic_version,
         });
     }
 
-    Ok(HttpResponse::Ok().json(TopicsGetResponse {
-        total,
-        page,
-        page_size,
-        topics: topic_briefs,
-    }))
+    Ok(HttpResponse::Ok().json(TopicsGetResponse {
+        total,
+        page,
+        page_size,
+        topics: topic_briefs,
+    }))
+}
+
+#[get("/topics/tags")]
+pub async fn get_topic_tags(
+    pool: web::Data<DbPool>,
+    session: Session,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let username = get_session_username(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
+    let user_role = get_session_user_role(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    // Only count topics the user could also see through GET /topics.
+    let scope = get_topic_scope(&mut conn, &user_role, &username)?;
+    let visible_topic_ids = visible_topics_query(&scope, &[])
+        .select(topic::columns::topic_id)
+        .load::<i32>(&mut conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load topics")))?;
+
+    let tags = topictag::table
+        .inner_join(tag::table)
+        .filter(topictag::columns::topic_id.eq_any(&visible_topic_ids))
+        .group_by(tag::columns::tag_name)
+        .select((tag::columns::tag_name, diesel::dsl::count_star()))
+        .order((
+            diesel::dsl::count_star().desc(),
+            tag::columns::tag_name.asc(),
+        ))
+        .load::<(String, i64)>(&mut conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to count tags")))?
+        .into_iter()
+        .map(|(tag_name, topic_count)| TagCount {
+            tag_name,
+            topic_count,
+        })
+        .collect::<Vec<_>>();
+
+    Ok(HttpResponse::Ok().json(TagsGetResponse { tags }))
+}
+
+#[get("/topics/{topic_id}")]
+pub async fn get_topic_detail(
+    pool: web::Data<DbPool>,
+    session: Session,
+    topic_id: web::Path<i32>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let username = get_session_username(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
+    let user_role = get_session_user_role(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    // Why boxed query here? Since we don't need it to be Copy, unlike the previous (count, records) pattern.
+    let mut query_builder = topic::table
+        .inner_join(teacher::table)
+        .inner_join(major::table)
+        .filter(topic::columns::topic_id.eq(*topic_id))
+        .into_boxed();
+
+    match user_role {
+        AuthInfoUserRole::Student => {
+            // Student: Get topics approved for their major
+            let student_info = student::dsl::student
+                .find(&username)
+                .first::<Student>(&mut conn)
+                .map_err(|_| {
+                    ApiError::InternalServerError(str!("Failed to get student information"))
+                })?;
+
+            query_builder = query_builder
+                .filter(topic::columns::major_id.eq(student_info.major_id))
+                .filter(topic::columns::topic_review_status.eq(TopicReviewStatus::Approved as i16));
+        }
+        AuthInfoUserRole::Teacher => {
+            // Teacher: Get topics they supervise or co-supervise
+            query_builder = query_builder.filter(
+                topic::columns::teacher_user_name
+                    .eq(&username)
+                    .or(topic::columns::topic_id.eq_any(cosupervised_topic_ids(&username))),
+            );
+        }
+        AuthInfoUserRole::Office | AuthInfoUserRole::DefenseBoard => {
+            // Office and Defense Board: Get all topics without additional filtering
+        }
+    }
+
+    let (topic, teacher, major): (Topic, Teacher, Major) =
+        query_builder.first(&mut conn).map_err(|e| {
+            if let diesel::result::Error::NotFound = e {
+                ApiError::NotFound
+            } else {
+                ApiError::InternalServerError(str!("Failed to load topic details"))
+            }
+        })?;
+
+    let current_student_count = count_students_by_topic(&mut conn, &[topic.topic_id])?
+        .remove(&topic.topic_id)
+        .unwrap_or(0);
+    let tags = load_topic_tags(&mut conn, &[topic.topic_id])?
+        .remove(&topic.topic_id)
+        .unwrap_or_default();
+    let cosupervisors = load_topic_cosupervisors(&mut conn, topic.topic_id)?;
+
+    // Build response
+    let topic_details = TopicDetails {
+        topic_id: topic.topic_id,
+        major_id: topic.major_id,
+        major_name: major.major_name,
+        teacher_user_name: topic.teacher_user_name,
+        teacher_name: teacher.teacher_name,
+        topic_name: topic.topic_name,
+        topic_description: topic.topic_description,
+        topic_max_students: topic.topic_max_students,
+        topic_type: TopicType::try_from(topic.topic_type)
+            .map_err(|_| ApiError::InternalServerError(str!("Invalid topic type")))?,
+        topic_review_status: TopicReviewStatus::try_from(topic.topic_review_status)
+            .map_err(|_| ApiError::InternalServerError(str!("Invalid topic review status")))?,
+        current_student_count: current_student_count as i32,
+        tags,
+        cosupervisors,
+        topic_version: topic.topic_version,
+        topic_review_comment: topic.topic_review_comment,
+    };
+
+    Ok(HttpResponse::Ok().json(topic_details))
+}
+
+#[patch("/topics/{topic_id}")]
+pub async fn update_topic(
+    pool: web::Data<DbPool>,
+    session: Session,
+    topic_id: web::Path<i32>,
+    req: web::Json<TopicPatchRequest>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let username = get_session_username(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
+    let user_role = get_session_user_role(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    let result = conn.build_transaction().read_write().run(|conn| {
+        // Lock the topic so that placements cannot fill it while its capacity is checked
+        let topic = topic::dsl::topic
+            .find(*topic_id)
+            .for_update()
+            .first::<Topic>(conn)
+            .map_err(|e| {
+                if let diesel::result::Error::NotFound = e {
+                    ApiError::NotFound
+                } else {
+                    ApiError::InternalServerError(str!("Failed to load topic"))
+                }
+            })?;
+
+        let topic = match user_role {
+            AuthInfoUserRole::Teacher => {
+                // Is a teacher!
+                if topic.teacher_user_name != username {
+                    // Teacher can only update topics they created
+                    return Err(ApiError::Forbidden);
+                }
+                // The teacher is locked after the topic, as placements do
+                teacher::table
+                    .find(&username)
+                    .select(teacher::columns::user_name)
+                    .for_update()
+                    .first::<String>(conn)
+                    .map_err(|_| ApiError::InternalServerError(str!("Failed to lock teacher")))?;
+
+                if req.topic_review_status.is_some() || req.topic_review_comment.is_some() {
+                    // Teacher can not update review status
+                    return Err(ApiError::BadRequest(str!(
+                        "Teachers cannot update review status"
+                    )));
+                }
+
+                // Tags only help students browse topics and taking more students does not change
+                // what the topic is about, so changing either alone neither requires the topic to
+                // be unclaimed nor sends it back to review. Taking fewer students does both.
+                let content_unchanged = (req.tags.is_some() || req.topic_max_students.is_some())
+                    && req
+                        .topic_max_students
+                        .is_none_or(|max| max >= topic.topic_max_students)
+                    && req.topic_name.is_none()
+                    && req.topic_description.is_none()
+                    && req.topic_type.is_none();
+
+                if !content_unchanged {
+                    // Check if any student has requested or is accepted for this topic
+                    let has_requests = diesel::select(diesel::dsl::exists(
+                        assignmentrequest::table
+                            .filter(assignmentrequest::columns::topic_id.eq(topic.topic_id))
+                            .filter(
+                                assignmentrequest::columns::assn_req_status
+                                    .eq(AssignmentStatus::Pending as i16),
+                            ),
+                    ))
+                    .get_result(conn)
+                    .map_err(|e| {
+                        ApiError::InternalServerError(format!(
+                            "Failed to check for assignment requests: {}",
+                            e
+                        ))
+                    })?;
+
+                    let has_accepted = diesel::select(diesel::dsl::exists(
+                        student::table.filter(student::columns::topic_id.eq(topic.topic_id)),
+                    ))
+                    .get_result(conn)
+                    .map_err(|e| {
+                        ApiError::InternalServerError(format!(
+                            "Failed to check for accepted students: {}",
+                            e
+                        ))
+                    })?;
+
+                    if has_requests || has_accepted {
+                        return Err(ApiError::Conflict(str!(
+                            "Topic cannot be updated once assignment requests or acceptances exist"
+                        )));
+                    }
+                }
+
+                if let Some(topic_max_students) = req.topic_max_students {
+                    if topic_max_students < 1 {
+                        return Err(ApiError::BadRequest(str!(
+                            "Topic must accept at least one student"
+                        )));
+                    }
+
+                    check_teacher_quota(
+                        conn,
+                        &username,
+                        topic_max_students as i64,
+                        Some(topic.topic_id),
+                    )?;
+
+                    // Students placed over capacity by the office may still outnumber the places
+                    if content_unchanged {
+                        let assigned_count: i64 = student::table
+                            .filter(student::columns::topic_id.eq(topic.topic_id))
+                            .count()
+                            .get_result(conn)
+                            .map_err(|_| {
+                                ApiError::InternalServerError(str!("Failed to count students"))
+                            })?;
+                        if (topic_max_students as i64) < assigned_count {
+                            return Err(ApiError::Conflict(format!(
+                                "Topic already has {} assigned students",
+                                assigned_count
+                            )));
+                        }
+                    }
+                }
+
+                if let Some(ref tags) = req.tags {
+                    let tags = normalize_tags(tags)?;
+                    replace_topic_tags(conn, topic.topic_id, &tags)?;
+                }
+
+                if content_unchanged {
+                    // Still bump the version so that pending reviews see the change
+                    let topic = diesel::update(&topic)
+                        .set((
+                            topic::columns::topic_max_students
+                                .eq(req.topic_max_students.unwrap_or(topic.topic_max_students)),
+                            topic::columns::topic_version.eq(topic::columns::topic_version + 1),
+                        ))
+                        .get_result::<Topic>(conn)
+                        .map_err(|e| {
+                            ApiError::InternalServerError(format!("Failed to update topic: {}", e))
+                        })?;
+                    // A larger capacity frees slots for waitlisted students
+                    promote_waitlist(conn, topic.topic_id)?;
+                    return build_topic_details(conn, topic);
+                }
+
+                let changeset = TopicChangeset {
+                    topic_name: req.topic_name.clone(),
+                    topic_description: req.topic_description.clone(),
+                    topic_max_students: req.topic_max_students,
+                    topic_type: req.topic_type.map(|t| t as i16),
+                    // topic_review_status is not settable by teacher, but will be reset to Pending on any update
+                    topic_review_status: Some(TopicReviewStatus::Pending as i16),
+                };
+
+                // Bump the version so that reviews based on the old content can be detected as stale
+                diesel::update(&topic)
+                    .set((
+                        changeset,
+                        topic::columns::topic_version.eq(topic::columns::topic_version + 1),
+                    ))
+                    .get_result::<Topic>(conn)
+                    .map_err(|e| {
+                        ApiError::InternalServerError(format!("Failed to update topic: {}", e))
+                    })?
+            }
+            AuthInfoUserRole::Office => {
+                // Is an office user!
+                // Office can only update review status
+                if req.topic_name.is_some()
+                    || req.topic_description.is_some()
+                    || req.topic_max_students.is_some()
+                    || req.topic_type.is_some()
+                    || req.tags.is_some()
+                {
+                    return Err(ApiError::BadRequest(str!(
+                        "Office can only update review status"
+                    )));
+                }
+
+                if let Some(topic_review_status) = req.topic_review_status {
+                    if topic_review_status == TopicReviewStatus::Pending {
+                        return Err(ApiError::BadRequest(str!(
+                            "Cannot explicitly set review status to Pending"
+                        )));
+                    }
+
+                    let topic = diesel::update(&topic)
+                        .set((
+                            topic::columns::topic_review_status.eq(topic_review_status as i16),
+                            topic::columns::topic_review_comment
+                                .eq(req.topic_review_comment.clone()),
+                        ))
+                        .get_result::<Topic>(conn)
+                        .map_err(|e| {
+                            ApiError::InternalServerError(format!(
+                                "Failed to update topic review status: {}",
+                                e
+                            ))
+                        })?;
+                    if topic_review_status == TopicReviewStatus::Rejected {
+                        release_proposing_students(conn, topic.topic_id)?;
+                    }
+                    topic
+                } else {
+                    return Err(ApiError::BadRequest(str!(
+                        "Review status must be provided by Office"
+                    )));
+                }
+            }
+            AuthInfoUserRole::DefenseBoard | AuthInfoUserRole::Student => {
+                // These roles are not allowed to update topics
+                return Err(ApiError::Forbidden);
+            }
+        };
+
+        build_topic_details(conn, topic)
+    })?;
+
+    Ok(HttpResponse::Ok().json(result))
+}
+
+#[post("/topics/review")]
+pub async fn review_topics(
+    pool: web::Data<DbPool>,
+    session: Session,
+    req: web::Json<TopicsReviewPostRequest>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let user_role = get_session_user_role(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+    if !matches!(user_role, AuthInfoUserRole::Office) {
+        return Err(ApiError::Forbidden);
+    }
+
+    if req.reviews.is_empty() {
+        return Err(ApiError::BadRequest(str!(
+            "At least one review must be provided"
+        )));
+    }
+    if req.reviews.len() > MAX_TOPIC_REVIEW_BATCH {
+        return Err(ApiError::BadRequest(format!(
+            "At most {} topics can be reviewed at once",
+            MAX_TOPIC_REVIEW_BATCH
+        )));
+    }
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    let results = conn.build_transaction().read_write().run(|conn| {
+        let topic_ids: Vec<i32> = req.reviews.iter().map(|review| review.topic_id).collect();
+        // Lock the topics so that teachers cannot edit them while the batch is being applied
+        let topics: HashMap<i32, Topic> = topic::table
+            .filter(topic::columns::topic_id.eq_any(&topic_ids))
+            .order(topic::columns::topic_id.asc())
+            .for_update()
+            .load::<Topic>(conn)
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to load topics")))?
+            .into_iter()
+            .map(|topic| (topic.topic_id, topic))
+            .collect();
+
+        let mut reviewed = HashSet::new();
+        let mut results = Vec::with_capacity(req.reviews.len());
+        for review in &req.reviews {
+            let Some(topic) = topics.get(&review.topic_id) else {
+                results.push(TopicReviewResult {
+                    topic_id: review.topic_id,
+                    outcome: TopicReviewOutcome::NotFound,
+                    topic_version: None,
+                });
+                continue;
+            };
+
+            // Each topic is decided at most once per batch, and never sent back to Pending
+            let outcome = if review.topic_review_status == TopicReviewStatus::Pending
+                || !reviewed.insert(review.topic_id)
+            {
+                TopicReviewOutcome::Invalid
+            } else if review.topic_version != topic.topic_version {
+                TopicReviewOutcome::Stale
+            } else {
+                diesel::update(topic)
+                    .set((
+                        topic::columns::topic_review_status.eq(review.topic_review_status as i16),
+                        topic::columns::topic_review_comment.eq(&review.topic_review_comment),
+                    ))
+                    .execute(conn)
+                    .map_err(|_| {
+                        ApiError::InternalServerError(str!("Failed to update topic review status"))
+                    })?;
+                if review.topic_review_status == TopicReviewStatus::Rejected {
+                    release_proposing_students(conn, topic.topic_id)?;
+                }
+                TopicReviewOutcome::Applied
+            };
+
+            results.push(TopicReviewResult {
+                topic_id: review.topic_id,
+                outcome,
+                topic_version: Some(topic.topic_version),
+            });
+        }
+
+        Ok::<_, ApiError>(results)
+    })?;
+
+    Ok(HttpResponse::Ok().json(TopicsReviewPostResponse { results }))
+}
+
+#[put("/topics/{topic_id}/cosupervisors/{teacher_user_name}")]
+pub async fn put_topic_cosupervisor(
+    pool: web::Data<DbPool>,
+    session: Session,
+    path: web::Path<(i32, String)>,
+    req: web::Json<CoSupervisorPutRequest>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let username = get_session_username(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
+    let user_role = get_session_user_role(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+
+    let (topic_id, cosup_user_name) = path.into_inner();
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    let result = conn.build_transaction().read_write().run(|conn| {
+        let topic = topic::dsl::topic
+            .find(topic_id)
+            .first::<Topic>(conn)
+            .map_err(|_| ApiError::NotFound)?;
+
+        match user_role {
+            // Only the primary supervisor manages co-supervisors of their topic
+            AuthInfoUserRole::Teacher if topic.teacher_user_name == username => {}
+            AuthInfoUserRole::Office => {}
+            _ => return Err(ApiError::Forbidden),
+        }
+
+        if topic.teacher_user_name == cosup_user_name {
+            return Err(ApiError::BadRequest(str!(
+                "Primary supervisor cannot be a co-supervisor"
+            )));
+        }
+
+        let teacher_exists = diesel::select(diesel::dsl::exists(
+            teacher::dsl::teacher.find(&cosup_user_name),
+        ))
+        .get_result::<bool>(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to check teacher")))?;
+        if !teacher_exists {
+            return Err(ApiError::BadRequest(str!(
+                "Co-supervisor must be a teacher"
+            )));
+        }
+
+        let cosupervisor = NewTopicCoSupervisor {
+            topic_id: topic.topic_id,
+            teacher_user_name: &cosup_user_name,
+            cosup_can_approve: req.can_approve,
+        };
+        diesel::insert_into(topiccosupervisor::table)
+            .values(&cosupervisor)
+            .on_conflict((
+                topiccosupervisor::columns::topic_id,
+                topiccosupervisor::columns::teacher_user_name,
+            ))
+            .do_update()
+            .set(&cosupervisor)
+            .execute(conn)
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to save co-supervisor")))?;
+
+        build_topic_details(conn, topic)
+    })?;
+
+    Ok(HttpResponse::Ok().json(result))
+}
+
+#[delete("/topics/{topic_id}/cosupervisors/{teacher_user_name}")]
+pub async fn delete_topic_cosupervisor(
+    pool: web::Data<DbPool>,
+    session: Session,
+    path: web::Path<(i32, String)>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let username = get_session_username(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
+    let user_role = get_session_user_role(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+
+    let (topic_id, cosup_user_name) = path.into_inner();
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    conn.build_transaction().read_write().run(|conn| {
+        let topic = topic::dsl::topic
+            .find(topic_id)
+            .first::<Topic>(conn)
+            .map_err(|_| ApiError::NotFound)?;
+
+        match user_role {
+            // Only the primary supervisor manages co-supervisors of their topic
+            AuthInfoUserRole::Teacher if topic.teacher_user_name == username => {}
+            AuthInfoUserRole::Office => {}
+            _ => return Err(ApiError::Forbidden),
+        }
+
+        let deleted = diesel::delete(
+            topiccosupervisor::dsl::topiccosupervisor.find((topic_id, &cosup_user_name)),
+        )
+        .execute(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to delete co-supervisor")))?;
+        if deleted == 0 {
+            return Err(ApiError::NotFound);
+        }
+
+        Ok::<_, ApiError>(())
+    })?;
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+#[get("/topics/{topic_id}/waitlist")]
+pub async fn get_topic_waitlist(
+    pool: web::Data<DbPool>,
+    session: Session,
+    topic_id: web::Path<i32>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let username = get_session_username(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
+    let user_role = get_session_user_role(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    let topic = topic::dsl::topic
+        .find(*topic_id)
+        .first::<Topic>(&mut conn)
+        .map_err(|_| ApiError::NotFound)?;
+
+    let entries = load_topic_waitlist(&mut conn, topic.topic_id)?;
+    let entries = match user_role {
+        // Students only see their own place in the line
+        AuthInfoUserRole::Student => entries
+            .into_iter()
+            .filter(|entry| entry.student_user_name == username)
+            .collect(),
+        AuthInfoUserRole::Teacher => {
+            let is_supervisor = topic.teacher_user_name == username
+                || diesel::select(diesel::dsl::exists(
+                    topiccosupervisor::dsl::topiccosupervisor.find((topic.topic_id, &username)),
+                ))
+                .get_result::<bool>(&mut conn)
+                .map_err(|_| {
+                    ApiError::InternalServerError(str!("Failed to check co-supervisor"))
+                })?;
+            if !is_supervisor {
+                return Err(ApiError::Forbidden);
+            }
+            entries
+        }
+        AuthInfoUserRole::Office => entries,
+        AuthInfoUserRole::DefenseBoard => return Err(ApiError::Forbidden),
+    };
+
+    Ok(HttpResponse::Ok().json(TopicWaitlistGetResponse {
+        topic_id: topic.topic_id,
+        entries,
+    }))
+}
+
+#[post("/topics/{topic_id}/waitlist")]
+pub async fn join_topic_waitlist(
+    pool: web::Data<DbPool>,
+    session: Session,
+    topic_id: web::Path<i32>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let username = get_session_username(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
+    let user_role = get_session_user_role(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+    if !matches!(user_role, AuthInfoUserRole::Student) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    let entry = conn.build_transaction().read_write().run(|conn| {
+        let student = student::table
+            .find(&username)
+            .first::<Student>(conn)
+            .map_err(|_| ApiError::NotFound)?;
+        if student.topic_id.is_some() {
+            return Err(ApiError::Conflict(str!("Student already has a topic")));
+        }
+
+        let now = Utc::now();
+        let has_open_round = diesel::select(diesel::dsl::exists(
+            selectionround::table
+                .inner_join(selectionroundmajor::table)
+                .filter(selectionroundmajor::columns::major_id.eq(student.major_id))
+                .filter(selectionround::columns::sel_round_open_time.le(now))
+                .filter(selectionround::columns::sel_round_close_time.gt(now)),
+        ))
+        .get_result::<bool>(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load selection round")))?;
+        if !has_open_round {
+            return Err(ApiError::Conflict(str!(
+                "No selection round is open for the student's major"
+            )));
+        }
+
+        // Lock the topic so that promotions see the entry once this transaction commits
+        let topic = topic::table
+            .find(*topic_id)
+            .for_update()
+            .first::<Topic>(conn)
+            .map_err(|_| ApiError::NotFound)?;
+        if topic.topic_review_status != TopicReviewStatus::Approved as i16
+            || topic.major_id != student.major_id
+        {
+            return Err(ApiError::Forbidden);
+        }
+
+        if !must_wait_for_topic(conn, &topic, &username)? {
+            return Err(ApiError::Conflict(str!(
+                "Topic is not full; request it directly"
+            )));
+        }
+
+        let has_pending = diesel::select(diesel::dsl::exists(
+            assignmentrequest::table
+                .filter(assignmentrequest::columns::student_user_name.eq(&username))
+                .filter(assignmentrequest::columns::topic_id.eq(topic.topic_id))
+                .filter(
+                    assignmentrequest::columns::assn_req_status
+                        .eq(AssignmentStatus::Pending as i16),
+                ),
+        ))
+        .get_result::<bool>(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to check existing request")))?;
+        if has_pending {
+            return Err(ApiError::Conflict(str!(
+                "Assignment request already exists"
+            )));
+        }
+
+        diesel::insert_into(topicwaitlist::table)
+            .values(NewTopicWaitlist {
+                topic_id: topic.topic_id,
+                student_user_name: &username,
+                waitlist_time: now,
+            })
+            .execute(conn)
+            .map_err(|_| ApiError::Conflict(str!("Already on the waitlist")))?;
+
+        load_topic_waitlist(conn, topic.topic_id)?
+            .into_iter()
+            .find(|entry| entry.student_user_name == username)
+            .ok_or_else(|| ApiError::InternalServerError(str!("Failed to load waitlist")))
+    })?;
+
+    Ok(HttpResponse::Created().json(entry))
+}
+
+#[delete("/topics/{topic_id}/waitlist")]
+pub async fn leave_topic_waitlist(
+    pool: web::Data<DbPool>,
+    session: Session,
+    topic_id: web::Path<i32>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let username = get_session_username(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
+    let user_role = get_session_user_role(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+    if !matches!(user_role, AuthInfoUserRole::Student) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    let deleted = diesel::delete(
+        topicwaitlist::table
+            .filter(topicwaitlist::columns::topic_id.eq(*topic_id))
+            .filter(topicwaitlist::columns::student_user_name.eq(&username)),
+    )
+    .execute(&mut conn)
+    .map_err(|_| ApiError::InternalServerError(str!("Failed to update waitlist")))?;
+    if deleted == 0 {
+        return Err(ApiError::NotFound);
+    }
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+#[get("/topic_proposals")]
+pub async fn get_topic_proposals(
+    pool: web::Data<DbPool>,
+    session: Session,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let username = get_session_username(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
+    let user_role = get_session_user_role(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    let mut base = topicproposal::table
+        .inner_join(student::table)
+        .inner_join(teacher::table)
+        .into_boxed();
+
+    match user_role {
+        AuthInfoUserRole::Student => {
+            base = base.filter(topicproposal::columns::student_user_name.eq(&username));
+        }
+        AuthInfoUserRole::Teacher => {
+            base = base.filter(topicproposal::columns::teacher_user_name.eq(&username));
+        }
+        AuthInfoUserRole::Office => {}
+        AuthInfoUserRole::DefenseBoard => return Err(ApiError::Forbidden),
+    }
+
+    let rows = base
+        .order(topicproposal::columns::prop_time.desc())
+        .select((
+            topicproposal::all_columns,
+            student::columns::student_name,
+            teacher::columns::teacher_name,
+        ))
+        .load::<(TopicProposal, String, String)>(&mut conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load topic proposals")))?;
+
+    let proposals = rows
+        .into_iter()
+        .map(|(proposal, student_name, teacher_name)| {
+            build_topic_proposal_details(proposal, student_name, teacher_name)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(HttpResponse::Ok().json(TopicProposalsGetResponse { proposals }))
+}
+
+#[post("/topic_proposals")]
+pub async fn create_topic_proposal(
+    pool: web::Data<DbPool>,
+    session: Session,
+    req: web::Json<TopicProposalsPostRequest>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let user_role = get_session_user_role(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+    if !matches!(user_role, AuthInfoUserRole::Student) {
+        // Only students can propose topics
+        return Err(ApiError::Forbidden);
+    }
+
+    let username = get_session_username(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
+
+    if req.topic_name.trim().is_empty() || req.topic_description.trim().is_empty() {
+        return Err(ApiError::BadRequest(str!(
+            "Topic name and description must not be empty"
+        )));
+    }
+    if req.topic_name.chars().count() > MAX_PROPOSAL_NAME_LEN
+        || req.topic_description.chars().count() > MAX_PROPOSAL_DESCRIPTION_LEN
+    {
+        return Err(ApiError::BadRequest(format!(
+            "Topic name must be at most {} and description at most {} characters",
+            MAX_PROPOSAL_NAME_LEN, MAX_PROPOSAL_DESCRIPTION_LEN
+        )));
+    }
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    let result = conn.build_transaction().read_write().run(|conn| {
+        let student = student::dsl::student
+            .find(&username)
+            .first::<Student>(conn)
+            .map_err(|_| {
+                ApiError::InternalServerError(str!("Failed to get student information"))
+            })?;
+        if student.topic_id.is_some() {
+            return Err(ApiError::Conflict(str!("Student already has a topic")));
+        }
+
+        let teacher_exists = diesel::select(diesel::dsl::exists(
+            teacher::dsl::teacher.find(&req.teacher_user_name),
+        ))
+        .get_result::<bool>(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to check teacher")))?;
+        if !teacher_exists {
+            return Err(ApiError::BadRequest(str!(
+                "Proposals must be addressed to a teacher"
+            )));
+        }
+
+        let new_proposal = NewTopicProposal {
+            student_user_name: &username,
+            teacher_user_name: &req.teacher_user_name,
+            prop_topic_name: &req.topic_name,
+            prop_topic_description: &req.topic_description,
+            prop_topic_type: req.topic_type as i16,
+            prop_status: TopicProposalStatus::Pending as i16,
+            prop_time: Utc::now(),
+        };
+        let inserted = diesel::insert_into(topicproposal::table)
+            .values(&new_proposal)
+            .get_result::<TopicProposal>(conn)
+            .map_err(|e| {
+                if e.to_string().contains("duplicate key") {
+                    ApiError::Conflict(str!("Student already has a pending proposal"))
+                } else {
+                    ApiError::InternalServerError(str!("Failed to create topic proposal"))
+                }
+            })?;
+
+        load_topic_proposal_details(conn, inserted.prop_id)
+    })?;
+
+    Ok(HttpResponse::Created().json(result))
+}
+
+#[patch("/topic_proposals/{prop_id}")]
+pub async fn update_topic_proposal(
+    pool: web::Data<DbPool>,
+    session: Session,
+    prop_id: web::Path<i32>,
+    req: web::Json<TopicProposalPatchRequest>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let user_role = get_session_user_role(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+    if !matches!(user_role, AuthInfoUserRole::Teacher) {
+        // Only the addressed teacher can decide on a proposal
+        return Err(ApiError::Forbidden);
+    }
+
+    let username = get_session_username(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
+
+    let decline_reason = req
+        .reason
+        .as_deref()
+        .map(str::trim)
+        .filter(|reason| !reason.is_empty());
+    if !req.accepted && decline_reason.is_none() {
+        return Err(ApiError::BadRequest(str!(
+            "A reason must be given when declining a proposal"
+        )));
+    }
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    let result = conn.build_transaction().read_write().run(|conn| {
+        let proposal = topicproposal::dsl::topicproposal
+            .find(*prop_id)
+            .for_update()
+            .first::<TopicProposal>(conn)
+            .map_err(|_| ApiError::NotFound)?;
+        if proposal.teacher_user_name != username {
+            return Err(ApiError::Forbidden);
+        }
+        if proposal.prop_status != TopicProposalStatus::Pending as i16 {
+            return Err(ApiError::Conflict(str!(
+                "Proposal has already been decided"
+            )));
+        }
+
+        if !req.accepted {
+            diesel::update(&proposal)
+                .set((
+                    topicproposal::columns::prop_status.eq(TopicProposalStatus::Declined as i16),
+                    topicproposal::columns::prop_decided_time.eq(Some(Utc::now())),
+                    topicproposal::columns::prop_decline_reason.eq(decline_reason),
+                ))
+                .execute(conn)
+                .map_err(|_| {
+                    ApiError::InternalServerError(str!("Failed to update topic proposal"))
+                })?;
+
+            return load_topic_proposal_details(conn, proposal.prop_id);
+        }
+
+        let student = student::dsl::student
+            .find(&proposal.student_user_name)
+            .for_no_key_update()
+            .first::<Student>(conn)
+            .map_err(|_| ApiError::NotFound)?;
+        if student.topic_id.is_some() {
+            return Err(ApiError::Conflict(str!("Student already has a topic")));
+        }
+        lock_placement_topics(conn, &[&student.user_name], &[])?;
+
+        // The proposed topic is made for the proposing student alone. The teacher is locked
+        // after the topics, as placements do.
+        teacher::table
+            .find(&username)
+            .select(teacher::columns::user_name)
+            .for_update()
+            .first::<String>(conn)
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to lock teacher")))?;
+        check_teacher_quota(conn, &username, 1, None)?;
+
+        let new_topic = NewTopic {
+            major_id: student.major_id,
+            teacher_user_name: &username,
+            topic_name: &proposal.prop_topic_name,
+            topic_description: &proposal.prop_topic_description,
+            topic_max_students: 1,
+            topic_type: proposal.prop_topic_type,
+            topic_review_status: TopicReviewStatus::Pending as i16,
+        };
+        let inserted_topic = diesel::insert_into(topic::dsl::topic)
+            .values(&new_topic)
+            .get_result::<Topic>(conn)
+            .map_err(|e| {
+                if e.to_string().contains("duplicate key") {
+                    ApiError::Conflict(str!("Topic with similar name already exists"))
+                } else {
+                    ApiError::InternalServerError(str!("Failed to create topic"))
+                }
+            })?;
+
+        let now = Utc::now();
+        diesel::update(&student)
+            .set(StudentAssignmentChangeset {
+                topic_id: inserted_topic.topic_id,
+                assn_time: now,
+            })
+            .execute(conn)
+            .map_err(map_placement_error)?;
+
+        // Requests for other topics are moot once the student has their own
+        close_sibling_requests(
+            conn,
+            &student.user_name,
+            "Student's own topic proposal was accepted",
+        )?;
+
+        diesel::update(&proposal)
+            .set((
+                topicproposal::columns::prop_status.eq(TopicProposalStatus::Accepted as i16),
+                topicproposal::columns::prop_decided_time.eq(Some(now)),
+                topicproposal::columns::topic_id.eq(Some(inserted_topic.topic_id)),
+            ))
+            .execute(conn)
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to update topic proposal")))?;
+
+        load_topic_proposal_details(conn, proposal.prop_id)
+    })?;
+
+    Ok(HttpResponse::Ok().json(result))
+}
+
+#[get("/assignments")]
+pub async fn get_assignments(
+    pool: web::Data<DbPool>,
+    session: Session,
+    query: web::Query<AssignmentsQuery>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::view::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let is_admin = is_session_admin(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Can't deserialize auth info")))?;
+
+    let username = get_session_username(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
+    let user_role = if is_admin {
+        None
+    } else {
+        Some(get_session_user_role(&session).map_err(|_| {
+            ApiError::InternalServerError(str!("Failed to get user role from session"))
+        })?)
+    };
+
+    if !is_admin {
+        match user_role {
+            Some(AuthInfoUserRole::Student | AuthInfoUserRole::Teacher) => {}
+            _ => return Err(ApiError::Forbidden),
+        }
+    }
+
+    let page = query.page.unwrap_or(1);
+    let page_size = query.page_size.unwrap_or(20);
+    if page < 1 || !(1..=100).contains(&page_size) {
+        return Err(ApiError::BadRequest(str!(
+            "Page must be positive and page size between 1 and 100"
+        )));
+    }
+    let offset = (page - 1) * page_size;
+    let descending = is_descending_order(query.order.as_deref())?;
+    let name_pattern = search_pattern(query.student_name.as_deref());
+
+    let visible = || {
+        let mut q = assignmentview::table.into_boxed();
+        match user_role {
+            Some(AuthInfoUserRole::Student) => {
+                q = q.filter(assignmentview::columns::student_user_name.eq(&username));
+            }
+            Some(AuthInfoUserRole::Teacher) => {
+                q = q.filter(assignmentview::columns::teacher_user_name.eq(&username).or(
+                    assignmentview::columns::topic_id.eq_any(cosupervised_topic_ids(&username)),
+                ));
+            }
+            _ => {}
+        }
+        if let Some(status) = query.status {
+            q = q.filter(assignmentview::columns::assn_status.eq(status as i16));
+        }
+        if let Some(topic_id) = query.topic_id {
+            q = q.filter(assignmentview::columns::topic_id.eq(topic_id));
+        }
+        if let Some(major_id) = query.major_id {
+            q = q.filter(assignmentview::columns::major_id.eq(major_id));
+        }
+        if let Some(pattern) = &name_pattern {
+            q = q.filter(assignmentview::columns::student_name.ilike(pattern));
+        }
+        q
+    };
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    let total: i64 = visible()
+        .count()
+        .get_result(&mut conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to count assignments")))?;
+
+    let ordered = if descending {
+        visible().order((
+            assignmentview::columns::assn_request_time.desc(),
+            assignmentview::columns::assn_key.desc(),
+        ))
+    } else {
+        visible().order((
+            assignmentview::columns::assn_request_time.asc(),
+            assignmentview::columns::assn_key.asc(),
+        ))
+    };
+    let assignments = ordered
+        .offset(offset)
+        .limit(page_size)
+        .select(AssignmentViewEntry::as_select())
+        .load::<AssignmentViewEntry>(&mut conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load assignments")))?
+        .into_iter()
+        .map(|entry| {
+            Ok(Assignment {
+                student_user_name: entry.student_user_name,
+                student_name: entry.student_name,
+                student_major: entry.major_name,
+                topic_id: entry.topic_id,
+                topic_name: entry.topic_name,
+                request_time: entry.assn_request_time,
+                status: AssignmentStatus::try_from(entry.assn_status).map_err(|_| {
+                    ApiError::InternalServerError(str!("Invalid assignment status"))
+                })?,
+                decided_time: entry.assn_decided_time,
+                decided_by: entry.assn_decided_by,
+                reason: entry.assn_reason,
+            })
+        })
+        .collect::<Result<Vec<_>, ApiError>>()?;
+
+    Ok(HttpResponse::Ok().json(AssignmentsGetResponse {
+        total,
+        page,
+        page_size,
+        assignments,
+    }))
+}
+
+#[post("/assignments")]
+pub async fn create_assignment(
+    pool: web::Data<DbPool>,
+    session: Session,
+    req: web::Json<AssignmentsPostRequest>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let username = get_session_username(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
+    let user_role = get_session_user_role(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+    if !matches!(user_role, AuthInfoUserRole::Student) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    conn.build_transaction().read_write().run(|conn| {
+        let student = student::dsl::student
+            .find(&username)
+            .for_no_key_update()
+            .first::<Student>(conn)
+            .map_err(|_| ApiError::NotFound)?;
+
+        if student.topic_id.is_some() {
+            return Err(ApiError::Conflict(str!("Student already has a topic")));
+        }
+
+        let now = Utc::now();
+        let round = selectionround::table
+            .inner_join(selectionroundmajor::table)
+            .filter(selectionroundmajor::columns::major_id.eq(student.major_id))
+            .filter(selectionround::columns::sel_round_open_time.le(now))
+            .filter(selectionround::columns::sel_round_close_time.gt(now))
+            .select(SelectionRound::as_select())
+            .first::<SelectionRound>(conn)
+            .optional()
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to load selection round")))?
+            .ok_or_else(|| {
+                ApiError::Conflict(str!("No selection round is open for the student's major"))
+            })?;
+
+        // Locked as for a placement, so that requests and promotions count each other
+        let topic = lock_topic_for_placement(conn, &student, req.topic_id)?;
+
+        if topic.topic_review_status != TopicReviewStatus::Approved as i16
+            || topic.major_id != student.major_id
+        {
+            return Err(ApiError::Forbidden);
+        }
+
+        // Places held by pending requests are taken, and students already waiting go first
+        if must_wait_for_topic(conn, &topic, &username)? {
+            return Err(ApiError::Conflict(str!("Topic is full")));
+        }
+
+        let has_pending = diesel::select(diesel::dsl::exists(
+            assignmentrequest::dsl::assignmentrequest
+                .filter(assignmentrequest::columns::student_user_name.eq(&username))
+                .filter(assignmentrequest::columns::topic_id.eq(req.topic_id))
+                .filter(
+                    assignmentrequest::columns::assn_req_status
+                        .eq(AssignmentStatus::Pending as i16),
+                ),
+        ))
+        .get_result(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to check existing request")))?;
+        if has_pending {
+            return Err(ApiError::Conflict(str!(
+                "Assignment request already exists"
+            )));
+        }
+
+        // Withdrawn and rejected requests still count, so the limit cannot be cycled through
+        let round_request_count: i64 = assignmentrequest::table
+            .filter(assignmentrequest::columns::student_user_name.eq(&username))
+            .filter(assignmentrequest::columns::sel_round_id.eq(round.sel_round_id))
+            .count()
+            .get_result(conn)
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to count requests")))?;
+        if round_request_count >= round.sel_round_max_requests as i64 {
+            return Err(ApiError::Conflict(format!(
+                "Request limit reached: at most {} requests per student in this round",
+                round.sel_round_max_requests
+            )));
+        }
+
+        diesel::insert_into(assignmentrequest::dsl::assignmentrequest)
+            .values(NewAssignmentRequest {
+                student_user_name: &username,
+                topic_id: req.topic_id,
+                assn_req_time: now,
+                sel_round_id: Some(round.sel_round_id),
+            })
+            .execute(conn)
+            .map_err(|_| ApiError::Conflict(str!("Failed to create assignment request")))?;
+
+        // A direct request supersedes the student's place on the topic's waitlist
+        diesel::delete(
+            topicwaitlist::table
+                .filter(topicwaitlist::columns::topic_id.eq(req.topic_id))
+                .filter(topicwaitlist::columns::student_user_name.eq(&username)),
+        )
+        .execute(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to update waitlist")))?;
+
+        Ok::<_, ApiError>(())
+    })?;
+
+    Ok(HttpResponse::Created().finish())
+}
+
+#[patch("/assignments/{student_username}/{topic_id}")]
+pub async fn update_assignment_status(
+    pool: web::Data<DbPool>,
+    session: Session,
+    path: web::Path<(String, i32)>,
+    req: web::Json<AssignmentRecordPatchRequest>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let username = get_session_username(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
+    let user_role = get_session_user_role(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+
+    if !matches!(user_role, AuthInfoUserRole::Teacher) {
+        // Only teachers can approve/reject assignment requests
+        return Err(ApiError::Forbidden);
+    }
+
+    let (student_username, topic_id) = path.into_inner();
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    conn.build_transaction().read_write().run(|conn| {
+        let topic = topic::dsl::topic
+            .find(topic_id)
+            .first::<Topic>(conn)
+            .map_err(|_| ApiError::NotFound)?;
+        if !can_approve_for_topic(conn, topic.topic_id, &topic.teacher_user_name, &username)? {
+            return Err(ApiError::Forbidden);
+        }
+
+        // Take the locks in the order every placement does: student, topics, teacher, and
+        // only then the request
+        let placement = if req.approved {
+            let student = student::dsl::student
+                .find(&student_username)
+                .for_no_key_update()
+                .first::<Student>(conn)
+                .map_err(|_| ApiError::NotFound)?;
+            let topic = lock_topic_for_placement(conn, &student, topic_id)?;
+            Some((student, topic))
+        } else {
+            topic::table
+                .find(topic_id)
+                .select(topic::columns::topic_id)
+                .for_update()
+                .first::<i32>(conn)
+                .map_err(|_| ApiError::NotFound)?;
+            None
+        };
+
+        let req_row = assignmentrequest::table
+            .filter(assignmentrequest::columns::student_user_name.eq(&student_username))
+            .filter(assignmentrequest::columns::topic_id.eq(topic_id))
+            .filter(
+                assignmentrequest::columns::assn_req_status.eq(AssignmentStatus::Pending as i16),
+            )
+            .for_update()
+            .first::<AssignmentRequest>(conn)
+            .map_err(|_| ApiError::NotFound)?;
+
+        if let Some((student, topic)) = placement {
+            if student.topic_id.is_some() {
+                return Err(ApiError::Conflict(str!("Student already has a topic")));
+            }
+
+            let current_student_count: i64 = student::dsl::student
+                .filter(student::columns::topic_id.eq(topic.topic_id))
+                .count()
+                .get_result(conn)
+                .map_err(|_| ApiError::InternalServerError(str!("Failed to count students")))?;
+            if current_student_count >= topic.topic_max_students as i64 {
+                return Err(ApiError::Conflict(str!("Topic is full")));
+            }
+
+            check_teacher_quota(conn, &topic.teacher_user_name, 0, None)?;
+
+            let changeset = StudentAssignmentChangeset {
+                topic_id: topic.topic_id,
+                assn_time: req_row.assn_req_time,
+            };
+            diesel::update(&student)
+                .set(changeset)
+                .execute(conn)
+                .map_err(map_placement_error)?;
+        }
+
+        let status = if req.approved {
+            AssignmentStatus::Approved
+        } else {
+            AssignmentStatus::Rejected
+        };
+        let reason = req
+            .reason
+            .as_deref()
+            .map(str::trim)
+            .filter(|reason| !reason.is_empty());
+        diesel::update(&req_row)
+            .set((
+                assignmentrequest::columns::assn_req_status.eq(status as i16),
+                assignmentrequest::columns::assn_req_decided_time.eq(Some(Utc::now())),
+                assignmentrequest::columns::assn_req_decided_by.eq(Some(&username)),
+                assignmentrequest::columns::assn_req_reason.eq(reason),
+            ))
+            .execute(conn)
+            .map_err(|_| {
+                ApiError::InternalServerError(str!("Failed to update assignment request"))
+            })?;
+
+        if req.approved {
+            close_sibling_requests(
+                conn,
+                &student_username,
+                "Student was assigned to another topic",
+            )?;
+        } else {
+            promote_waitlist(conn, req_row.topic_id)?;
+        }
+
+        Ok::<_, ApiError>(())
+    })?;
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+#[delete("/assignments/{topic_id}")]
+pub async fn withdraw_assignment(
+    pool: web::Data<DbPool>,
+    session: Session,
+    topic_id: web::Path<i32>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let username = get_session_username(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
+    let user_role = get_session_user_role(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+    if !matches!(user_role, AuthInfoUserRole::Student) {
+        // Only the requesting student can withdraw their request
+        return Err(ApiError::Forbidden);
+    }
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    conn.build_transaction().read_write().run(|conn| {
+        // Lock the topic before the request, in the order placements take them
+        topic::table
+            .find(*topic_id)
+            .select(topic::columns::topic_id)
+            .for_update()
+            .first::<i32>(conn)
+            .optional()
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to lock topic")))?;
+
+        let req_row = assignmentrequest::table
+            .filter(assignmentrequest::columns::student_user_name.eq(&username))
+            .filter(assignmentrequest::columns::topic_id.eq(*topic_id))
+            .filter(
+                assignmentrequest::columns::assn_req_status.eq(AssignmentStatus::Pending as i16),
+            )
+            .for_update()
+            .first::<AssignmentRequest>(conn)
+            .optional()
+            .map_err(|_| {
+                ApiError::InternalServerError(str!("Failed to load assignment request"))
+            })?;
+
+        let Some(req_row) = req_row else {
+            // Tell apart an approved request from a missing one
+            let assigned_topic = student::table
+                .find(&username)
+                .select(student::columns::topic_id)
+                .first::<Option<i32>>(conn)
+                .map_err(|_| ApiError::NotFound)?;
+            if assigned_topic == Some(*topic_id) {
+                return Err(ApiError::Conflict(str!(
+                    "Assignment request has already been approved"
+                )));
+            }
+            return Err(ApiError::NotFound);
+        };
+
+        diesel::update(&req_row)
+            .set((
+                assignmentrequest::columns::assn_req_status.eq(AssignmentStatus::Withdrawn as i16),
+                assignmentrequest::columns::assn_req_decided_time.eq(Some(Utc::now())),
+            ))
+            .execute(conn)
+            .map_err(|_| {
+                ApiError::InternalServerError(str!("Failed to withdraw assignment request"))
+            })?;
+
+        promote_waitlist(conn, req_row.topic_id)?;
+
+        Ok::<_, ApiError>(())
+    })?;
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+#[put("/students/{student_user_name}/topic")]
+pub async fn put_student_topic(
+    pool: web::Data<DbPool>,
+    session: Session,
+    student_user_name: web::Path<String>,
+    req: web::Json<StudentTopicPutRequest>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let username = get_session_username(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
+    let user_role = get_session_user_role(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+    if !matches!(user_role, AuthInfoUserRole::Office) {
+        // Only office can place students outside the request workflow
+        return Err(ApiError::Forbidden);
+    }
+
+    let reason = req.reason.trim();
+    if reason.is_empty() {
+        return Err(ApiError::BadRequest(str!("A reason is required")));
+    }
+    let override_capacity = req.override_capacity.unwrap_or(false);
+    let move_records = req.move_records.unwrap_or(false);
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    let change = conn.build_transaction().read_write().run(|conn| {
+        let student = student::table
+            .find(student_user_name.as_str())
+            .for_no_key_update()
+            .first::<Student>(conn)
+            .map_err(|_| ApiError::NotFound)?;
+        if student.topic_id == Some(req.topic_id) {
+            return Err(ApiError::Conflict(str!(
+                "Student is already assigned to this topic"
+            )));
+        }
+
+        let topic = lock_topic_for_placement(conn, &student, req.topic_id)?;
+        if topic.topic_review_status != TopicReviewStatus::Approved as i16 {
+            return Err(ApiError::Conflict(str!("Topic is not approved")));
+        }
+
+        if override_capacity {
+            // Lets the capacity trigger on Student pass for this transaction only
+            diesel::sql_query("SET LOCAL thesis_mgmt.capacity_override = 'on'")
+                .execute(conn)
+                .map_err(|_| {
+                    ApiError::InternalServerError(str!("Failed to override topic capacity"))
+                })?;
+        } else {
+            let current_student_count: i64 = student::table
+                .filter(student::columns::topic_id.eq(topic.topic_id))
+                .count()
+                .get_result(conn)
+                .map_err(|_| ApiError::InternalServerError(str!("Failed to count students")))?;
+            if current_student_count >= topic.topic_max_students as i64 {
+                return Err(ApiError::Conflict(str!("Topic is full")));
+            }
+
+            check_teacher_quota(conn, &topic.teacher_user_name, 0, None)?;
+        }
+
+        let now = Utc::now();
+        diesel::update(&student)
+            .set(StudentAssignmentChangeset {
+                topic_id: topic.topic_id,
+                assn_time: now,
+            })
+            .execute(conn)
+            .map_err(map_placement_error)?;
+
+        close_sibling_requests(conn, &student.user_name, "Student was placed by the office")?;
+
+        if let Some(old_topic_id) = student.topic_id {
+            settle_topic_records(
+                conn,
+                &student.user_name,
+                old_topic_id,
+                move_records.then_some(topic.topic_id),
+            )?;
+            promote_waitlist(conn, old_topic_id)?;
+        }
+
+        let change = diesel::insert_into(assignmentchange::table)
+            .values(NewAssignmentChange {
+                student_user_name: &student.user_name,
+                office_user_name: &username,
+                assn_change_old_topic_id: student.topic_id,
+                assn_change_new_topic_id: Some(topic.topic_id),
+                assn_change_reason: reason,
+                assn_change_time: now,
+                assn_change_overrode_capacity: override_capacity,
+                assn_change_moved_records: move_records && student.topic_id.is_some(),
+            })
+            .get_result::<AssignmentChange>(conn)
+            .map_err(|_| {
+                ApiError::InternalServerError(str!("Failed to record assignment change"))
+            })?;
+
+        let topic_ids = student
+            .topic_id
+            .into_iter()
+            .chain([topic.topic_id])
+            .collect::<Vec<_>>();
+        notify_assignment_change(
+            conn,
+            &student.user_name,
+            &topic_ids,
+            &format!(
+                "{} ({}) was placed on \"{}\" by the office. Reason: {}",
+                student.student_name, student.user_name, topic.topic_name, reason
+            ),
+        )?;
+
+        Ok::<_, ApiError>(change)
+    })?;
+
+    Ok(HttpResponse::Ok().json(build_assignment_change_details(change)))
+}
+
+#[delete("/students/{student_user_name}/topic")]
+pub async fn delete_student_topic(
+    pool: web::Data<DbPool>,
+    session: Session,
+    student_user_name: web::Path<String>,
+    req: web::Json<StudentTopicDeleteRequest>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let username = get_session_username(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
+    let user_role = get_session_user_role(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+    if !matches!(user_role, AuthInfoUserRole::Office) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let reason = req.reason.trim();
+    if reason.is_empty() {
+        return Err(ApiError::BadRequest(str!("A reason is required")));
+    }
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    let change = conn.build_transaction().read_write().run(|conn| {
+        let student = student::table
+            .find(student_user_name.as_str())
+            .for_no_key_update()
+            .first::<Student>(conn)
+            .map_err(|_| ApiError::NotFound)?;
+        let old_topic_id = student
+            .topic_id
+            .ok_or_else(|| ApiError::Conflict(str!("Student has no assigned topic")))?;
+
+        let now = Utc::now();
+        diesel::update(&student)
+            .set(student::columns::topic_id.eq(None::<i32>))
+            .execute(conn)
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to unassign topic")))?;
+
+        settle_topic_records(conn, &student.user_name, old_topic_id, None)?;
+        promote_waitlist(conn, old_topic_id)?;
+
+        let change = diesel::insert_into(assignmentchange::table)
+            .values(NewAssignmentChange {
+                student_user_name: &student.user_name,
+                office_user_name: &username,
+                assn_change_old_topic_id: Some(old_topic_id),
+                assn_change_new_topic_id: None,
+                assn_change_reason: reason,
+                assn_change_time: now,
+                assn_change_overrode_capacity: false,
+                assn_change_moved_records: false,
+            })
+            .get_result::<AssignmentChange>(conn)
+            .map_err(|_| {
+                ApiError::InternalServerError(str!("Failed to record assignment change"))
+            })?;
+
+        notify_assignment_change(
+            conn,
+            &student.user_name,
+            &[old_topic_id],
+            &format!(
+                "{} ({}) was removed from their topic by the office. Reason: {}",
+                student.student_name, student.user_name, reason
+            ),
+        )?;
+
+        Ok::<_, ApiError>(change)
+    })?;
+
+    Ok(HttpResponse::Ok().json(build_assignment_change_details(change)))
+}
+
+#[get("/students/{student_user_name}/assignment_changes")]
+pub async fn get_assignment_changes(
+    pool: web::Data<DbPool>,
+    session: Session,
+    student_user_name: web::Path<String>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let username = get_session_username(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
+    let user_role = get_session_user_role(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+    match user_role {
+        AuthInfoUserRole::Office => {}
+        AuthInfoUserRole::Student if username == *student_user_name => {}
+        _ => return Err(ApiError::Forbidden),
+    }
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    let changes = assignmentchange::table
+        .filter(assignmentchange::columns::student_user_name.eq(student_user_name.as_str()))
+        .order(assignmentchange::columns::assn_change_time.desc())
+        .load::<AssignmentChange>(&mut conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load assignment changes")))?
+        .into_iter()
+        .map(build_assignment_change_details)
+        .collect();
+
+    Ok(HttpResponse::Ok().json(AssignmentChangesGetResponse { changes }))
+}
+
+#[get("/selection_rounds")]
+pub async fn get_selection_rounds(
+    pool: web::Data<DbPool>,
+    session: Session,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    let rounds = selectionround::table
+        .order(selectionround::columns::sel_round_open_time.desc())
+        .load::<SelectionRound>(&mut conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load selection rounds")))?;
+    let rounds = build_selection_round_details(&mut conn, rounds)?;
+
+    Ok(HttpResponse::Ok().json(SelectionRoundsGetResponse { rounds }))
+}
+
+#[post("/selection_rounds")]
+pub async fn create_selection_round(
+    pool: web::Data<DbPool>,
+    session: Session,
+    req: web::Json<SelectionRoundsPostRequest>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let user_role = get_session_user_role(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+    if !matches!(user_role, AuthInfoUserRole::Office) {
+        // Only office can organize selection rounds
+        return Err(ApiError::Forbidden);
+    }
+
+    if req.sel_round_name.trim().is_empty() {
+        return Err(ApiError::BadRequest(str!("Round name must not be empty")));
+    }
+    if req.max_requests < 1 {
+        return Err(ApiError::BadRequest(str!(
+            "Students must be able to file at least one request"
+        )));
+    }
+    if req.open_time >= req.close_time {
+        return Err(ApiError::BadRequest(str!(
+            "Selection window must close after it opens"
+        )));
+    }
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    let details = conn.build_transaction().read_write().run(|conn| {
+        let mut major_ids = req.major_ids.clone().unwrap_or_default();
+
+        if let Some(prev_id) = req.carry_over_from {
+            let prev = selectionround::table
+                .find(prev_id)
+                .first::<SelectionRound>(conn)
+                .map_err(|_| ApiError::NotFound)?;
+            if prev.sel_round_close_time > req.open_time {
+                return Err(ApiError::Conflict(str!(
+                    "A carried-over round cannot open before the previous round closes"
+                )));
+            }
+            if prev.sel_round_close_time > Utc::now() {
+                return Err(ApiError::Conflict(str!(
+                    "Only a closed round can be carried over"
+                )));
+            }
+
+            // Majors of the previous round that still have students without a topic are always
+            // admitted, so that no leftover student is dropped
+            let prev_major_ids = selectionroundmajor::table
+                .filter(selectionroundmajor::columns::sel_round_id.eq(prev_id))
+                .select(selectionroundmajor::columns::major_id)
+                .load::<i32>(conn)
+                .map_err(|_| ApiError::InternalServerError(str!("Failed to load round majors")))?;
+            let leftover_major_ids = student::table
+                .filter(student::columns::major_id.eq_any(&prev_major_ids))
+                .filter(student::columns::topic_id.is_null())
+                .select(student::columns::major_id)
+                .distinct()
+                .load::<i32>(conn)
+                .map_err(|_| ApiError::InternalServerError(str!("Failed to load students")))?;
+            major_ids.extend(leftover_major_ids);
+        }
+
+        major_ids.sort_unstable();
+        major_ids.dedup();
+        if major_ids.is_empty() {
+            return Err(ApiError::BadRequest(str!(
+                "A round must admit at least one major"
+            )));
+        }
+        let known_majors: i64 = major::table
+            .filter(major::columns::major_id.eq_any(&major_ids))
+            .count()
+            .get_result(conn)
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to check majors")))?;
+        if known_majors != major_ids.len() as i64 {
+            return Err(ApiError::BadRequest(str!("Unknown major")));
+        }
+
+        check_selection_round_overlap(conn, None, &major_ids, req.open_time, req.close_time)?;
+
+        let round = diesel::insert_into(selectionround::table)
+            .values(NewSelectionRound {
+                sel_round_prev_id: req.carry_over_from,
+                sel_round_name: req.sel_round_name.trim(),
+                sel_round_open_time: req.open_time,
+                sel_round_close_time: req.close_time,
+                sel_round_max_requests: req.max_requests,
+            })
+            .get_result::<SelectionRound>(conn)
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to create selection round")))?;
+
+        diesel::insert_into(selectionroundmajor::table)
+            .values(
+                major_ids
+                    .iter()
+                    .map(|&major_id| NewSelectionRoundMajor {
+                        sel_round_id: round.sel_round_id,
+                        major_id,
+                    })
+                    .collect::<Vec<_>>(),
+            )
+            .execute(conn)
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to save round majors")))?;
+
+        // Requests still pending when the previous round closed move on to this round as they
+        // are, keeping their original time and so their place
+        if let Some(prev_id) = req.carry_over_from {
+            diesel::update(
+                assignmentrequest::table
+                    .filter(assignmentrequest::columns::sel_round_id.eq(prev_id))
+                    .filter(
+                        assignmentrequest::columns::assn_req_status
+                            .eq(AssignmentStatus::Pending as i16),
+                    ),
+            )
+            .set(assignmentrequest::columns::sel_round_id.eq(Some(round.sel_round_id)))
+            .execute(conn)
+            .map_err(|_| {
+                ApiError::InternalServerError(str!("Failed to carry over assignment requests"))
+            })?;
+        }
+
+        let mut details = build_selection_round_details(conn, vec![round])?;
+        Ok::<_, ApiError>(details.remove(0))
+    })?;
+
+    Ok(HttpResponse::Created().json(details))
+}
+
+#[patch("/selection_rounds/{sel_round_id}")]
+pub async fn update_selection_round(
+    pool: web::Data<DbPool>,
+    session: Session,
+    sel_round_id: web::Path<i32>,
+    req: web::Json<SelectionRoundPatchRequest>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let user_role = get_session_user_role(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+    if !matches!(user_role, AuthInfoUserRole::Office) {
+        return Err(ApiError::Forbidden);
+    }
+
+    if req
+        .sel_round_name
+        .as_ref()
+        .is_some_and(|name| name.trim().is_empty())
+    {
+        return Err(ApiError::BadRequest(str!("Round name must not be empty")));
+    }
+    if req.max_requests.is_some_and(|max| max < 1) {
+        return Err(ApiError::BadRequest(str!(
+            "Students must be able to file at least one request"
+        )));
+    }
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    let details = conn.build_transaction().read_write().run(|conn| {
+        let round = selectionround::table
+            .find(*sel_round_id)
+            .for_update()
+            .first::<SelectionRound>(conn)
+            .map_err(|_| ApiError::NotFound)?;
+
+        let open_time = req.open_time.unwrap_or(round.sel_round_open_time);
+        let close_time = req.close_time.unwrap_or(round.sel_round_close_time);
+        if open_time >= close_time {
+            return Err(ApiError::BadRequest(str!(
+                "Selection window must close after it opens"
+            )));
+        }
+
+        if let Some(prev_id) = round.sel_round_prev_id {
+            let prev_close_time = selectionround::table
+                .find(prev_id)
+                .select(selectionround::columns::sel_round_close_time)
+                .first::<DateTime<Utc>>(conn)
+                .map_err(|_| {
+                    ApiError::InternalServerError(str!("Failed to load previous round"))
+                })?;
+            if prev_close_time > open_time {
+                return Err(ApiError::Conflict(str!(
+                    "A carried-over round cannot open before the previous round closes"
+                )));
+            }
+        }
+
+        if req.open_time.is_some() || req.close_time.is_some() {
+            let major_ids = selectionroundmajor::table
+                .filter(selectionroundmajor::columns::sel_round_id.eq(round.sel_round_id))
+                .select(selectionroundmajor::columns::major_id)
+                .load::<i32>(conn)
+                .map_err(|_| ApiError::InternalServerError(str!("Failed to load round majors")))?;
+            check_selection_round_overlap(
+                conn,
+                Some(round.sel_round_id),
+                &major_ids,
+                open_time,
+                close_time,
+            )?;
+        }
+
+        let changeset = SelectionRoundChangeset {
+            sel_round_name: req
+                .sel_round_name
+                .as_ref()
+                .map(|name| name.trim().to_owned()),
+            sel_round_open_time: req.open_time,
+            sel_round_close_time: req.close_time,
+            sel_round_max_requests: req.max_requests,
+        };
+        let round = if changeset.sel_round_name.is_none()
+            && changeset.sel_round_open_time.is_none()
+            && changeset.sel_round_close_time.is_none()
+            && changeset.sel_round_max_requests.is_none()
+        {
+            round
+        } else {
+            diesel::update(&round)
+                .set(changeset)
+                .get_result::<SelectionRound>(conn)
+                .map_err(|_| {
+                    ApiError::InternalServerError(str!("Failed to update selection round"))
+                })?
+        };
+
+        let mut details = build_selection_round_details(conn, vec![round])?;
+        Ok::<_, ApiError>(details.remove(0))
+    })?;
+
+    Ok(HttpResponse::Ok().json(details))
+}
+
+#[get("/report_stages")]
+pub async fn get_report_stages(
+    pool: web::Data<DbPool>,
+    session: Session,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    let stages = reportstage::table
+        .order((
+            reportstage::columns::stage_order.asc(),
+            reportstage::columns::stage_id.asc(),
+        ))
+        .load::<ReportStage>(&mut conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load stages")))?;
+    let stages = build_report_stage_details(&mut conn, stages)?;
+
+    Ok(HttpResponse::Ok().json(ReportStagesGetResponse { stages }))
+}
+
+#[post("/report_stages")]
+pub async fn create_report_stage(
+    pool: web::Data<DbPool>,
+    session: Session,
+    req: web::Json<ReportStagesPostRequest>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let user_role = get_session_user_role(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+    if !matches!(user_role, AuthInfoUserRole::Office) {
+        // Only office can define the stage pipeline
+        return Err(ApiError::Forbidden);
+    }
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    let details = conn.build_transaction().read_write().run(|conn| {
+        let stage_name = req.stage_name.trim();
+        check_stage_name(conn, None, stage_name)?;
+
+        let stage = diesel::insert_into(reportstage::table)
+            .values(NewReportStage {
+                stage_name,
+                stage_order: req.order,
+                stage_reviewer: req.reviewer as i16,
+                stage_grade_required: req.grade_required,
+            })
+            .get_result::<ReportStage>(conn)
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to create stage")))?;
+        replace_stage_prerequisites(
+            conn,
+            stage.stage_id,
+            req.prerequisites.as_deref().unwrap_or_default(),
+        )?;
+
+        let mut details = build_report_stage_details(conn, vec![stage])?;
+        Ok::<_, ApiError>(details.remove(0))
+    })?;
+
+    Ok(HttpResponse::Created().json(details))
+}
+
+#[patch("/report_stages/{stage_id}")]
+pub async fn update_report_stage(
+    pool: web::Data<DbPool>,
+    session: Session,
+    stage_id: web::Path<i16>,
+    req: web::Json<ReportStagePatchRequest>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let user_role = get_session_user_role(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+    if !matches!(user_role, AuthInfoUserRole::Office) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    let details = conn.build_transaction().read_write().run(|conn| {
+        let stage = reportstage::table
+            .find(*stage_id)
+            .for_update()
+            .first::<ReportStage>(conn)
+            .map_err(|_| ApiError::NotFound)?;
+
+        let stage_name = req.stage_name.as_deref().map(str::trim);
+        if let Some(stage_name) = stage_name {
+            check_stage_name(conn, Some(stage.stage_id), stage_name)?;
+        }
+        if let Some(prerequisites) = &req.prerequisites {
+            replace_stage_prerequisites(conn, stage.stage_id, prerequisites)?;
+        }
+
+        let changeset = ReportStageChangeset {
+            stage_name: stage_name.map(str::to_owned),
+            stage_order: req.order,
+            stage_reviewer: req.reviewer.map(|reviewer| reviewer as i16),
+            stage_grade_required: req.grade_required,
+        };
+        let stage = if changeset.stage_name.is_none()
+            && changeset.stage_order.is_none()
+            && changeset.stage_reviewer.is_none()
+            && changeset.stage_grade_required.is_none()
+        {
+            stage
+        } else {
+            diesel::update(&stage)
+                .set(changeset)
+                .get_result::<ReportStage>(conn)
+                .map_err(|_| ApiError::InternalServerError(str!("Failed to update stage")))?
+        };
+
+        let mut details = build_report_stage_details(conn, vec![stage])?;
+        Ok::<_, ApiError>(details.remove(0))
+    })?;
+
+    Ok(HttpResponse::Ok().json(details))
+}
+
+#[delete("/report_stages/{stage_id}")]
+pub async fn delete_report_stage(
+    pool: web::Data<DbPool>,
+    session: Session,
+    stage_id: web::Path<i16>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let user_role = get_session_user_role(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+    if !matches!(user_role, AuthInfoUserRole::Office) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    conn.build_transaction().read_write().run(|conn| {
+        let stage = reportstage::table
+            .find(*stage_id)
+            .for_update()
+            .first::<ReportStage>(conn)
+            .map_err(|_| ApiError::NotFound)?;
+
+        let has_reports = diesel::select(diesel::dsl::exists(
+            progressreport::table
+                .filter(progressreport::columns::prog_report_stage_id.eq(stage.stage_id)),
+        ))
+        .get_result::<bool>(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to check stage reports")))?;
+        if has_reports {
+            return Err(ApiError::Conflict(str!(
+                "Reports have already been submitted for this stage"
+            )));
+        }
+
+        let is_required = diesel::select(diesel::dsl::exists(
+            reportstageprerequisite::table
+                .filter(reportstageprerequisite::columns::prereq_stage_id.eq(stage.stage_id)),
+        ))
+        .get_result::<bool>(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to check dependent stages")))?;
+        if is_required {
+            return Err(ApiError::Conflict(str!("Other stages require this stage")));
+        }
+
+        diesel::delete(
+            reportstageprerequisite::table
+                .filter(reportstageprerequisite::columns::stage_id.eq(stage.stage_id)),
+        )
+        .execute(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to clear stage prerequisites")))?;
+
+        let deadline_ids = submissiondeadline::table
+            .filter(submissiondeadline::columns::stage_id.eq(stage.stage_id))
+            .select(submissiondeadline::columns::deadline_id);
+        diesel::delete(
+            deadlineextension::table
+                .filter(deadlineextension::columns::deadline_id.eq_any(deadline_ids)),
+        )
+        .execute(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to clear deadline extensions")))?;
+        diesel::delete(
+            submissiondeadline::table
+                .filter(submissiondeadline::columns::stage_id.eq(stage.stage_id)),
+        )
+        .execute(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to clear stage deadlines")))?;
+        diesel::delete(
+            rubriccriterion::table.filter(rubriccriterion::columns::stage_id.eq(stage.stage_id)),
+        )
+        .execute(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to clear stage rubric")))?;
+        diesel::delete(&stage)
+            .execute(conn)
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to delete stage")))?;
+
+        Ok::<_, ApiError>(())
+    })?;
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+#[get("/deadlines")]
+pub async fn get_deadlines(
+    pool: web::Data<DbPool>,
+    session: Session,
+    query: web::Query<DeadlinesQuery>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let username = get_session_username(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
+    let user_role = get_session_user_role(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    let mut base = submissiondeadline::table.into_boxed();
+    let only_student = match user_role {
+        AuthInfoUserRole::Student => {
+            // Students only see the deadlines of their own major
+            let student = student::table
+                .find(&username)
+                .first::<Student>(&mut conn)
+                .map_err(|_| ApiError::NotFound)?;
+            base = base.filter(submissiondeadline::columns::major_id.eq(student.major_id));
+            Some(username.as_str())
+        }
+        _ => {
+            if let Some(major_id) = query.major_id {
+                base = base.filter(submissiondeadline::columns::major_id.eq(major_id));
+            }
+            None
+        }
+    };
+
+    let deadlines = base
+        .order((
+            submissiondeadline::columns::major_id.asc(),
+            submissiondeadline::columns::stage_id.asc(),
+        ))
+        .load::<SubmissionDeadline>(&mut conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load deadlines")))?;
+    let deadlines = build_deadline_details(&mut conn, deadlines, only_student)?;
+
+    Ok(HttpResponse::Ok().json(DeadlinesGetResponse { deadlines }))
+}
+
+#[post("/deadlines")]
+pub async fn create_deadline(
+    pool: web::Data<DbPool>,
+    session: Session,
+    req: web::Json<DeadlinesPostRequest>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let user_role = get_session_user_role(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+    if !matches!(user_role, AuthInfoUserRole::Office) {
+        // Only office can schedule submission deadlines
+        return Err(ApiError::Forbidden);
+    }
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    let details = conn.build_transaction().read_write().run(|conn| {
+        major::table
+            .find(req.major_id)
+            .first::<Major>(conn)
+            .map_err(|_| ApiError::BadRequest(str!("Unknown major")))?;
+        if let Some(stage_id) = req.stage_id {
+            reportstage::table
+                .find(stage_id)
+                .first::<ReportStage>(conn)
+                .map_err(|_| ApiError::BadRequest(str!("Unknown stage")))?;
+        }
+
+        let mut existing = submissiondeadline::table
+            .filter(submissiondeadline::columns::major_id.eq(req.major_id))
+            .into_boxed();
+        existing = match req.stage_id {
+            Some(stage_id) => existing.filter(submissiondeadline::columns::stage_id.eq(stage_id)),
+            None => existing.filter(submissiondeadline::columns::stage_id.is_null()),
+        };
+        let exists = diesel::select(diesel::dsl::exists(existing))
+            .get_result::<bool>(conn)
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to check deadlines")))?;
+        if exists {
+            return Err(ApiError::Conflict(str!(
+                "A deadline already exists for this major and stage"
+            )));
+        }
+
+        let deadline = diesel::insert_into(submissiondeadline::table)
+            .values(NewSubmissionDeadline {
+                major_id: req.major_id,
+                stage_id: req.stage_id,
+                deadline_due_time: req.due_time,
+                deadline_is_hard: req.is_hard,
+            })
+            .get_result::<SubmissionDeadline>(conn)
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to create deadline")))?;
+
+        let mut details = build_deadline_details(conn, vec![deadline], None)?;
+        Ok::<_, ApiError>(details.remove(0))
+    })?;
+
+    Ok(HttpResponse::Created().json(details))
+}
+
+#[patch("/deadlines/{deadline_id}")]
+pub async fn update_deadline(
+    pool: web::Data<DbPool>,
+    session: Session,
+    deadline_id: web::Path<i32>,
+    req: web::Json<DeadlinePatchRequest>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let user_role = get_session_user_role(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+    if !matches!(user_role, AuthInfoUserRole::Office) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    let details = conn.build_transaction().read_write().run(|conn| {
+        let deadline = submissiondeadline::table
+            .find(*deadline_id)
+            .for_update()
+            .first::<SubmissionDeadline>(conn)
+            .map_err(|_| ApiError::NotFound)?;
+
+        let changeset = SubmissionDeadlineChangeset {
+            deadline_due_time: req.due_time,
+            deadline_is_hard: req.is_hard,
+        };
+        let deadline =
+            if changeset.deadline_due_time.is_none() && changeset.deadline_is_hard.is_none() {
+                deadline
+            } else {
+                diesel::update(&deadline)
+                    .set(changeset)
+                    .get_result::<SubmissionDeadline>(conn)
+                    .map_err(|_| ApiError::InternalServerError(str!("Failed to update deadline")))?
+            };
+
+        let mut details = build_deadline_details(conn, vec![deadline], None)?;
+        Ok::<_, ApiError>(details.remove(0))
+    })?;
+
+    Ok(HttpResponse::Ok().json(details))
+}
+
+#[delete("/deadlines/{deadline_id}")]
+pub async fn delete_deadline(
+    pool: web::Data<DbPool>,
+    session: Session,
+    deadline_id: web::Path<i32>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let user_role = get_session_user_role(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+    if !matches!(user_role, AuthInfoUserRole::Office) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    conn.build_transaction().read_write().run(|conn| {
+        let deadline = submissiondeadline::table
+            .find(*deadline_id)
+            .for_update()
+            .first::<SubmissionDeadline>(conn)
+            .map_err(|_| ApiError::NotFound)?;
+
+        diesel::delete(
+            deadlineextension::table
+                .filter(deadlineextension::columns::deadline_id.eq(deadline.deadline_id)),
+        )
+        .execute(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to clear deadline extensions")))?;
+        diesel::delete(&deadline)
+            .execute(conn)
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to delete deadline")))?;
+
+        Ok::<_, ApiError>(())
+    })?;
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+#[put("/deadlines/{deadline_id}/extensions/{student_user_name}")]
+pub async fn put_deadline_extension(
+    pool: web::Data<DbPool>,
+    session: Session,
+    path: web::Path<(i32, String)>,
+    req: web::Json<DeadlineExtensionPutRequest>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let user_role = get_session_user_role(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+    if !matches!(user_role, AuthInfoUserRole::Office) {
+        // Only office can grant extensions
+        return Err(ApiError::Forbidden);
+    }
+
+    let (deadline_id, student_user_name) = path.into_inner();
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    let details = conn.build_transaction().read_write().run(|conn| {
+        let deadline = submissiondeadline::table
+            .find(deadline_id)
+            .first::<SubmissionDeadline>(conn)
+            .map_err(|_| ApiError::NotFound)?;
+        let student = student::table
+            .find(&student_user_name)
+            .first::<Student>(conn)
+            .map_err(|_| ApiError::NotFound)?;
+        if student.major_id != deadline.major_id {
+            return Err(ApiError::BadRequest(str!(
+                "The student is not in the major of this deadline"
+            )));
+        }
+        if req.due_time <= deadline.deadline_due_time {
+            return Err(ApiError::BadRequest(str!(
+                "An extension must end after the deadline"
+            )));
+        }
+
+        let extension = NewDeadlineExtension {
+            deadline_id,
+            student_user_name: &student.user_name,
+            ext_due_time: req.due_time,
+        };
+        diesel::insert_into(deadlineextension::table)
+            .values(&extension)
+            .on_conflict((
+                deadlineextension::columns::deadline_id,
+                deadlineextension::columns::student_user_name,
+            ))
+            .do_update()
+            .set(&extension)
+            .execute(conn)
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to store extension")))?;
+
+        diesel::insert_into(notification::table)
+            .values(NewNotification {
+                user_name: &student.user_name,
+                notif_content: format!(
+                    "Your submission deadline was extended to {}.",
+                    req.due_time.format("%Y-%m-%d %H:%M UTC")
+                ),
+                notif_time: Utc::now(),
+            })
+            .execute(conn)
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to send notifications")))?;
+
+        Ok::<_, ApiError>(DeadlineExtensionDetails {
+            student_user_name: student.user_name,
+            student_name: student.student_name,
+            due_time: req.due_time,
+        })
+    })?;
+
+    Ok(HttpResponse::Ok().json(details))
+}
+
+#[delete("/deadlines/{deadline_id}/extensions/{student_user_name}")]
+pub async fn delete_deadline_extension(
+    pool: web::Data<DbPool>,
+    session: Session,
+    path: web::Path<(i32, String)>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let user_role = get_session_user_role(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+    if !matches!(user_role, AuthInfoUserRole::Office) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let (deadline_id, student_user_name) = path.into_inner();
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    let deleted = diesel::delete(deadlineextension::table.find((deadline_id, &student_user_name)))
+        .execute(&mut conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to delete extension")))?;
+    if deleted == 0 {
+        return Err(ApiError::NotFound);
+    }
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+#[get("/revision_locks")]
+pub async fn get_revision_locks(
+    pool: web::Data<DbPool>,
+    session: Session,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let user_role = get_session_user_role(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+    if !matches!(
+        user_role,
+        AuthInfoUserRole::Teacher | AuthInfoUserRole::Office
+    ) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    let locks = revisionlock::table
+        .order(revisionlock::columns::major_id.asc())
+        .load::<RevisionLock>(&mut conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load revision locks")))?
+        .into_iter()
+        .map(|lock| RevisionLockDetails {
+            major_id: lock.major_id,
+            lock_time: lock.rev_lock_time,
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(RevisionLocksGetResponse { locks }))
+}
+
+#[put("/revision_locks/{major_id}")]
+pub async fn put_revision_lock(
+    pool: web::Data<DbPool>,
+    session: Session,
+    major_id: web::Path<i32>,
+    req: web::Json<RevisionLockPutRequest>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let user_role = get_session_user_role(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+    if !matches!(user_role, AuthInfoUserRole::Office) {
+        // Only office can lock the outcomes of a cohort
+        return Err(ApiError::Forbidden);
+    }
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    conn.build_transaction().read_write().run(|conn| {
+        major::table
+            .find(*major_id)
+            .first::<Major>(conn)
+            .map_err(|_| ApiError::NotFound)?;
+
+        let lock = NewRevisionLock {
+            major_id: *major_id,
+            rev_lock_time: req.lock_time,
+        };
+        diesel::insert_into(revisionlock::table)
+            .values(&lock)
+            .on_conflict(revisionlock::columns::major_id)
+            .do_update()
+            .set(&lock)
+            .execute(conn)
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to store revision lock")))?;
+
+        Ok::<_, ApiError>(())
+    })?;
+
+    Ok(HttpResponse::Ok().json(RevisionLockDetails {
+        major_id: *major_id,
+        lock_time: req.lock_time,
+    }))
+}
+
+#[delete("/revision_locks/{major_id}")]
+pub async fn delete_revision_lock(
+    pool: web::Data<DbPool>,
+    session: Session,
+    major_id: web::Path<i32>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let user_role = get_session_user_role(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+    if !matches!(user_role, AuthInfoUserRole::Office) {
+        // Only office can lock the outcomes of a cohort
+        return Err(ApiError::Forbidden);
+    }
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    let deleted = diesel::delete(revisionlock::table.find(*major_id))
+        .execute(&mut conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to delete revision lock")))?;
+    if deleted == 0 {
+        return Err(ApiError::NotFound);
+    }
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+#[get("/rubrics")]
+pub async fn get_rubrics(
+    pool: web::Data<DbPool>,
+    session: Session,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    let criteria = rubriccriterion::table
+        .order((
+            rubriccriterion::columns::stage_id.asc(),
+            rubriccriterion::columns::crit_order.asc(),
+            rubriccriterion::columns::crit_id.asc(),
+        ))
+        .load::<RubricCriterion>(&mut conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load rubrics")))?;
+
+    let mut rubrics: Vec<RubricDetails> = Vec::new();
+    for criterion in criteria {
+        match rubrics.last_mut() {
+            Some(rubric) if rubric.stage_id == criterion.stage_id => {
+                rubric
+                    .criteria
+                    .push(build_rubric_criterion_details(criterion));
+            }
+            _ => rubrics.push(RubricDetails {
+                stage_id: criterion.stage_id,
+                criteria: vec![build_rubric_criterion_details(criterion)],
+            }),
+        }
+    }
+
+    Ok(HttpResponse::Ok().json(RubricsGetResponse { rubrics }))
+}
+
+#[put("/rubrics")]
+pub async fn put_rubric(
+    pool: web::Data<DbPool>,
+    session: Session,
+    req: web::Json<RubricPutRequest>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let user_role = get_session_user_role(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+    if !matches!(user_role, AuthInfoUserRole::Office) {
+        // Only office can define grading rubrics
+        return Err(ApiError::Forbidden);
+    }
+
+    if req.criteria.len() > MAX_RUBRIC_CRITERIA {
+        return Err(ApiError::BadRequest(format!(
+            "A rubric may have at most {MAX_RUBRIC_CRITERIA} criteria"
+        )));
+    }
+    let zero = BigDecimal::from(0);
+    let max_value = BigDecimal::new(99999.into(), 2);
+    let mut names = HashSet::new();
+    for criterion in &req.criteria {
+        let name = criterion.name.trim();
+        if name.is_empty() || name.chars().count() > MAX_CRITERION_NAME_LEN {
+            return Err(ApiError::BadRequest(format!(
+                "Criterion names must be 1 to {MAX_CRITERION_NAME_LEN} characters"
+            )));
+        }
+        if !names.insert(name) {
+            return Err(ApiError::BadRequest(str!("Criterion names must be unique")));
+        }
+        for value in [&criterion.weight, &criterion.max_score] {
+            if *value <= zero || *value > max_value {
+                return Err(ApiError::BadRequest(str!(
+                    "Weights and maximum scores must be between 0 and 999.99"
+                )));
+            }
+        }
+    }
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    let details = conn.build_transaction().read_write().run(|conn| {
+        if let Some(stage_id) = req.stage_id {
+            reportstage::table
+                .find(stage_id)
+                .for_update()
+                .first::<ReportStage>(conn)
+                .map_err(|_| ApiError::BadRequest(str!("Unknown stage")))?;
+        }
+
+        let old_ids = load_rubric_criteria(conn, req.stage_id)?
+            .into_iter()
+            .map(|criterion| criterion.crit_id)
+            .collect::<Vec<_>>();
+        let is_used = diesel::select(
+            diesel::dsl::exists(
+                progressreportscore::table
+                    .filter(progressreportscore::columns::crit_id.eq_any(&old_ids)),
+            )
+            .or(diesel::dsl::exists(finaldefensescore::table.filter(
+                finaldefensescore::columns::crit_id.eq_any(&old_ids),
+            ))),
+        )
+        .get_result::<bool>(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to check rubric scores")))?;
+        if is_used {
+            return Err(ApiError::Conflict(str!(
+                "Submissions have already been scored with this rubric"
+            )));
+        }
+
+        diesel::delete(
+            rubriccriterion::table.filter(rubriccriterion::columns::crit_id.eq_any(&old_ids)),
+        )
+        .execute(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to clear rubric")))?;
+        let criteria = diesel::insert_into(rubriccriterion::table)
+            .values(
+                req.criteria
+                    .iter()
+                    .enumerate()
+                    .map(|(order, criterion)| NewRubricCriterion {
+                        stage_id: req.stage_id,
+                        crit_name: criterion.name.trim(),
+                        crit_order: order as i16,
+                        crit_weight: criterion.weight.clone(),
+                        crit_max_score: criterion.max_score.clone(),
+                    })
+                    .collect::<Vec<_>>(),
+            )
+            .get_results::<RubricCriterion>(conn)
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to store rubric")))?;
+
+        Ok::<_, ApiError>(RubricDetails {
+            stage_id: req.stage_id,
+            criteria: criteria
+                .into_iter()
+                .map(build_rubric_criterion_details)
+                .collect(),
+        })
+    })?;
+
+    Ok(HttpResponse::Ok().json(details))
+}
+
+#[get("/progress_reports")]
+pub async fn get_progress_reports(
+    pool: web::Data<DbPool>,
+    session: Session,
+    query: web::Query<ProgressReportsQuery>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let is_admin = is_session_admin(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Can't deserialize auth info")))?;
+
+    let username = get_session_username(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
+    let user_role = if is_admin {
+        None
+    } else {
+        Some(get_session_user_role(&session).map_err(|_| {
+            ApiError::InternalServerError(str!("Failed to get user role from session"))
+        })?)
+    };
+
+    if !is_admin {
+        match user_role {
+            Some(
+                AuthInfoUserRole::Student | AuthInfoUserRole::Teacher | AuthInfoUserRole::Office,
+            ) => {}
+            _ => return Err(ApiError::Forbidden),
+        }
+    }
+
+    let page = query.page.unwrap_or(1);
+    let page_size = query.page_size.unwrap_or(20);
+    if page < 1 || !(1..=100).contains(&page_size) {
+        return Err(ApiError::BadRequest(str!(
+            "Page must be positive and page size between 1 and 100"
+        )));
+    }
+    let offset = (page - 1) * page_size;
+    let descending = is_descending_order(query.order.as_deref())?;
+    let student_pattern = search_pattern(query.student.as_deref());
+
+    // Office and admins oversee every report; the others only see their own
+    let visible = || {
+        let mut q = progressreport::table
+            .inner_join(student::table)
+            .inner_join(topic::table)
+            .inner_join(reportstage::table)
+            .into_boxed();
+        match user_role {
+            Some(AuthInfoUserRole::Student) => {
+                q = q.filter(progressreport::columns::student_user_name.eq(&username));
+            }
+            Some(AuthInfoUserRole::Teacher) => {
+                q = q.filter(
+                    topic::columns::teacher_user_name
+                        .eq(&username)
+                        .or(topic::columns::topic_id.eq_any(cosupervised_topic_ids(&username))),
+                );
+            }
+            _ => {}
+        }
+        if let Some(major_id) = query.major_id {
+            q = q.filter(student::columns::major_id.eq(major_id));
+        }
+        if let Some(teacher_user_name) = &query.teacher_user_name {
+            q = q.filter(topic::columns::teacher_user_name.eq(teacher_user_name));
+        }
+        if let Some(stage_id) = query.stage_id {
+            q = q.filter(progressreport::columns::prog_report_stage_id.eq(stage_id));
+        }
+        if let Some(outcome) = query.outcome {
+            q = q.filter(progressreport::columns::prog_report_outcome.eq(outcome as i16));
+        }
+        match query.pending {
+            Some(true) => {
+                q = q.filter(
+                    progressreport::columns::prog_report_outcome
+                        .eq(ProgressOutcome::NoConclusion as i16),
+                );
+            }
+            Some(false) => {
+                q = q.filter(
+                    progressreport::columns::prog_report_outcome
+                        .ne(ProgressOutcome::NoConclusion as i16),
+                );
+            }
+            None => {}
+        }
+        if let Some(pattern) = &student_pattern {
+            q = q.filter(
+                student::columns::student_name
+                    .ilike(pattern)
+                    .or(progressreport::columns::student_user_name.ilike(pattern)),
+            );
+        }
+        q
+    };
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    let total: i64 = visible()
+        .count()
+        .get_result(&mut conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to count progress reports")))?;
+    if total == 0 {
+        return Err(ApiError::NotFound);
+    }
+
+    let ordered = if descending {
+        visible().order((
+            progressreport::columns::prog_report_time.desc(),
+            progressreport::columns::prog_report_id.desc(),
+        ))
+    } else {
+        visible().order((
+            progressreport::columns::prog_report_time.asc(),
+            progressreport::columns::prog_report_id.asc(),
+        ))
+    };
+    let rows = ordered
+        .offset(offset)
+        .limit(page_size)
+        .select((
+            progressreport::all_columns,
+            student::columns::student_name,
+            reportstage::columns::stage_name,
+        ))
+        .load::<(ProgressReport, String, String)>(&mut conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load progress reports")))?;
+
+    let report_ids = rows
+        .iter()
+        .map(|(r, _, _)| r.prog_report_id)
+        .collect::<Vec<_>>();
+    let mut files_by_report = load_progress_report_files(&mut conn, &report_ids)?;
+    let mut scores_by_report = load_progress_report_scores(&mut conn, &report_ids)?;
+
+    let reports = rows
+        .into_iter()
+        .map(|(r, student_name, stage_name)| {
+            Ok::<_, ApiError>(ProgressReportDetailResponse {
+                prog_report_id: r.prog_report_id,
+                topic_id: r.topic_id,
+                student_user_name: r.student_user_name,
+                student_name,
+                prog_report_stage_id: r.prog_report_stage_id,
+                prog_report_stage_name: stage_name,
+                prog_report_time: r.prog_report_time,
+                prog_report_version: r.prog_report_version,
+                prog_report_files: files_by_report
+                    .remove(&r.prog_report_id)
+                    .unwrap_or_default(),
+                prog_report_due_time: r.prog_report_due_time,
+                prog_report_late: r
+                    .prog_report_due_time
+                    .is_some_and(|due_time| r.prog_report_time > due_time),
+                prog_report_outcome: ProgressOutcome::try_from(r.prog_report_outcome)
+                    .map_err(|_| ApiError::InternalServerError(str!("Invalid progress outcome")))?,
+                prog_report_comment: r.prog_report_comment,
+                prog_report_grade: r.prog_report_grade,
+                prog_report_scores: scores_by_report
+                    .remove(&r.prog_report_id)
+                    .unwrap_or_default(),
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(HttpResponse::Ok().json(ProgressReportsGetResponse {
+        total,
+        page,
+        page_size,
+        reports,
+    }))
+}
+
+#[post("/progress_reports")]
+pub async fn create_progress_report(
+    pool: web::Data<DbPool>,
+    session: Session,
+    req: web::Json<ProgressReportsPostRequest>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let username = get_session_username(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    let result = conn.build_transaction().read_write().run(|conn| {
+        let student = student::dsl::student
+            .find(&username)
+            .first::<Student>(conn)
+            .map_err(|_| ApiError::NotFound)?;
+        let topic_id = student
+            .topic_id
+            .ok_or_else(|| ApiError::Conflict(str!("Student has no assigned topic")))?;
+        check_submission_files(conn, &req.files, &username)?;
+
+        // Follow the stage pipeline: a stage opens once all of its prerequisites have passed.
+        let passed_stage_ids = load_passed_stage_ids(conn, &username, topic_id)?;
+        let prerequisites = reportstageprerequisite::table
+            .load::<ReportStagePrerequisite>(conn)
+            .map_err(|_| {
+                ApiError::InternalServerError(str!("Failed to load stage prerequisites"))
+            })?;
+        let is_open = |stage_id: i16| {
+            prerequisites
+                .iter()
+                .filter(|prereq| prereq.stage_id == stage_id)
+                .all(|prereq| passed_stage_ids.contains(&prereq.prereq_stage_id))
+        };
+
+        let stage_id = match req.stage_id {
+            Some(stage_id) => {
+                reportstage::table
+                    .find(stage_id)
+                    .first::<ReportStage>(conn)
+                    .map_err(|_| ApiError::BadRequest(str!("Unknown stage")))?;
+                if !is_open(stage_id) {
+                    return Err(ApiError::Conflict(str!(
+                        "Prerequisite stages have not been passed"
+                    )));
+                }
+                stage_id
+            }
+            None => reportstage::table
+                .order((
+                    reportstage::columns::stage_order.asc(),
+                    reportstage::columns::stage_id.asc(),
+                ))
+                .select(reportstage::columns::stage_id)
+                .load::<i16>(conn)
+                .map_err(|_| ApiError::InternalServerError(str!("Failed to load stages")))?
+                .into_iter()
+                .find(|&stage_id| !passed_stage_ids.contains(&stage_id) && is_open(stage_id))
+                .ok_or_else(|| ApiError::Conflict(str!("No stage is open for submission")))?,
+        };
+
+        // Enforce: per (student, topic, stage) at most one pending and one passed.
+        let has_pending = diesel::select(diesel::dsl::exists(
+            progressreport::dsl::progressreport
+                .filter(progressreport::columns::student_user_name.eq(&username))
+                .filter(progressreport::columns::topic_id.eq(topic_id))
+                .filter(progressreport::columns::prog_report_stage_id.eq(stage_id))
+                .filter(
+                    progressreport::columns::prog_report_outcome
+                        .eq(ProgressOutcome::NoConclusion as i16),
+                ),
+        ))
+        .get_result(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to check pending reports")))?;
+        if has_pending {
+            return Err(ApiError::Conflict(str!("A pending report already exists")));
+        }
+
+        let has_passed = diesel::select(diesel::dsl::exists(
+            progressreport::dsl::progressreport
+                .filter(progressreport::columns::student_user_name.eq(&username))
+                .filter(progressreport::columns::topic_id.eq(topic_id))
+                .filter(progressreport::columns::prog_report_stage_id.eq(stage_id))
+                .filter(
+                    progressreport::columns::prog_report_outcome.eq(ProgressOutcome::Passed as i16),
+                ),
+        ))
+        .get_result(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to check passed reports")))?;
+        if has_passed {
+            return Err(ApiError::Conflict(str!("A passed report already exists")));
+        }
+
+        let now = Utc::now();
+        let deadline = load_effective_deadline(conn, &student, Some(stage_id))?;
+        if deadline.is_some_and(|(due_time, is_hard)| is_hard && now > due_time) {
+            return Err(ApiError::Conflict(str!(
+                "The submission deadline for this stage has passed"
+            )));
+        }
+        let due_time = deadline.map(|(due_time, _)| due_time);
+
+        // Earlier rejected submissions are kept, so that teachers can compare versions.
+        let last_version = progressreport::dsl::progressreport
+            .filter(progressreport::columns::student_user_name.eq(&username))
+            .filter(progressreport::columns::topic_id.eq(topic_id))
+            .filter(progressreport::columns::prog_report_stage_id.eq(stage_id))
+            .select(diesel::dsl::max(
+                progressreport::columns::prog_report_version,
+            ))
+            .first::<Option<i32>>(conn)
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to count earlier reports")))?;
+
+        let version = last_version.unwrap_or(0) + 1;
+        let report_id = diesel::insert_into(progressreport::dsl::progressreport)
+            .values(NewProgressReport {
+                topic_id,
+                student_user_name: &username,
+                prog_report_stage_id: stage_id,
+                prog_report_time: now,
+                prog_report_outcome: ProgressOutcome::NoConclusion as i16,
+                prog_report_comment: None,
+                prog_report_grade: None,
+                prog_report_version: version,
+                prog_report_due_time: due_time,
+            })
+            .returning(progressreport::columns::prog_report_id)
+            .get_result::<i32>(conn)
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to create progress report")))?;
+
+        diesel::insert_into(progressreportfile::table)
+            .values(
+                req.files
+                    .iter()
+                    .map(|file| NewProgressReportFile {
+                        prog_report_id: report_id,
+                        attach_id: file.attachment_id,
+                        prog_file_kind: file.kind as i16,
+                    })
+                    .collect::<Vec<_>>(),
+            )
+            .execute(conn)
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to record report files")))?;
+
+        Ok::<_, ApiError>(ProgressReportsPostResponse {
+            prog_report_id: report_id,
+            prog_report_version: version,
+            prog_report_due_time: due_time,
+            prog_report_late: due_time.is_some_and(|due_time| now > due_time),
+        })
+    })?;
+
+    Ok(HttpResponse::Ok().json(result))
+}
+
+#[patch("/progress_reports/{report_id}")]
+pub async fn update_progress_report(
+    pool: web::Data<DbPool>,
+    session: Session,
+    report_id: web::Path<i32>,
+    req: web::Json<ProgressReportRecordPatchRequest>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    // Admins may list progress reports but never review them
+    if is_session_admin(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Can't deserialize auth info")))?
+    {
+        return Err(ApiError::Forbidden);
+    }
+
+    let username = get_session_username(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
+    let user_role = get_session_user_role(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    let result = conn.build_transaction().read_write().run(|conn| {
+        let (report, student_name, teacher_username, stage) = progressreport::table
+            .inner_join(student::table)
+            .inner_join(topic::table)
+            .inner_join(reportstage::table)
+            .filter(progressreport::columns::prog_report_id.eq(*report_id))
+            .select((
+                progressreport::all_columns,
+                student::columns::student_name,
+                topic::columns::teacher_user_name,
+                reportstage::all_columns,
+            ))
+            .first::<(ProgressReport, String, String, ReportStage)>(conn)
+            .map_err(|_| ApiError::NotFound)?;
+
+        if !can_review_progress_report(
+            conn,
+            &report,
+            &stage,
+            &teacher_username,
+            &username,
+            &user_role,
+        )? {
+            return Err(ApiError::Forbidden);
+        }
+        // Concluded outcomes only change through an audited revision
+        if report.prog_report_outcome != ProgressOutcome::NoConclusion as i16 {
+            return Err(ApiError::Conflict(str!(
+                "This report has already been concluded; revise its outcome instead"
+            )));
+        }
+
+        let (grade, scores) = check_rubric_scores(
+            conn,
+            Some(stage.stage_id),
+            req.grade.as_ref(),
+            req.scores.as_deref(),
+        )?;
+        if req.outcome == ProgressOutcome::Passed && stage.stage_grade_required && grade.is_none() {
+            return Err(ApiError::BadRequest(str!(
+                "This stage requires a grade to pass"
+            )));
+        }
+
+        // Enforce per (student, topic, stage): at most one pending + one passed.
+        match req.outcome {
+            ProgressOutcome::NoConclusion => {
+                let other_pending = diesel::select(diesel::dsl::exists(
+                    progressreport::dsl::progressreport
+                        .filter(
+                            progressreport::columns::student_user_name
+                                .eq(&report.student_user_name),
+                        )
+                        .filter(progressreport::columns::topic_id.eq(report.topic_id))
+                        .filter(
+                            progressreport::columns::prog_report_stage_id
+                                .eq(report.prog_report_stage_id),
+                        )
+                        .filter(
+                            progressreport::columns::prog_report_outcome
+                                .eq(ProgressOutcome::NoConclusion as i16),
+                        )
+                        .filter(progressreport::columns::prog_report_id.ne(report.prog_report_id)),
+                ))
+                .get_result::<bool>(conn)
+                .map_err(|_| {
+                    ApiError::InternalServerError(str!("Failed to check pending reports"))
+                })?;
+                if other_pending {
+                    return Err(ApiError::Conflict(str!(
+                        "Another pending report already exists"
+                    )));
+                }
+            }
+            ProgressOutcome::Passed => {
+                let other_passed = diesel::select(diesel::dsl::exists(
+                    progressreport::dsl::progressreport
+                        .filter(
+                            progressreport::columns::student_user_name
+                                .eq(&report.student_user_name),
+                        )
+                        .filter(progressreport::columns::topic_id.eq(report.topic_id))
+                        .filter(
+                            progressreport::columns::prog_report_stage_id
+                                .eq(report.prog_report_stage_id),
+                        )
+                        .filter(
+                            progressreport::columns::prog_report_outcome
+                                .eq(ProgressOutcome::Passed as i16),
+                        )
+                        .filter(progressreport::columns::prog_report_id.ne(report.prog_report_id)),
+                ))
+                .get_result::<bool>(conn)
+                .map_err(|_| {
+                    ApiError::InternalServerError(str!("Failed to check passed reports"))
+                })?;
+                if other_passed {
+                    return Err(ApiError::Conflict(str!(
+                        "Another passed report already exists"
+                    )));
+                }
+            }
+            ProgressOutcome::Rejected => {}
+        }
+
+        diesel::update(progressreport::dsl::progressreport.find(report.prog_report_id))
+            .set((
+                progressreport::columns::prog_report_outcome.eq(req.outcome as i16),
+                progressreport::columns::prog_report_comment.eq(req.comment.clone()),
+                progressreport::columns::prog_report_grade.eq(grade),
+            ))
+            .execute(conn)
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to update progress report")))?;
+        replace_progress_report_scores(conn, report.prog_report_id, scores)?;
+
+        let updated = progressreport::dsl::progressreport
+            .find(report.prog_report_id)
+            .first::<ProgressReport>(conn)
+            .map_err(|_| ApiError::NotFound)?;
+
+        build_progress_report_details(conn, updated, student_name, stage.stage_name)
+    })?;
+
+    Ok(HttpResponse::Ok().json(result))
+}
+
+#[get("/progress_reports/{report_id}/revisions")]
+pub async fn get_progress_report_revisions(
+    pool: web::Data<DbPool>,
+    session: Session,
+    report_id: web::Path<i32>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let is_admin = is_session_admin(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Can't deserialize auth info")))?;
+
+    let username = get_session_username(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
+    let user_role = if is_admin {
+        None
+    } else {
+        Some(get_session_user_role(&session).map_err(|_| {
+            ApiError::InternalServerError(str!("Failed to get user role from session"))
+        })?)
+    };
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    let revisions =
+        conn.build_transaction().read_only().run(|conn| {
+            let report = progressreport::table
+                .find(*report_id)
+                .first::<ProgressReport>(conn)
+                .map_err(|_| ApiError::NotFound)?;
+            // Admins oversee every report
+            let visible = match &user_role {
+                Some(user_role) => can_view_progress_report(conn, &report, &username, user_role)?,
+                None => true,
+            };
+            if !visible {
+                return Err(ApiError::Forbidden);
+            }
+
+            progressreportrevision::table
+                .left_join(teacher::table.on(
+                    teacher::columns::user_name.eq(progressreportrevision::columns::rev_user_name),
+                ))
+                .filter(progressreportrevision::columns::prog_report_id.eq(report.prog_report_id))
+                .order((
+                    progressreportrevision::columns::rev_time.asc(),
+                    progressreportrevision::columns::rev_id.asc(),
+                ))
+                .select((
+                    progressreportrevision::all_columns,
+                    teacher::columns::teacher_name.nullable(),
+                ))
+                .load::<(ProgressReportRevision, Option<String>)>(conn)
+                .map_err(|_| ApiError::InternalServerError(str!("Failed to load revisions")))?
+                .into_iter()
+                .map(|(rev, reviser_name)| {
+                    let outcome = |outcome| {
+                        ProgressOutcome::try_from(outcome).map_err(|_| {
+                            ApiError::InternalServerError(str!("Invalid progress outcome"))
+                        })
+                    };
+                    Ok(ProgressReportRevisionDetails {
+                        revision_id: rev.rev_id,
+                        prog_report_id: rev.prog_report_id,
+                        reviser_user_name: rev.rev_user_name,
+                        reviser_name,
+                        cause_id: rev.rev_cause_id,
+                        reason: rev.rev_reason,
+                        time: rev.rev_time,
+                        old_outcome: outcome(rev.rev_old_outcome)?,
+                        old_comment: rev.rev_old_comment,
+                        old_grade: rev.rev_old_grade,
+                        new_outcome: outcome(rev.rev_new_outcome)?,
+                        new_comment: rev.rev_new_comment,
+                        new_grade: rev.rev_new_grade,
+                    })
+                })
+                .collect::<Result<Vec<_>, ApiError>>()
+        })?;
+
+    Ok(HttpResponse::Ok().json(ProgressReportRevisionsGetResponse { revisions }))
+}
+
+#[post("/progress_reports/{report_id}/revisions")]
+pub async fn create_progress_report_revision(
+    pool: web::Data<DbPool>,
+    session: Session,
+    report_id: web::Path<i32>,
+    req: web::Json<ProgressReportRevisionsPostRequest>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let username = get_session_username(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
+    let user_role = get_session_user_role(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+
+    let reason = req.reason.trim();
+    if reason.is_empty() {
+        return Err(ApiError::BadRequest(str!("A reason is required")));
+    }
+    if req.outcome == ProgressOutcome::NoConclusion {
+        return Err(ApiError::BadRequest(str!(
+            "A revision must either pass or reject the report"
+        )));
+    }
+    let invalidate_dependents = req.invalidate_dependents.unwrap_or(false);
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    let result = conn.build_transaction().read_write().run(|conn| {
+        let (report, student_name, teacher_username, stage) = progressreport::table
+            .inner_join(student::table)
+            .inner_join(topic::table)
+            .inner_join(reportstage::table)
+            .filter(progressreport::columns::prog_report_id.eq(*report_id))
+            .select((
+                progressreport::all_columns,
+                student::columns::student_name,
+                topic::columns::teacher_user_name,
+                reportstage::all_columns,
+            ))
+            .first::<(ProgressReport, String, String, ReportStage)>(conn)
+            .map_err(|_| ApiError::NotFound)?;
+
+        if !can_review_progress_report(
+            conn,
+            &report,
+            &stage,
+            &teacher_username,
+            &username,
+            &user_role,
+        )? {
+            return Err(ApiError::Forbidden);
+        }
+        if report.prog_report_outcome == ProgressOutcome::NoConclusion as i16 {
+            return Err(ApiError::Conflict(str!(
+                "This report has not been reviewed yet"
+            )));
+        }
+        check_revision_lock(conn, &report.student_user_name)?;
+
+        let (grade, scores) = check_rubric_scores(
+            conn,
+            Some(stage.stage_id),
+            req.grade.as_ref(),
+            req.scores.as_deref(),
+        )?;
+        if req.outcome == ProgressOutcome::Passed && stage.stage_grade_required && grade.is_none() {
+            return Err(ApiError::BadRequest(str!(
+                "This stage requires a grade to pass"
+            )));
+        }
+
+        let was_passed = report.prog_report_outcome == ProgressOutcome::Passed as i16;
+        let same_stage = progressreport::table
+            .filter(progressreport::columns::student_user_name.eq(&report.student_user_name))
+            .filter(progressreport::columns::topic_id.eq(report.topic_id))
+            .filter(progressreport::columns::prog_report_stage_id.eq(report.prog_report_stage_id))
+            .filter(progressreport::columns::prog_report_id.ne(report.prog_report_id));
+        if req.outcome == ProgressOutcome::Passed && !was_passed {
+            let other_passed = diesel::select(diesel::dsl::exists(same_stage.filter(
+                progressreport::columns::prog_report_outcome.eq(ProgressOutcome::Passed as i16),
+            )))
+            .get_result::<bool>(conn)
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to check passed reports")))?;
+            if other_passed {
+                return Err(ApiError::Conflict(str!(
+                    "Another passed report already exists"
+                )));
+            }
+        }
+
+        // Rejecting a passed report pulls the ground from under the later stages built on it:
+        // their reports are only rejected along with it when explicitly asked to.
+        let dependents = if was_passed && req.outcome == ProgressOutcome::Rejected {
+            let dependent_stages = load_dependent_stage_ids(conn, stage.stage_id)?;
+            progressreport::table
+                .filter(progressreport::columns::student_user_name.eq(&report.student_user_name))
+                .filter(progressreport::columns::topic_id.eq(report.topic_id))
+                .filter(progressreport::columns::prog_report_stage_id.eq_any(dependent_stages))
+                .filter(
+                    progressreport::columns::prog_report_outcome
+                        .ne(ProgressOutcome::Rejected as i16),
+                )
+                .load::<ProgressReport>(conn)
+                .map_err(|_| {
+                    ApiError::InternalServerError(str!("Failed to load progress reports"))
+                })?
+        } else {
+            Vec::new()
+        };
+        if !dependents.is_empty() && !invalidate_dependents {
+            return Err(ApiError::Conflict(format!(
+                "{} report(s) of later stages rely on this report passing; \
+                 set invalidate_dependents to reject them as well",
+                dependents.len()
+            )));
+        }
+
+        // A pending or passed final defense only holds on to the last stages of the pipeline, so
+        // earlier stages stay revisable as long as no passed report of a last stage goes with them
+        if was_passed && req.outcome == ProgressOutcome::Rejected {
+            let defense_stages = load_defense_stage_ids(conn)?;
+            let touches_defense = defense_stages.contains(&stage.stage_id)
+                || dependents.iter().any(|dependent| {
+                    dependent.prog_report_outcome == ProgressOutcome::Passed as i16
+                        && defense_stages.contains(&dependent.prog_report_stage_id)
+                });
+            if touches_defense {
+                let relying_defense = diesel::select(diesel::dsl::exists(
+                    finaldefense::table
+                        .filter(
+                            finaldefense::columns::student_user_name.eq(&report.student_user_name),
+                        )
+                        .filter(finaldefense::columns::topic_id.eq(report.topic_id))
+                        .filter(
+                            finaldefense::columns::final_def_outcome
+                                .is_null()
+                                .or(finaldefense::columns::final_def_outcome.eq(true)),
+                        ),
+                ))
+                .get_result::<bool>(conn)
+                .map_err(|_| {
+                    ApiError::InternalServerError(str!("Failed to check final defenses"))
+                })?;
+                if relying_defense {
+                    return Err(ApiError::Conflict(str!(
+                        "A final defense already relies on this report passing"
+                    )));
+                }
+            }
+        }
+
+        // Passing a rejected report settles the stage, so resubmissions still awaiting review are
+        // closed along with it
+        let superseded = if req.outcome == ProgressOutcome::Passed && !was_passed {
+            progressreport::table
+                .filter(progressreport::columns::student_user_name.eq(&report.student_user_name))
+                .filter(progressreport::columns::topic_id.eq(report.topic_id))
+                .filter(
+                    progressreport::columns::prog_report_stage_id.eq(report.prog_report_stage_id),
+                )
+                .filter(progressreport::columns::prog_report_id.ne(report.prog_report_id))
+                .filter(
+                    progressreport::columns::prog_report_outcome
+                        .eq(ProgressOutcome::NoConclusion as i16),
+                )
+                .load::<ProgressReport>(conn)
+                .map_err(|_| {
+                    ApiError::InternalServerError(str!("Failed to load progress reports"))
+                })?
+        } else {
+            Vec::new()
+        };
+
+        let now = Utc::now();
+        let comment = req.comment.clone().or(report.prog_report_comment.clone());
+        let revision = diesel::insert_into(progressreportrevision::table)
+            .values(NewProgressReportRevision {
+                prog_report_id: report.prog_report_id,
+                rev_user_name: &username,
+                rev_cause_id: None,
+                rev_reason: reason,
+                rev_time: now,
+                rev_old_outcome: report.prog_report_outcome,
+                rev_old_comment: report.prog_report_comment.clone(),
+                rev_old_grade: report.prog_report_grade.clone(),
+                rev_new_outcome: req.outcome as i16,
+                rev_new_comment: comment.clone(),
+                rev_new_grade: grade.clone(),
+            })
+            .get_result::<ProgressReportRevision>(conn)
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to record revision")))?;
+        diesel::update(progressreport::table.find(report.prog_report_id))
+            .set((
+                progressreport::columns::prog_report_outcome.eq(req.outcome as i16),
+                progressreport::columns::prog_report_comment.eq(comment),
+                progressreport::columns::prog_report_grade.eq(grade),
+            ))
+            .execute(conn)
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to update progress report")))?;
+        replace_progress_report_scores(conn, report.prog_report_id, scores)?;
+
+        for dependent in dependents.iter().chain(&superseded) {
+            diesel::insert_into(progressreportrevision::table)
+                .values(NewProgressReportRevision {
+                    prog_report_id: dependent.prog_report_id,
+                    rev_user_name: &username,
+                    rev_cause_id: Some(revision.rev_id),
+                    rev_reason: reason,
+                    rev_time: now,
+                    rev_old_outcome: dependent.prog_report_outcome,
+                    rev_old_comment: dependent.prog_report_comment.clone(),
+                    rev_old_grade: dependent.prog_report_grade.clone(),
+                    rev_new_outcome: ProgressOutcome::Rejected as i16,
+                    rev_new_comment: dependent.prog_report_comment.clone(),
+                    rev_new_grade: dependent.prog_report_grade.clone(),
+                })
+                .execute(conn)
+                .map_err(|_| ApiError::InternalServerError(str!("Failed to record revision")))?;
+        }
+        diesel::update(
+            progressreport::table.filter(
+                progressreport::columns::prog_report_id.eq_any(
+                    dependents
+                        .iter()
+                        .chain(&superseded)
+                        .map(|r| r.prog_report_id)
+                        .collect::<Vec<_>>(),
+                ),
+            ),
+        )
+        .set(progressreport::columns::prog_report_outcome.eq(ProgressOutcome::Rejected as i16))
+        .execute(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to reject dependent reports")))?;
+
+        let mut notif_content = format!(
+            "The outcome of your {} (report #{}) was revised to {}. Reason: {}",
+            stage.stage_name,
+            report.prog_report_id,
+            if req.outcome == ProgressOutcome::Passed {
+                "passed"
+            } else {
+                "rejected"
+            },
+            reason
+        );
+        if !dependents.is_empty() {
+            notif_content.push_str(&format!(
+                " {} report(s) of later stages were rejected as a result.",
+                dependents.len()
+            ));
+        }
+        if !superseded.is_empty() {
+            notif_content.push_str(&format!(
+                " {} pending resubmission(s) of this stage were closed.",
+                superseded.len()
+            ));
+        }
+        diesel::insert_into(notification::table)
+            .values(NewNotification {
+                user_name: &report.student_user_name,
+                notif_content,
+                notif_time: now,
+            })
+            .execute(conn)
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to send notification")))?;
+
+        let updated = progressreport::table
+            .find(report.prog_report_id)
+            .first::<ProgressReport>(conn)
+            .map_err(|_| ApiError::NotFound)?;
+
+        build_progress_report_details(conn, updated, student_name, stage.stage_name)
+    })?;
+
+    Ok(HttpResponse::Created().json(result))
+}
+
+#[get("/final_defenses")]
+pub async fn get_final_defenses(
+    pool: web::Data<DbPool>,
+    session: Session,
+    query: web::Query<FinalDefensesQuery>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    // Admins oversee progress reports only
+    if is_session_admin(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Can't deserialize auth info")))?
+    {
+        return Err(ApiError::Forbidden);
+    }
+
+    let username = get_session_username(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
+    let user_role = get_session_user_role(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+
+    match user_role {
+        AuthInfoUserRole::Student | AuthInfoUserRole::Teacher | AuthInfoUserRole::DefenseBoard => {}
+        _ => return Err(ApiError::Forbidden),
+    }
+
+    let page = query.page.unwrap_or(1);
+    let page_size = query.page_size.unwrap_or(20);
+    if page < 1 || !(1..=100).contains(&page_size) {
+        return Err(ApiError::BadRequest(str!(
+            "Page must be positive and page size between 1 and 100"
+        )));
+    }
+    let offset = (page - 1) * page_size;
+    let descending = is_descending_order(query.order.as_deref())?;
+    let student_pattern = search_pattern(query.student.as_deref());
+
+    let visible = || {
+        let mut q = finaldefense::table
+            .inner_join(student::table)
+            .inner_join(topic::table)
+            .into_boxed();
+        match user_role {
+            AuthInfoUserRole::Student => {
+                q = q.filter(finaldefense::columns::student_user_name.eq(&username));
+            }
+            AuthInfoUserRole::Teacher => {
+                q = q.filter(
+                    topic::columns::teacher_user_name
+                        .eq(&username)
+                        .or(topic::columns::topic_id.eq_any(cosupervised_topic_ids(&username))),
+                );
+            }
+            AuthInfoUserRole::DefenseBoard => {
+                q = q.filter(finaldefense::columns::def_board_user_name.eq(&username));
+            }
+            _ => unreachable!(),
+        }
+        if let Some(outcome) = query.outcome {
+            q = q.filter(finaldefense::columns::final_def_outcome.eq(outcome));
+        }
+        match query.pending {
+            Some(true) => q = q.filter(finaldefense::columns::final_def_outcome.is_null()),
+            Some(false) => q = q.filter(finaldefense::columns::final_def_outcome.is_not_null()),
+            None => {}
+        }
+        if let Some(pattern) = &student_pattern {
+            q = q.filter(
+                student::columns::student_name
+                    .ilike(pattern)
+                    .or(finaldefense::columns::student_user_name.ilike(pattern)),
+            );
+        }
+        q
+    };
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    let total: i64 = visible()
+        .count()
+        .get_result(&mut conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to count final defenses")))?;
+    if total == 0 {
+        return Err(ApiError::NotFound);
+    }
+
+    let ordered = if descending {
+        visible().order((
+            finaldefense::columns::final_def_time.desc(),
+            finaldefense::columns::final_def_id.desc(),
+        ))
+    } else {
+        visible().order((
+            finaldefense::columns::final_def_time.asc(),
+            finaldefense::columns::final_def_id.asc(),
+        ))
+    };
+    let rows = ordered
+        .offset(offset)
+        .limit(page_size)
+        .select((
+            finaldefense::all_columns,
+            student::columns::student_name,
+            topic::columns::topic_name,
+        ))
+        .load::<(FinalDefense, String, String)>(&mut conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load final defenses")))?;
+
+    let defense_ids = rows
+        .iter()
+        .map(|(d, _, _)| d.final_def_id)
+        .collect::<Vec<_>>();
+    let mut files_by_defense = load_final_defense_files(&mut conn, &defense_ids)?;
+    let mut scores_by_defense = load_final_defense_scores(&mut conn, &defense_ids)?;
+
+    let defenses = rows
+        .into_iter()
+        .map(|(d, student_name, topic_name)| FinalDefenseDetails {
+            final_def_id: d.final_def_id,
+            topic_id: d.topic_id,
+            topic_name,
+            student_user_name: d.student_user_name,
+            student_name,
+            def_board_user_name: d.def_board_user_name,
+            final_def_time: d.final_def_time,
+            final_def_version: d.final_def_version,
+            final_def_files: files_by_defense.remove(&d.final_def_id).unwrap_or_default(),
+            final_def_due_time: d.final_def_due_time,
+            final_def_late: d
+                .final_def_due_time
+                .is_some_and(|due_time| d.final_def_time > due_time),
+            final_def_outcome: d.final_def_outcome,
+            final_def_comment: d.final_def_comment,
+            final_def_grade: d.final_def_grade,
+            final_def_scores: scores_by_defense
+                .remove(&d.final_def_id)
+                .unwrap_or_default(),
+        })
+        .collect::<Vec<_>>();
+
+    Ok(HttpResponse::Ok().json(FinalDefensesGetResponse {
+        total,
+        page,
+        page_size,
+        defenses,
+    }))
+}
+
+#[post("/final_defenses")]
+pub async fn create_final_defense(
+    pool: web::Data<DbPool>,
+    session: Session,
+    req: web::Json<FinalDefensesPostRequest>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let username = get_session_username(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    let result = conn.build_transaction().read_write().run(|conn| {
+        let student = student::dsl::student
+            .find(&username)
+            .first::<Student>(conn)
+            .map_err(|_| ApiError::NotFound)?;
+        let topic_id = student
+            .topic_id
+            .ok_or_else(|| ApiError::Conflict(str!("Student has no assigned topic")))?;
+        check_submission_files(conn, &req.files, &username)?;
+
+        // Enforce: per student on the current topic, at most one pending (NULL) and one passed (true).
+        let has_pending = diesel::select(diesel::dsl::exists(
+            finaldefense::dsl::finaldefense
+                .filter(finaldefense::columns::student_user_name.eq(&username))
+                .filter(finaldefense::columns::topic_id.eq(topic_id))
+                .filter(finaldefense::columns::final_def_outcome.is_null()),
+        ))
+        .get_result::<bool>(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to check pending defenses")))?;
+        if has_pending {
+            return Err(ApiError::Conflict(str!(
+                "A pending final defense already exists"
+            )));
+        }
+
+        let has_passed = diesel::select(diesel::dsl::exists(
+            finaldefense::dsl::finaldefense
+                .filter(finaldefense::columns::student_user_name.eq(&username))
+                .filter(finaldefense::columns::topic_id.eq(topic_id))
+                .filter(finaldefense::columns::final_def_outcome.eq(true)),
+        ))
+        .get_result::<bool>(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to check passed defenses")))?;
+        if has_passed {
+            return Err(ApiError::Conflict(str!(
+                "A passed final defense already exists"
+            )));
+        }
+
+        let now = Utc::now();
+        let deadline = load_effective_deadline(conn, &student, None)?;
+        if deadline.is_some_and(|(due_time, is_hard)| is_hard && now > due_time) {
+            return Err(ApiError::Conflict(str!(
+                "The final defense submission deadline has passed"
+            )));
+        }
+        let due_time = deadline.map(|(due_time, _)| due_time);
+
+        let last_version = finaldefense::dsl::finaldefense
+            .filter(finaldefense::columns::student_user_name.eq(&username))
+            .filter(finaldefense::columns::topic_id.eq(topic_id))
+            .select(diesel::dsl::max(finaldefense::columns::final_def_version))
+            .first::<Option<i32>>(conn)
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to count earlier defenses")))?;
+
+        let version = last_version.unwrap_or(0) + 1;
+        let defense_id = diesel::insert_into(finaldefense::dsl::finaldefense)
+            .values(NewFinalDefense {
+                topic_id,
+                student_user_name: &username,
+                def_board_user_name: None,
+                final_def_time: now,
+                final_def_outcome: None,
+                final_def_comment: None,
+                final_def_grade: None,
+                final_def_version: version,
+                final_def_due_time: due_time,
+            })
+            .returning(finaldefense::columns::final_def_id)
+            .get_result::<i32>(conn)
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to create final defense")))?;
+
+        diesel::insert_into(finaldefensefile::table)
+            .values(
+                req.files
+                    .iter()
+                    .map(|file| NewFinalDefenseFile {
+                        final_def_id: defense_id,
+                        attach_id: file.attachment_id,
+                        def_file_kind: file.kind as i16,
+                    })
+                    .collect::<Vec<_>>(),
+            )
+            .execute(conn)
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to record defense files")))?;
+
+        Ok::<_, ApiError>(FinalDefensesPostResponse {
+            final_def_id: defense_id,
+            final_def_version: version,
+            final_def_due_time: due_time,
+            final_def_late: due_time.is_some_and(|due_time| now > due_time),
+        })
+    })?;
+
+    Ok(HttpResponse::Ok().json(result))
+}
+
+#[patch("/final_defenses/{report_id}")]
+pub async fn update_final_defense(
+    pool: web::Data<DbPool>,
+    session: Session,
+    report_id: web::Path<i32>,
+    req: web::Json<FinalDefensesRecordPatchRequest>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let username = get_session_username(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
+    let user_role = get_session_user_role(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    let result = conn.build_transaction().read_write().run(|conn| {
+        match (user_role, req.into_inner()) {
+            (AuthInfoUserRole::Teacher, FinalDefensesRecordPatchRequest::Teacher(req)) => {
+                // Teacher reviews the request: reject -> mark not passed; approve -> assign least-loaded defense group.
+                let (defense, student_name, topic_name, teacher_username): (
+                    FinalDefense,
+                    String,
+                    String,
+                    String,
+                ) = finaldefense::table
+                    .inner_join(student::table)
+                    .inner_join(topic::table)
+                    .filter(finaldefense::columns::final_def_id.eq(*report_id))
+                    .select((
+                        finaldefense::all_columns,
+                        student::columns::student_name,
+                        topic::columns::topic_name,
+                        topic::columns::teacher_user_name,
+                    ))
+                    .first(conn)
+                    .map_err(|_| ApiError::NotFound)?;
+
+                if !can_approve_for_topic(conn, defense.topic_id, &teacher_username, &username)? {
+                    return Err(ApiError::Forbidden);
+                }
+
+                // Only allow reviewing pending applications.
+                if defense.final_def_outcome.is_some() {
+                    return Err(ApiError::Conflict(str!("Final defense already finalized")));
+                }
+
+                if !req.approved {
+                    // Keep the rejected application, without a board, so that its files can be
+                    // compared with the student's next submission.
+                    diesel::update(finaldefense::dsl::finaldefense.find(defense.final_def_id))
+                        .set(finaldefense::columns::final_def_outcome.eq(Some(false)))
+                        .execute(conn)
+                        .map_err(|_| {
+                            ApiError::InternalServerError(str!("Failed to reject final defense"))
+                        })?;
+
+                    return Ok(FinalDefenseDetails {
+                        final_def_id: defense.final_def_id,
+                        topic_id: defense.topic_id,
+                        topic_name,
+                        student_user_name: defense.student_user_name,
+                        student_name,
+                        def_board_user_name: defense.def_board_user_name,
+                        final_def_time: defense.final_def_time,
+                        final_def_version: defense.final_def_version,
+                        final_def_files: load_final_defense_files(conn, &[defense.final_def_id])?
+                            .remove(&defense.final_def_id)
+                            .unwrap_or_default(),
+                        final_def_due_time: defense.final_def_due_time,
+                        final_def_late: defense
+                            .final_def_due_time
+                            .is_some_and(|due_time| defense.final_def_time > due_time),
+                        final_def_outcome: Some(false),
+                        final_def_comment: defense.final_def_comment,
+                        final_def_grade: defense.final_def_grade,
+                        final_def_scores: load_final_defense_scores(conn, &[defense.final_def_id])?
+                            .remove(&defense.final_def_id)
+                            .unwrap_or_default(),
+                    });
+                }
+
+                if defense.def_board_user_name.is_some() {
+                    return Err(ApiError::Conflict(str!("Final defense already assigned")));
+                }
+
+                // Choose the defense group with the least number of pending tasks.
+                // (Pending task = assigned to a defense group AND final outcome is NULL.)
+                // We compute counts via GROUP BY, then pick the minimum in Rust to keep the Diesel types simple.
+
+                let defense_board_usernames = defenseboard::dsl::defenseboard
+                    .select(defenseboard::columns::user_name)
+                    .order(defenseboard::columns::user_name.asc())
+                    .load::<String>(conn)
+                    .map_err(|_| {
+                        ApiError::InternalServerError(str!("Failed to load defense boards"))
+                    })?;
+                if defense_board_usernames.is_empty() {
+                    return Err(ApiError::Conflict(str!("No defense board available")));
+                }
+
+                let pending_counts = finaldefense::dsl::finaldefense
+                    .filter(finaldefense::columns::final_def_outcome.is_null())
+                    .filter(finaldefense::columns::def_board_user_name.is_not_null())
+                    .group_by(finaldefense::columns::def_board_user_name)
+                    .select((
+                        finaldefense::columns::def_board_user_name,
+                        diesel::dsl::count_star(),
+                    ))
+                    .load::<(Option<String>, i64)>(conn)
+                    .map_err(|_| {
+                        ApiError::InternalServerError(str!("Failed to count defense board tasks"))
+                    })?;
+
+                let mut count_by_board: HashMap<String, i64> = HashMap::new();
+                for (board_username, c) in pending_counts {
+                    if let Some(board_username) = board_username {
+                        count_by_board.insert(board_username, c);
+                    }
+                }
+
+                let mut assigned_defense_board_username = defense_board_usernames[0].clone();
+                let mut assigned_count = *count_by_board
+                    .get(&assigned_defense_board_username)
+                    .unwrap_or(&0);
+                for board_username in defense_board_usernames.into_iter().skip(1) {
+                    let c = *count_by_board.get(&board_username).unwrap_or(&0);
+                    if c < assigned_count {
+                        assigned_defense_board_username = board_username;
+                        assigned_count = c;
+                    }
+                }
+
+                diesel::update(finaldefense::dsl::finaldefense.find(defense.final_def_id))
+                    .set(
+                        finaldefense::columns::def_board_user_name
+                            .eq(Some(assigned_defense_board_username)),
+                    )
+                    .execute(conn)
+                    .map_err(|_| {
+                        ApiError::InternalServerError(str!("Failed to assign defense board"))
+                    })?;
+
+                let updated = finaldefense::dsl::finaldefense
+                    .find(defense.final_def_id)
+                    .first::<FinalDefense>(conn)
+                    .map_err(|_| ApiError::NotFound)?;
+
+                Ok(FinalDefenseDetails {
+                    final_def_id: updated.final_def_id,
+                    topic_id: updated.topic_id,
+                    topic_name,
+                    student_user_name: updated.student_user_name,
+                    student_name,
+                    def_board_user_name: updated.def_board_user_name,
+                    final_def_time: updated.final_def_time,
+                    final_def_version: updated.final_def_version,
+                    final_def_files: load_final_defense_files(conn, &[updated.final_def_id])?
+                        .remove(&updated.final_def_id)
+                        .unwrap_or_default(),
+                    final_def_due_time: updated.final_def_due_time,
+                    final_def_late: updated
+                        .final_def_due_time
+                        .is_some_and(|due_time| updated.final_def_time > due_time),
+                    final_def_outcome: updated.final_def_outcome,
+                    final_def_comment: updated.final_def_comment,
+                    final_def_grade: updated.final_def_grade,
+                    final_def_scores: load_final_defense_scores(conn, &[updated.final_def_id])?
+                        .remove(&updated.final_def_id)
+                        .unwrap_or_default(),
+                })
+            }
+            (
+                AuthInfoUserRole::DefenseBoard,
+                FinalDefensesRecordPatchRequest::DefenseBoard(req),
+            ) => {
+                let (defense, student_name, topic_name): (FinalDefense, String, String) =
+                    finaldefense::table
+                        .inner_join(student::table)
+                        .inner_join(topic::table)
+                        .filter(finaldefense::columns::final_def_id.eq(*report_id))
+                        .select((
+                            finaldefense::all_columns,
+                            student::columns::student_name,
+                            topic::columns::topic_name,
+                        ))
+                        .first(conn)
+                        .map_err(|_| ApiError::NotFound)?;
+
+                if defense.def_board_user_name != Some(username) {
+                    return Err(ApiError::Forbidden);
+                }
+
+                let (grade, scores) =
+                    check_rubric_scores(conn, None, req.grade.as_ref(), req.scores.as_deref())?;
+                let Some(grade) = grade else {
+                    return Err(ApiError::BadRequest(str!("A grade is required")));
+                };
+
+                // Enforce: per student on the topic, at most one passed (true).
+                if req.outcome {
+                    let other_passed = diesel::select(diesel::dsl::exists(
+                        finaldefense::dsl::finaldefense
+                            .filter(
+                                finaldefense::columns::student_user_name
+                                    .eq(&defense.student_user_name),
+                            )
+                            .filter(finaldefense::columns::topic_id.eq(defense.topic_id))
+                            .filter(finaldefense::columns::final_def_outcome.eq(true))
+                            .filter(finaldefense::columns::final_def_id.ne(defense.final_def_id)),
+                    ))
+                    .get_result::<bool>(conn)
+                    .map_err(|_| {
+                        ApiError::InternalServerError(str!("Failed to check passed defenses"))
+                    })?;
+                    if other_passed {
+                        return Err(ApiError::Conflict(str!(
+                            "Another passed final defense already exists"
+                        )));
+                    }
+                }
+
+                diesel::update(finaldefense::dsl::finaldefense.find(defense.final_def_id))
+                    .set((
+                        finaldefense::columns::final_def_outcome.eq(Some(req.outcome)),
+                        finaldefense::columns::final_def_comment.eq(Some(req.comment.clone())),
+                        finaldefense::columns::final_def_grade.eq(Some(grade)),
+                    ))
+                    .execute(conn)
+                    .map_err(|_| {
+                        ApiError::InternalServerError(str!("Failed to update final defense"))
+                    })?;
+
+                diesel::delete(
+                    finaldefensescore::table
+                        .filter(finaldefensescore::columns::final_def_id.eq(defense.final_def_id)),
+                )
+                .execute(conn)
+                .map_err(|_| {
+                    ApiError::InternalServerError(str!("Failed to clear defense scores"))
+                })?;
+                diesel::insert_into(finaldefensescore::table)
+                    .values(
+                        scores
+                            .into_iter()
+                            .map(|(crit_id, score)| NewFinalDefenseScore {
+                                final_def_id: defense.final_def_id,
+                                crit_id,
+                                def_score: score,
+                            })
+                            .collect::<Vec<_>>(),
+                    )
+                    .execute(conn)
+                    .map_err(|_| {
+                        ApiError::InternalServerError(str!("Failed to record defense scores"))
+                    })?;
+
+                let updated = finaldefense::dsl::finaldefense
+                    .find(defense.final_def_id)
+                    .first::<FinalDefense>(conn)
+                    .map_err(|_| ApiError::NotFound)?;
+
+                Ok(FinalDefenseDetails {
+                    final_def_id: updated.final_def_id,
+                    topic_id: updated.topic_id,
+                    topic_name,
+                    student_user_name: updated.student_user_name,
+                    student_name,
+                    def_board_user_name: updated.def_board_user_name,
+                    final_def_time: updated.final_def_time,
+                    final_def_version: updated.final_def_version,
+                    final_def_files: load_final_defense_files(conn, &[updated.final_def_id])?
+                        .remove(&updated.final_def_id)
+                        .unwrap_or_default(),
+                    final_def_due_time: updated.final_def_due_time,
+                    final_def_late: updated
+                        .final_def_due_time
+                        .is_some_and(|due_time| updated.final_def_time > due_time),
+                    final_def_outcome: updated.final_def_outcome,
+                    final_def_comment: updated.final_def_comment,
+                    final_def_grade: updated.final_def_grade,
+                    final_def_scores: load_final_defense_scores(conn, &[updated.final_def_id])?
+                        .remove(&updated.final_def_id)
+                        .unwrap_or_default(),
+                })
+            }
+            (AuthInfoUserRole::Teacher, _) => Err(ApiError::BadRequest(str!(
+                "Teacher patch request must be { approved: bool }"
+            ))),
+            (AuthInfoUserRole::DefenseBoard, _) => Err(ApiError::BadRequest(str!(
+                "Defense board patch request must be { outcome, comment, grade | scores }"
+            ))),
+            _ => Err(ApiError::Forbidden),
+        }
+    })?;
+
+    Ok(HttpResponse::Ok().json(result))
 }
 
-#[get("/topics/{topic_id}")]
-pub async fn get_topic_detail(
+#[get("/progress_reports/{report_id}/comments")]
+pub async fn get_progress_report_comments(
     pool: web::Data<DbPool>,
     session: Session,
-    topic_id: web::Path<i32>,
+    report_id: web::Path<i32>,
 ) -> Result<HttpResponse, ApiError> {
     use backend_database::schema::*;
 
@@ -644,86 +7499,48 @@ pub async fn get_topic_detail(
         return Err(ApiError::Unauthorized);
     }
 
+    let is_admin = is_session_admin(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Can't deserialize auth info")))?;
+
     let username = get_session_username(&session)
         .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
-    let user_role = get_session_user_role(&session)
-        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
-
+    let user_role = if is_admin {
+        None
+    } else {
+        Some(get_session_user_role(&session).map_err(|_| {
+            ApiError::InternalServerError(str!("Failed to get user role from session"))
+        })?)
+    };
     let mut conn = pool
         .get()
         .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
 
-    // Why boxed query here? Since we don't need it to be Copy, unlike the previous (count, records) pattern.
-    let mut query_builder = topic::table
-        .inner_join(teacher::table)
-        .inner_join(major::table)
-        .filter(topic::columns::topic_id.eq(*topic_id))
-        .into_boxed();
-
-    match user_role {
-        AuthInfoUserRole::Student => {
-            // Student: Get topics approved for their major
-            let student_info = student::dsl::student
-                .find(&username)
-                .first::<Student>(&mut conn)
-                .map_err(|_| {
-                    ApiError::InternalServerError(str!("Failed to get student information"))
-                })?;
-
-            query_builder = query_builder
-                .filter(topic::columns::major_id.eq(student_info.major_id))
-                .filter(topic::columns::topic_review_status.eq(TopicReviewStatus::Approved as i16));
-        }
-        AuthInfoUserRole::Teacher => {
-            // Teacher: Get topics created by themselves
-            query_builder = query_builder.filter(topic::columns::teacher_user_name.eq(&username));
-        }
-        AuthInfoUserRole::Office | AuthInfoUserRole::DefenseBoard => {
-            // Office and Defense Board: Get all topics without additional filtering
+    let comments = conn.build_transaction().read_only().run(|conn| {
+        let report = progressreport::table
+            .find(*report_id)
+            .first::<ProgressReport>(conn)
+            .map_err(|_| ApiError::NotFound)?;
+        // Admins oversee every report
+        let visible = match &user_role {
+            Some(user_role) => can_view_progress_report(conn, &report, &username, user_role)?,
+            None => true,
+        };
+        if !visible {
+            return Err(ApiError::Forbidden);
         }
-    }
-
-    let (topic, teacher, major): (Topic, Teacher, Major) =
-        query_builder.first(&mut conn).map_err(|e| {
-            if let diesel::result::Error::NotFound = e {
-                ApiError::NotFound
-            } else {
-                ApiError::InternalServerError(str!("Failed to load topic details"))
-            }
-        })?;
 
-    let current_student_count: i64 = student::dsl::student
-        .filter(student::columns::topic_id.eq(topic.topic_id))
-        .count()
-        .get_result(&mut conn)
-        .map_err(|_| ApiError::InternalServerError(str!("Failed to count students for topic")))?;
-
-    // Build response
-    let topic_details = TopicDetails {
-        topic_id: topic.topic_id,
-        major_id: topic.major_id,
-        major_name: major.major_name,
-        teacher_user_name: topic.teacher_user_name,
-        teacher_name: teacher.teacher_name,
-        topic_name: topic.topic_name,
-        topic_description: topic.topic_description,
-        topic_max_students: topic.topic_max_students,
-        topic_type: TopicType::try_from(topic.topic_type)
-            .map_err(|_| ApiError::InternalServerError(str!("Invalid topic type")))?,
-        topic_review_status: TopicReviewStatus::try_from(topic.topic_review_status)
-            .map_err(|_| ApiError::InternalServerError(str!("Invalid topic review status")))?,
-        current_student_count: current_student_count as i32,
-    };
+        load_comment_details(conn, CommentSubject::ProgressReport(report.prog_report_id))
+    })?;
 
-    Ok(HttpResponse::Ok().json(topic_details))
+    Ok(HttpResponse::Ok().json(CommentsGetResponse { comments }))
 }
 
-#[patch("/topics/{topic_id}")]
-pub async fn update_topic(
+#[post("/progress_reports/{report_id}/comments")]
+pub async fn create_progress_report_comment(
     pool: web::Data<DbPool>,
     session: Session,
-    topic_id: web::Path<i32>,
-    req: web::Json<TopicPatchRequest>,
+    report_id: web::Path<i32>,
+    req: web::Json<CommentsPostRequest>,
 ) -> Result<HttpResponse, ApiError> {
     use backend_database::schema::*;
 
@@ -731,177 +7548,160 @@ pub async fn update_topic(
         return Err(ApiError::Unauthorized);
     }
 
+    // Admins may read discussions but not take part in them
+    if is_session_admin(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Can't deserialize auth info")))?
+    {
+        return Err(ApiError::Forbidden);
+    }
+
     let username = get_session_username(&session)
         .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
     let user_role = get_session_user_role(&session)
         .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
-
     let mut conn = pool
         .get()
         .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
 
-    let result = conn.build_transaction().read_write().run(|conn| {
-        let topic = topic::dsl::topic
-            .find(*topic_id)
-            .first::<Topic>(conn)
-            .map_err(|e| {
-                if let diesel::result::Error::NotFound = e {
-                    ApiError::NotFound
-                } else {
-                    ApiError::InternalServerError(str!("Failed to load topic"))
-                }
-            })?;
+    let comment = conn.build_transaction().read_write().run(|conn| {
+        let (report, teacher_username) = progressreport::table
+            .inner_join(topic::table)
+            .filter(progressreport::columns::prog_report_id.eq(*report_id))
+            .select((
+                progressreport::all_columns,
+                topic::columns::teacher_user_name,
+            ))
+            .first::<(ProgressReport, String)>(conn)
+            .map_err(|_| ApiError::NotFound)?;
+        if !can_view_progress_report(conn, &report, &username, &user_role)? {
+            return Err(ApiError::Forbidden);
+        }
 
-        let topic = match user_role {
-            AuthInfoUserRole::Teacher => {
-                // Is a teacher!
-                if topic.teacher_user_name != username {
-                    // Teacher can only update topics they created
-                    return Err(ApiError::Forbidden);
-                }
+        create_comment(
+            conn,
+            CommentSubject::ProgressReport(report.prog_report_id),
+            &req,
+            &username,
+            &user_role,
+            &[&report.student_user_name, &teacher_username],
+            format!(
+                "New comment on progress report #{} (version {})",
+                report.prog_report_id, report.prog_report_version
+            ),
+        )
+    })?;
 
-                if req.topic_review_status.is_some() {
-                    // Teacher can not update review status
-                    return Err(ApiError::BadRequest(str!(
-                        "Teachers cannot update review status"
-                    )));
-                }
+    Ok(HttpResponse::Created().json(comment))
+}
 
-                // Check if any student has requested or is accepted for this topic
-                let has_requests = diesel::select(diesel::dsl::exists(
-                    assignmentrequest::table
-                        .filter(assignmentrequest::columns::topic_id.eq(topic.topic_id)),
-                ))
-                .get_result(conn)
-                .map_err(|e| {
-                    ApiError::InternalServerError(format!(
-                        "Failed to check for assignment requests: {}",
-                        e
-                    ))
-                })?;
+#[get("/final_defenses/{report_id}/comments")]
+pub async fn get_final_defense_comments(
+    pool: web::Data<DbPool>,
+    session: Session,
+    report_id: web::Path<i32>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
 
-                let has_accepted = diesel::select(diesel::dsl::exists(
-                    student::table.filter(student::columns::topic_id.eq(topic.topic_id)),
-                ))
-                .get_result(conn)
-                .map_err(|e| {
-                    ApiError::InternalServerError(format!(
-                        "Failed to check for accepted students: {}",
-                        e
-                    ))
-                })?;
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    // Admins neither take part in discussions nor oversee final defenses
+    if is_session_admin(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Can't deserialize auth info")))?
+    {
+        return Err(ApiError::Forbidden);
+    }
+
+    let username = get_session_username(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
+    let user_role = get_session_user_role(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
 
-                if has_requests || has_accepted {
-                    return Err(ApiError::Conflict(str!(
-                        "Topic cannot be updated once assignment requests or acceptances exist"
-                    )));
-                }
+    let comments = conn.build_transaction().read_only().run(|conn| {
+        let defense = finaldefense::table
+            .find(*report_id)
+            .first::<FinalDefense>(conn)
+            .map_err(|_| ApiError::NotFound)?;
+        if !can_view_final_defense(conn, &defense, &username, &user_role)? {
+            return Err(ApiError::Forbidden);
+        }
 
-                let changeset = TopicChangeset {
-                    topic_name: req.topic_name.clone(),
-                    topic_description: req.topic_description.clone(),
-                    topic_max_students: req.topic_max_students,
-                    topic_type: req.topic_type.map(|t| t as i16),
-                    // topic_review_status is not settable by teacher, but will be reset to Pending on any update
-                    topic_review_status: Some(TopicReviewStatus::Pending as i16),
-                };
+        load_comment_details(conn, CommentSubject::FinalDefense(defense.final_def_id))
+    })?;
 
-                diesel::update(&topic)
-                    .set(changeset)
-                    .get_result::<Topic>(conn)
-                    .map_err(|e| {
-                        ApiError::InternalServerError(format!("Failed to update topic: {}", e))
-                    })?
-            }
-            AuthInfoUserRole::Office => {
-                // Is an office user!
-                // Office can only update review status
-                if req.topic_name.is_some()
-                    || req.topic_description.is_some()
-                    || req.topic_max_students.is_some()
-                    || req.topic_type.is_some()
-                {
-                    return Err(ApiError::BadRequest(str!(
-                        "Office can only update review status"
-                    )));
-                }
+    Ok(HttpResponse::Ok().json(CommentsGetResponse { comments }))
+}
 
-                if let Some(topic_review_status) = req.topic_review_status {
-                    if topic_review_status == TopicReviewStatus::Pending {
-                        return Err(ApiError::BadRequest(str!(
-                            "Cannot explicitly set review status to Pending"
-                        )));
-                    }
+#[post("/final_defenses/{report_id}/comments")]
+pub async fn create_final_defense_comment(
+    pool: web::Data<DbPool>,
+    session: Session,
+    report_id: web::Path<i32>,
+    req: web::Json<CommentsPostRequest>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
 
-                    diesel::update(&topic)
-                        .set(topic::columns::topic_review_status.eq(topic_review_status as i16))
-                        .get_result::<Topic>(conn)
-                        .map_err(|e| {
-                            ApiError::InternalServerError(format!(
-                                "Failed to update topic review status: {}",
-                                e
-                            ))
-                        })?
-                } else {
-                    return Err(ApiError::BadRequest(str!(
-                        "Review status must be provided by Office"
-                    )));
-                }
-            }
-            AuthInfoUserRole::DefenseBoard | AuthInfoUserRole::Student => {
-                // These roles are not allowed to update topics
-                return Err(ApiError::Forbidden);
-            }
-        };
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
 
-        let teacher = teacher::dsl::teacher
-            .find(&topic.teacher_user_name)
-            .first::<Teacher>(conn)
-            .map_err(|e| {
-                ApiError::InternalServerError(format!("Failed to load topic teacher: {}", e))
-            })?;
-        let major = major::dsl::major
-            .find(topic.major_id)
-            .first::<Major>(conn)
-            .map_err(|e| {
-                ApiError::InternalServerError(format!("Failed to load topic major: {}", e))
-            })?;
+    // Admins neither take part in discussions nor oversee final defenses
+    if is_session_admin(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Can't deserialize auth info")))?
+    {
+        return Err(ApiError::Forbidden);
+    }
 
-        let current_student_count: i64 = student::dsl::student
-            .filter(student::columns::topic_id.eq(topic.topic_id))
-            .count()
-            .get_result(conn)
-            .map_err(|_| {
-                ApiError::InternalServerError(str!("Failed to count students for topic"))
-            })?;
+    let username = get_session_username(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
+    let user_role = get_session_user_role(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
 
-        let topic_details = TopicDetails {
-            topic_id: topic.topic_id,
-            major_id: topic.major_id,
-            major_name: major.major_name,
-            teacher_user_name: topic.teacher_user_name,
-            teacher_name: teacher.teacher_name,
-            topic_name: topic.topic_name,
-            topic_description: topic.topic_description,
-            topic_max_students: topic.topic_max_students,
-            topic_type: TopicType::try_from(topic.topic_type)
-                .map_err(|_| ApiError::InternalServerError(str!("Invalid topic type")))?,
-            topic_review_status: TopicReviewStatus::try_from(topic.topic_review_status)
-                .map_err(|_| ApiError::InternalServerError(str!("Invalid topic review status")))?,
-            current_student_count: current_student_count as i32,
-        };
+    let comment = conn.build_transaction().read_write().run(|conn| {
+        let (defense, teacher_username) = finaldefense::table
+            .inner_join(topic::table)
+            .filter(finaldefense::columns::final_def_id.eq(*report_id))
+            .select((finaldefense::all_columns, topic::columns::teacher_user_name))
+            .first::<(FinalDefense, String)>(conn)
+            .map_err(|_| ApiError::NotFound)?;
+        if !can_view_final_defense(conn, &defense, &username, &user_role)? {
+            return Err(ApiError::Forbidden);
+        }
 
-        Ok::<_, ApiError>(topic_details)
+        let mut participants = vec![
+            defense.student_user_name.as_str(),
+            teacher_username.as_str(),
+        ];
+        participants.extend(defense.def_board_user_name.as_deref());
+        create_comment(
+            conn,
+            CommentSubject::FinalDefense(defense.final_def_id),
+            &req,
+            &username,
+            &user_role,
+            &participants,
+            format!(
+                "New comment on final defense #{} (version {})",
+                defense.final_def_id, defense.final_def_version
+            ),
+        )
     })?;
 
-    Ok(HttpResponse::Ok().json(result))
+    Ok(HttpResponse::Created().json(comment))
 }
 
-#[get("/assignments")]
-pub async fn get_assignments(
+#[get("/similarity_reports")]
+pub async fn get_similarity_report(
     pool: web::Data<DbPool>,
     session: Session,
-    query: web::Query<PaginationQuery>,
+    query: web::Query<SimilarityQuery>,
 ) -> Result<HttpResponse, ApiError> {
     use backend_database::schema::*;
 
@@ -921,259 +7721,237 @@ pub async fn get_assignments(
             ApiError::InternalServerError(str!("Failed to get user role from session"))
         })?)
     };
-
     if !is_admin {
         match user_role {
-            Some(AuthInfoUserRole::Student | AuthInfoUserRole::Teacher) => {}
+            Some(AuthInfoUserRole::Teacher | AuthInfoUserRole::Office) => {}
             _ => return Err(ApiError::Forbidden),
         }
     }
 
-    let page = query.page.unwrap_or(1);
-    let page_size = query.page_size.unwrap_or(20);
-    // FIXME: Negative checks
-    let offset = (page - 1) * page_size;
-    let want = offset + page_size;
+    let threshold = query.threshold.unwrap_or(DEFAULT_SIMILARITY_THRESHOLD);
+    if !(threshold > 0.0 && threshold <= 1.0) {
+        return Err(ApiError::BadRequest(str!(
+            "Threshold must be greater than 0 and at most 1"
+        )));
+    }
 
     let mut conn = pool
         .get()
         .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
 
-    let pending_base = assignmentrequest::table
-        .inner_join(student::table.inner_join(major::table))
-        .inner_join(topic::table);
-
-    let approved_base = student::table
-        .inner_join(major::table)
-        .inner_join(topic::table)
-        .filter(student::columns::topic_id.is_not_null());
-
-    let (pending_total, pending_rows, approved_total, approved_rows) = if is_admin {
-        let pending_total: i64 = pending_base
-            .count()
-            .get_result(&mut conn)
-            .map_err(|_| ApiError::InternalServerError(str!("Failed to count assignments")))?;
-        let pending_rows = pending_base
-            .order(assignmentrequest::columns::assn_req_time.desc())
-            .limit(want)
-            .select((
-                assignmentrequest::columns::student_user_name,
-                student::columns::student_name,
-                major::columns::major_name,
-                assignmentrequest::columns::topic_id,
-                topic::columns::topic_name,
-                assignmentrequest::columns::assn_req_time,
-            ))
-            .load::<(
-                String,
-                String,
-                String,
-                i32,
-                String,
-                chrono::DateTime<chrono::Utc>,
-            )>(&mut conn)
-            .map_err(|_| ApiError::InternalServerError(str!("Failed to load assignments")))?;
-
-        let approved_total: i64 = approved_base
-            .count()
-            .get_result(&mut conn)
-            .map_err(|_| ApiError::InternalServerError(str!("Failed to count assignments")))?;
-        let approved_rows = approved_base
-            .order(student::columns::assn_time.desc())
-            .limit(want)
-            .select((
-                student::columns::user_name,
-                student::columns::student_name,
-                major::columns::major_name,
-                topic::columns::topic_id,
-                topic::columns::topic_name,
-                student::columns::assn_time,
-            ))
-            .load::<(
-                String,
-                String,
-                String,
-                i32,
-                String,
-                chrono::DateTime<chrono::Utc>,
-            )>(&mut conn)
-            .map_err(|_| ApiError::InternalServerError(str!("Failed to load assignments")))?;
-
-        (pending_total, pending_rows, approved_total, approved_rows)
-    } else {
-        match user_role {
-            Some(AuthInfoUserRole::Student) => {
-                let pending_q = pending_base
-                    .filter(assignmentrequest::columns::student_user_name.eq(&username));
-                let pending_total: i64 = pending_q.count().get_result(&mut conn).map_err(|_| {
-                    ApiError::InternalServerError(str!("Failed to count assignments"))
-                })?;
-                let pending_rows = pending_q
-                    .order(assignmentrequest::columns::assn_req_time.desc())
-                    .limit(want)
+    let (submissions, files, supervised_topic_ids, cached_texts) =
+        conn.build_transaction().read_only().run(|conn| {
+            // Submission ID, topic ID, student username and name, latest submission first
+            let mut submissions = match query.stage_id {
+                Some(stage_id) => {
+                    reportstage::table
+                        .find(stage_id)
+                        .select(reportstage::columns::stage_id)
+                        .first::<i16>(conn)
+                        .map_err(|_| ApiError::NotFound)?;
+                    let mut q = progressreport::table
+                        .inner_join(student::table)
+                        .filter(progressreport::columns::prog_report_stage_id.eq(stage_id))
+                        .into_boxed();
+                    if let Some(major_id) = query.major_id {
+                        q = q.filter(student::columns::major_id.eq(major_id));
+                    }
+                    q.order((
+                        progressreport::columns::student_user_name.asc(),
+                        progressreport::columns::prog_report_time.desc(),
+                        progressreport::columns::prog_report_id.desc(),
+                    ))
                     .select((
-                        assignmentrequest::columns::student_user_name,
+                        progressreport::columns::prog_report_id,
+                        progressreport::columns::topic_id,
+                        progressreport::columns::student_user_name,
                         student::columns::student_name,
-                        major::columns::major_name,
-                        assignmentrequest::columns::topic_id,
-                        topic::columns::topic_name,
-                        assignmentrequest::columns::assn_req_time,
                     ))
-                    .load::<(
-                        String,
-                        String,
-                        String,
-                        i32,
-                        String,
-                        chrono::DateTime<chrono::Utc>,
-                    )>(&mut conn)
-                    .map_err(|_| {
-                        ApiError::InternalServerError(str!("Failed to load assignments"))
-                    })?;
-
-                let approved_q = approved_base.filter(student::columns::user_name.eq(&username));
-                let approved_total: i64 =
-                    approved_q.count().get_result(&mut conn).map_err(|_| {
-                        ApiError::InternalServerError(str!("Failed to count assignments"))
-                    })?;
-                let approved_rows = approved_q
-                    .order(student::columns::assn_time.desc())
-                    .limit(want)
+                    .load::<(i32, i32, String, String)>(conn)
+                }
+                None => {
+                    let mut q = finaldefense::table.inner_join(student::table).into_boxed();
+                    if let Some(major_id) = query.major_id {
+                        q = q.filter(student::columns::major_id.eq(major_id));
+                    }
+                    q.order((
+                        finaldefense::columns::student_user_name.asc(),
+                        finaldefense::columns::final_def_time.desc(),
+                        finaldefense::columns::final_def_id.desc(),
+                    ))
                     .select((
-                        student::columns::user_name,
+                        finaldefense::columns::final_def_id,
+                        finaldefense::columns::topic_id,
+                        finaldefense::columns::student_user_name,
                         student::columns::student_name,
-                        major::columns::major_name,
-                        topic::columns::topic_id,
-                        topic::columns::topic_name,
-                        student::columns::assn_time,
                     ))
-                    .load::<(
-                        String,
-                        String,
-                        String,
-                        i32,
-                        String,
-                        chrono::DateTime<chrono::Utc>,
-                    )>(&mut conn)
-                    .map_err(|_| {
-                        ApiError::InternalServerError(str!("Failed to load assignments"))
-                    })?;
-
-                (pending_total, pending_rows, approved_total, approved_rows)
+                    .load::<(i32, i32, String, String)>(conn)
+                }
             }
-            Some(AuthInfoUserRole::Teacher) => {
-                let pending_q =
-                    pending_base.filter(topic::columns::teacher_user_name.eq(&username));
-                let pending_total: i64 = pending_q.count().get_result(&mut conn).map_err(|_| {
-                    ApiError::InternalServerError(str!("Failed to count assignments"))
-                })?;
-                let pending_rows = pending_q
-                    .order(assignmentrequest::columns::assn_req_time.desc())
-                    .limit(want)
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to load submissions")))?;
+            // Only the latest submission of each student is checked
+            submissions.dedup_by(|later, latest| later.2 == latest.2);
+
+            let submission_ids = submissions.iter().map(|s| s.0).collect::<Vec<_>>();
+            let files = match query.stage_id {
+                Some(_) => progressreportfile::table
+                    .inner_join(attachment::table)
+                    .filter(progressreportfile::columns::prog_report_id.eq_any(&submission_ids))
+                    .filter(
+                        progressreportfile::columns::prog_file_kind
+                            .eq(SubmissionFileKind::Thesis as i16),
+                    )
+                    .order(progressreportfile::columns::attach_id.asc())
                     .select((
-                        assignmentrequest::columns::student_user_name,
-                        student::columns::student_name,
-                        major::columns::major_name,
-                        assignmentrequest::columns::topic_id,
-                        topic::columns::topic_name,
-                        assignmentrequest::columns::assn_req_time,
+                        progressreportfile::columns::prog_report_id,
+                        attachment::all_columns,
                     ))
-                    .load::<(
-                        String,
-                        String,
-                        String,
-                        i32,
-                        String,
-                        chrono::DateTime<chrono::Utc>,
-                    )>(&mut conn)
-                    .map_err(|_| {
-                        ApiError::InternalServerError(str!("Failed to load assignments"))
-                    })?;
-
-                let approved_q =
-                    approved_base.filter(topic::columns::teacher_user_name.eq(&username));
-                let approved_total: i64 =
-                    approved_q.count().get_result(&mut conn).map_err(|_| {
-                        ApiError::InternalServerError(str!("Failed to count assignments"))
-                    })?;
-                let approved_rows = approved_q
-                    .order(student::columns::assn_time.desc())
-                    .limit(want)
+                    .load::<(i32, Attachment)>(conn),
+                None => finaldefensefile::table
+                    .inner_join(attachment::table)
+                    .filter(finaldefensefile::columns::final_def_id.eq_any(&submission_ids))
+                    .filter(
+                        finaldefensefile::columns::def_file_kind
+                            .eq(SubmissionFileKind::Thesis as i16),
+                    )
+                    .order(finaldefensefile::columns::attach_id.asc())
                     .select((
-                        student::columns::user_name,
-                        student::columns::student_name,
-                        major::columns::major_name,
-                        topic::columns::topic_id,
-                        topic::columns::topic_name,
-                        student::columns::assn_time,
+                        finaldefensefile::columns::final_def_id,
+                        attachment::all_columns,
                     ))
-                    .load::<(
-                        String,
-                        String,
-                        String,
-                        i32,
-                        String,
-                        chrono::DateTime<chrono::Utc>,
-                    )>(&mut conn)
-                    .map_err(|_| {
-                        ApiError::InternalServerError(str!("Failed to load assignments"))
-                    })?;
-
-                (pending_total, pending_rows, approved_total, approved_rows)
+                    .load::<(i32, Attachment)>(conn),
             }
-            _ => return Err(ApiError::Forbidden),
-        }
-    };
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to load submission files")))?;
 
-    let total = pending_total + approved_total;
+            // Teachers only see documents of students on the topics they supervise
+            let supervised_topic_ids = match user_role {
+                Some(AuthInfoUserRole::Teacher) => Some(
+                    topic::table
+                        .filter(
+                            topic::columns::teacher_user_name
+                                .eq(&username)
+                                .or(topic::columns::topic_id
+                                    .eq_any(cosupervised_topic_ids(&username))),
+                        )
+                        .select(topic::columns::topic_id)
+                        .load::<i32>(conn)
+                        .map_err(|_| {
+                            ApiError::InternalServerError(str!("Failed to load supervised topics"))
+                        })?
+                        .into_iter()
+                        .collect::<HashSet<_>>(),
+                ),
+                _ => None,
+            };
+            let attach_ids = files
+                .iter()
+                .map(|(_, attach)| attach.attach_id)
+                .collect::<Vec<_>>();
+            let cached_texts = load_attachment_texts(conn, &attach_ids)?;
 
-    let pending = pending_rows.into_iter().map(
-        |(student_user_name, student_name, student_major, topic_id, topic_name, request_time)| {
-            Assignment {
-                student_user_name,
-                student_name,
-                student_major,
-                topic_id,
-                topic_name,
-                request_time,
-                status: AssignmentStatus::Pending,
-            }
-        },
-    );
-    let approved = approved_rows.into_iter().map(
-        |(student_user_name, student_name, student_major, topic_id, topic_name, assn_time)| {
-            Assignment {
-                student_user_name,
-                student_name,
-                student_major,
-                topic_id,
-                topic_name,
-                request_time: assn_time,
-                status: AssignmentStatus::Approved,
+            Ok::<_, ApiError>((submissions, files, supervised_topic_ids, cached_texts))
+        })?;
+
+    // Extraction and comparison take a while on larger cohorts, so they run off the worker
+    // threads and outside of any transaction
+    let stage_id = query.stage_id;
+    let (report, extracted_texts) = web::block(move || {
+        let is_visible = |topic_id: i32| {
+            supervised_topic_ids
+                .as_ref()
+                .is_none_or(|topic_ids| topic_ids.contains(&topic_id))
+        };
+
+        let submissions = submissions
+            .into_iter()
+            .map(|(id, topic_id, student_user_name, student_name)| {
+                (id, (topic_id, student_user_name, student_name))
+            })
+            .collect::<HashMap<_, _>>();
+        let mut extracted_texts = Vec::new();
+        let mut documents = Vec::new();
+        let mut unreadable = Vec::new();
+        for (submission_id, attach) in files {
+            let (topic_id, student_user_name, student_name) = &submissions[&submission_id];
+            let text = match cached_texts.get(&attach.attach_id) {
+                Some(text) => text.clone(),
+                None => {
+                    let text = extract_attachment_text(&attach)?;
+                    extracted_texts.push((attach.attach_id, text.clone()));
+                    text
+                }
+            };
+            let fingerprint = text.and_then(crate::similarity::Fingerprint::new);
+            let details = SimilarityDocumentDetails {
+                student_user_name: student_user_name.clone(),
+                student_name: student_name.clone(),
+                submission_id,
+                attachment: build_attachment_details(attach),
+            };
+            match fingerprint {
+                Some(fingerprint) => documents.push((*topic_id, details, fingerprint)),
+                None if is_visible(*topic_id) => unreadable.push(details),
+                None => {}
             }
-        },
-    );
+        }
 
-    // We assume this won't be too long.
-    let assignments = pending
-        .chain(approved)
-        .skip(offset as usize)
-        .take(page_size as usize)
-        .collect::<Vec<_>>();
+        let mut pairs = Vec::new();
+        for (i, first) in documents.iter().enumerate() {
+            for second in &documents[i + 1..] {
+                // The first document of a pair is always one the user may see, as the passages
+                // are quoted from it
+                let ((_, first, first_fingerprint), (_, second, second_fingerprint)) =
+                    if is_visible(first.0) {
+                        (first, second)
+                    } else if is_visible(second.0) {
+                        (second, first)
+                    } else {
+                        continue;
+                    };
+                if first.student_user_name == second.student_user_name {
+                    continue;
+                }
+                let similarity = first_fingerprint.similarity(second_fingerprint);
+                if similarity < threshold {
+                    continue;
+                }
+                pairs.push(SimilarityPairDetails {
+                    first: first.clone(),
+                    second: second.clone(),
+                    similarity,
+                    passages: first_fingerprint.matched_passages(second_fingerprint),
+                });
+            }
+        }
+        pairs.sort_by(|a, b| b.similarity.total_cmp(&a.similarity));
+
+        Ok::<_, ApiError>((
+            SimilarityReportResponse {
+                stage_id,
+                threshold,
+                checked: documents.len() as i64,
+                unreadable,
+                pairs,
+            },
+            extracted_texts,
+        ))
+    })
+    .await
+    .map_err(|_| ApiError::InternalServerError(str!("Failed to run the similarity check")))??;
+
+    if !extracted_texts.is_empty() {
+        conn.build_transaction()
+            .read_write()
+            .run(|conn| store_attachment_texts(conn, &extracted_texts))?;
+    }
 
-    Ok(HttpResponse::Ok().json(AssignmentsGetResponse {
-        total,
-        page,
-        page_size,
-        assignments,
-    }))
+    Ok(HttpResponse::Ok().json(report))
 }
 
-#[post("/assignments")]
-pub async fn create_assignment(
+#[get("/supervision_quotas")]
+pub async fn get_supervision_quotas(
     pool: web::Data<DbPool>,
     session: Session,
-    req: web::Json<AssignmentsPostRequest>,
 ) -> Result<HttpResponse, ApiError> {
     use backend_database::schema::*;
 
@@ -1181,11 +7959,12 @@ pub async fn create_assignment(
         return Err(ApiError::Unauthorized);
     }
 
-    let username = get_session_username(&session)
-        .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
     let user_role = get_session_user_role(&session)
         .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
-    if !matches!(user_role, AuthInfoUserRole::Student) {
+    if !matches!(
+        user_role,
+        AuthInfoUserRole::Teacher | AuthInfoUserRole::Office
+    ) {
         return Err(ApiError::Forbidden);
     }
 
@@ -1193,70 +7972,76 @@ pub async fn create_assignment(
         .get()
         .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
 
-    conn.build_transaction().read_write().run(|conn| {
-        let student = student::dsl::student
-            .find(&username)
-            .first::<Student>(conn)
-            .map_err(|_| ApiError::NotFound)?;
-
-        if student.topic_id.is_some() {
-            return Err(ApiError::Conflict(str!("Student already has a topic")));
-        }
-
-        let topic = topic::dsl::topic
-            .find(req.topic_id)
-            .first::<Topic>(conn)
-            .map_err(|_| ApiError::NotFound)?;
+    let quotas = supervisionquota::dsl::supervisionquota
+        .order(supervisionquota::columns::teacher_title.asc())
+        .load::<SupervisionQuota>(&mut conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load supervision quotas")))?
+        .into_iter()
+        .map(|q| {
+            Ok::<_, ApiError>(SupervisionQuotaDetails {
+                teacher_title: TeacherTitle::try_from(q.teacher_title)
+                    .map_err(|_| ApiError::InternalServerError(str!("Invalid teacher title")))?,
+                quota_max_students: q.quota_max_students,
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
 
-        if topic.topic_review_status != TopicReviewStatus::Approved as i16
-            || topic.major_id != student.major_id
-        {
-            return Err(ApiError::Forbidden);
-        }
+    Ok(HttpResponse::Ok().json(SupervisionQuotasGetResponse { quotas }))
+}
 
-        let current_student_count: i64 = student::dsl::student
-            .filter(student::columns::topic_id.eq(topic.topic_id))
-            .count()
-            .get_result(conn)
-            .map_err(|_| ApiError::InternalServerError(str!("Failed to count students")))?;
-        if current_student_count >= topic.topic_max_students as i64 {
-            return Err(ApiError::Conflict(str!("Topic is full")));
-        }
+#[put("/supervision_quotas/{teacher_title}")]
+pub async fn put_supervision_quota(
+    pool: web::Data<DbPool>,
+    session: Session,
+    teacher_title: web::Path<i16>,
+    req: web::Json<SupervisionQuotaPutRequest>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
 
-        let has_pending = diesel::select(diesel::dsl::exists(
-            assignmentrequest::dsl::assignmentrequest
-                .filter(assignmentrequest::columns::student_user_name.eq(&username))
-                .filter(assignmentrequest::columns::topic_id.eq(req.topic_id)),
-        ))
-        .get_result(conn)
-        .map_err(|_| ApiError::InternalServerError(str!("Failed to check existing request")))?;
-        if has_pending {
-            return Err(ApiError::Conflict(str!(
-                "Assignment request already exists"
-            )));
-        }
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
 
-        diesel::insert_into(assignmentrequest::dsl::assignmentrequest)
-            .values(NewAssignmentRequest {
-                student_user_name: &username,
-                topic_id: req.topic_id,
-                assn_req_time: Utc::now(),
-            })
-            .execute(conn)
-            .map_err(|_| ApiError::Conflict(str!("Failed to create assignment request")))?;
+    let user_role = get_session_user_role(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+    if !matches!(user_role, AuthInfoUserRole::Office) {
+        // Only office can configure supervision quotas
+        return Err(ApiError::Forbidden);
+    }
 
-        Ok::<_, ApiError>(())
-    })?;
+    let teacher_title = TeacherTitle::try_from(*teacher_title)
+        .map_err(|_| ApiError::BadRequest(str!("Invalid teacher title")))?;
+    if req.quota_max_students < 0 {
+        return Err(ApiError::BadRequest(str!("Quota must not be negative")));
+    }
 
-    Ok(HttpResponse::Created().finish())
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    let quota = NewSupervisionQuota {
+        teacher_title: teacher_title as i16,
+        quota_max_students: req.quota_max_students,
+    };
+    diesel::insert_into(supervisionquota::dsl::supervisionquota)
+        .values(&quota)
+        .on_conflict(supervisionquota::columns::teacher_title)
+        .do_update()
+        .set(&quota)
+        .execute(&mut conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to store supervision quota")))?;
+
+    Ok(HttpResponse::Ok().json(SupervisionQuotaDetails {
+        teacher_title,
+        quota_max_students: req.quota_max_students,
+    }))
 }
 
-#[patch("/assignments/{student_username}/{topic_id}")]
-pub async fn update_assignment_status(
+#[delete("/supervision_quotas/{teacher_title}")]
+pub async fn delete_supervision_quota(
     pool: web::Data<DbPool>,
     session: Session,
-    path: web::Path<(String, i32)>,
-    req: web::Json<AssignmentRecordPatchRequest>,
+    teacher_title: web::Path<i16>,
 ) -> Result<HttpResponse, ApiError> {
     use backend_database::schema::*;
 
@@ -1264,80 +8049,101 @@ pub async fn update_assignment_status(
         return Err(ApiError::Unauthorized);
     }
 
-    let username = get_session_username(&session)
-        .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
     let user_role = get_session_user_role(&session)
         .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
-
-    if !matches!(user_role, AuthInfoUserRole::Teacher) {
-        // Only teachers can approve/reject assignment requests
+    if !matches!(user_role, AuthInfoUserRole::Office) {
+        // Only office can configure supervision quotas
         return Err(ApiError::Forbidden);
     }
 
-    let (student_username, topic_id) = path.into_inner();
-
     let mut conn = pool
         .get()
         .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
 
-    conn.build_transaction().read_write().run(|conn| {
-        let req_row = assignmentrequest::dsl::assignmentrequest
-            .find((&student_username, topic_id))
-            .first::<AssignmentRequest>(conn)
-            .map_err(|_| ApiError::NotFound)?;
+    let deleted = diesel::delete(supervisionquota::dsl::supervisionquota.find(*teacher_title))
+        .execute(&mut conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to delete supervision quota")))?;
+    if deleted == 0 {
+        return Err(ApiError::NotFound);
+    }
 
-        let topic = topic::dsl::topic
-            .find(req_row.topic_id)
-            .first::<Topic>(conn)
-            .map_err(|_| ApiError::NotFound)?;
-        if topic.teacher_user_name != username {
+    Ok(HttpResponse::Ok().finish())
+}
+
+#[put("/teachers/{teacher_user_name}/title")]
+pub async fn put_teacher_title(
+    pool: web::Data<DbPool>,
+    session: Session,
+    teacher_user_name: web::Path<String>,
+    req: web::Json<TeacherTitlePutRequest>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let is_admin = is_session_admin(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Can't deserialize auth info")))?;
+    if !is_admin {
+        let user_role = get_session_user_role(&session).map_err(|_| {
+            ApiError::InternalServerError(str!("Failed to get user role from session"))
+        })?;
+        if !matches!(user_role, AuthInfoUserRole::Office) {
+            // Only office and admins can change which quota applies to a teacher
             return Err(ApiError::Forbidden);
         }
+    }
 
-        if req.approved {
-            let student = student::dsl::student
-                .find(&student_username)
-                .first::<Student>(conn)
-                .map_err(|_| ApiError::NotFound)?;
-            if student.topic_id.is_some() {
-                return Err(ApiError::Conflict(str!("Student already has a topic")));
-            }
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
 
-            let current_student_count: i64 = student::dsl::student
-                .filter(student::columns::topic_id.eq(topic.topic_id))
-                .count()
-                .get_result(conn)
-                .map_err(|_| ApiError::InternalServerError(str!("Failed to count students")))?;
-            if current_student_count >= topic.topic_max_students as i64 {
-                return Err(ApiError::Conflict(str!("Topic is full")));
-            }
+    let updated = diesel::update(teacher::dsl::teacher.find(teacher_user_name.as_str()))
+        .set(teacher::columns::teacher_title.eq(req.teacher_title as i16))
+        .execute(&mut conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to update teacher title")))?;
+    if updated == 0 {
+        return Err(ApiError::NotFound);
+    }
 
-            let changeset = StudentAssignmentChangeset {
-                topic_id: topic.topic_id,
-                assn_time: req_row.assn_req_time,
-            };
-            diesel::update(&student)
-                .set(changeset)
-                .execute(conn)
-                .map_err(|_| ApiError::InternalServerError(str!("Failed to assign topic")))?;
-        }
+    Ok(HttpResponse::Ok().json(TeacherTitleDetails {
+        teacher_user_name: teacher_user_name.into_inner(),
+        teacher_title: req.teacher_title,
+    }))
+}
 
-        diesel::delete(
-            assignmentrequest::dsl::assignmentrequest.find((&student_username, topic_id)),
-        )
-        .execute(conn)
-        .map_err(|_| ApiError::InternalServerError(str!("Failed to delete assignment request")))?;
+#[get("/matching_rounds")]
+pub async fn get_matching_rounds(
+    pool: web::Data<DbPool>,
+    session: Session,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
 
-        Ok::<_, ApiError>(())
-    })?;
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
 
-    Ok(HttpResponse::Ok().finish())
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    let rounds = matchinground::table
+        .order(matchinground::columns::match_round_open_time.desc())
+        .load::<MatchingRound>(&mut conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load matching rounds")))?
+        .into_iter()
+        .map(build_matching_round_details)
+        .collect();
+
+    Ok(HttpResponse::Ok().json(MatchingRoundsGetResponse { rounds }))
 }
 
-#[get("/progress_reports")]
-pub async fn get_progress_reports(
+#[post("/matching_rounds")]
+pub async fn create_matching_round(
     pool: web::Data<DbPool>,
     session: Session,
+    req: web::Json<MatchingRoundsPostRequest>,
 ) -> Result<HttpResponse, ApiError> {
     use backend_database::schema::*;
 
@@ -1345,74 +8151,78 @@ pub async fn get_progress_reports(
         return Err(ApiError::Unauthorized);
     }
 
-    let username = get_session_username(&session)
-        .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
     let user_role = get_session_user_role(&session)
         .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+    if !matches!(user_role, AuthInfoUserRole::Office) {
+        // Only office can organize matching rounds
+        return Err(ApiError::Forbidden);
+    }
 
-    match user_role {
-        AuthInfoUserRole::Student | AuthInfoUserRole::Teacher => {}
-        _ => return Err(ApiError::Forbidden),
+    if req.match_round_name.trim().is_empty() {
+        return Err(ApiError::BadRequest(str!("Round name must not be empty")));
+    }
+    if req.max_preferences < 1 {
+        return Err(ApiError::BadRequest(str!(
+            "Students must be able to list at least one preference"
+        )));
+    }
+    if req.open_time >= req.close_time {
+        return Err(ApiError::BadRequest(str!(
+            "Preference window must close after it opens"
+        )));
     }
 
     let mut conn = pool
         .get()
         .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
 
-    let mut base = progressreport::table
-        .inner_join(student::table)
-        .inner_join(topic::table)
-        .into_boxed();
+    let round = diesel::insert_into(matchinground::table)
+        .values(NewMatchingRound {
+            match_round_name: req.match_round_name.trim(),
+            match_round_max_prefs: req.max_preferences,
+            match_round_open_time: req.open_time,
+            match_round_close_time: req.close_time,
+        })
+        .get_result::<MatchingRound>(&mut conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to create matching round")))?;
 
-    match user_role {
-        AuthInfoUserRole::Student => {
-            base = base.filter(progressreport::columns::student_user_name.eq(&username));
-        }
-        AuthInfoUserRole::Teacher => {
-            base = base.filter(topic::columns::teacher_user_name.eq(&username));
-        }
-        _ => unreachable!(),
-    }
+    Ok(HttpResponse::Created().json(build_matching_round_details(round)))
+}
 
-    let rows = base
-        .order(progressreport::columns::prog_report_time.desc())
-        .select((progressreport::all_columns, student::columns::student_name))
-        .load::<(ProgressReport, String)>(&mut conn)
-        .map_err(|_| ApiError::InternalServerError(str!("Failed to load progress reports")))?;
+#[get("/matching_rounds/{match_round_id}/preferences")]
+pub async fn get_preferences(
+    pool: web::Data<DbPool>,
+    session: Session,
+    match_round_id: web::Path<i32>,
+) -> Result<HttpResponse, ApiError> {
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
 
-    if rows.is_empty() {
-        return Err(ApiError::NotFound);
+    let user_role = get_session_user_role(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+    if !matches!(user_role, AuthInfoUserRole::Student) {
+        return Err(ApiError::Forbidden);
     }
 
-    let reports = rows
-        .into_iter()
-        .map(|(r, student_name)| {
-            Ok::<_, ApiError>(ProgressReportDetailResponse {
-                prog_report_id: r.prog_report_id,
-                topic_id: r.topic_id,
-                student_user_name: r.student_user_name,
-                student_name,
-                prog_report_type: ProgressReportType::try_from(r.prog_report_type).map_err(
-                    |_| ApiError::InternalServerError(str!("Invalid progress report type")),
-                )?,
-                prog_report_time: r.prog_report_time,
-                prog_report_attachment: r.prog_report_attachment,
-                prog_report_outcome: ProgressOutcome::try_from(r.prog_report_outcome)
-                    .map_err(|_| ApiError::InternalServerError(str!("Invalid progress outcome")))?,
-                prog_report_comment: r.prog_report_comment,
-                prog_report_grade: r.prog_report_grade,
-            })
-        })
-        .collect::<Result<Vec<_>, _>>()?;
+    let username = get_session_username(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
+
+    let preferences = load_student_preferences(&mut conn, *match_round_id, &username)?;
 
-    Ok(HttpResponse::Ok().json(ProgressReportsGetResponse { reports }))
+    Ok(HttpResponse::Ok().json(PreferencesGetResponse { preferences }))
 }
 
-#[post("/progress_reports")]
-pub async fn create_progress_report(
+#[put("/matching_rounds/{match_round_id}/preferences")]
+pub async fn put_preferences(
     pool: web::Data<DbPool>,
     session: Session,
-    req: web::Json<ProgressReportsPostRequest>,
+    match_round_id: web::Path<i32>,
+    req: web::Json<PreferencesPutRequest>,
 ) -> Result<HttpResponse, ApiError> {
     use backend_database::schema::*;
 
@@ -1420,99 +8230,153 @@ pub async fn create_progress_report(
         return Err(ApiError::Unauthorized);
     }
 
+    let user_role = get_session_user_role(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+    if !matches!(user_role, AuthInfoUserRole::Student) {
+        // Only students list preferences
+        return Err(ApiError::Forbidden);
+    }
+
     let username = get_session_username(&session)
         .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
 
+    let distinct_topics = req.topic_ids.iter().collect::<HashSet<_>>().len();
+    if distinct_topics != req.topic_ids.len() {
+        return Err(ApiError::BadRequest(str!(
+            "A topic must not be listed more than once"
+        )));
+    }
+
     let mut conn = pool
         .get()
         .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
 
-    conn.build_transaction().read_write().run(|conn| {
+    let preferences = conn.build_transaction().read_write().run(|conn| {
+        let round = load_uncommitted_matching_round(conn, *match_round_id)?;
+        let now = Utc::now();
+        if now < round.match_round_open_time || now >= round.match_round_close_time {
+            return Err(ApiError::Conflict(str!(
+                "Matching round is not accepting preferences"
+            )));
+        }
+        if req.topic_ids.len() > round.match_round_max_prefs as usize {
+            return Err(ApiError::BadRequest(format!(
+                "At most {} preferences can be listed",
+                round.match_round_max_prefs
+            )));
+        }
+
         let student = student::dsl::student
             .find(&username)
             .first::<Student>(conn)
-            .map_err(|_| ApiError::NotFound)?;
-        let topic_id = student
-            .topic_id
-            .ok_or_else(|| ApiError::Conflict(str!("Student has no assigned topic")))?;
+            .map_err(|_| {
+                ApiError::InternalServerError(str!("Failed to get student information"))
+            })?;
+        if student.topic_id.is_some() {
+            return Err(ApiError::Conflict(str!("Student already has a topic")));
+        }
 
-        // Determine report type: proposal first, then midterm after proposal passed.
-        let has_passed_proposal = diesel::select(diesel::dsl::exists(
-            progressreport::dsl::progressreport
-                .filter(progressreport::columns::student_user_name.eq(&username))
-                .filter(
-                    progressreport::columns::prog_report_type
-                        .eq(ProgressReportType::Proposal as i16),
-                )
-                .filter(
-                    progressreport::columns::prog_report_outcome.eq(ProgressOutcome::Passed as i16),
-                ),
-        ))
-        .get_result::<bool>(conn)
-        .map_err(|_| ApiError::InternalServerError(str!("Failed to check proposal status")))?;
+        let eligible_topics: i64 = topic::table
+            .filter(topic::columns::topic_id.eq_any(&req.topic_ids))
+            .filter(topic::columns::major_id.eq(student.major_id))
+            .filter(topic::columns::topic_review_status.eq(TopicReviewStatus::Approved as i16))
+            .count()
+            .get_result(conn)
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to check topics")))?;
+        if eligible_topics != req.topic_ids.len() as i64 {
+            return Err(ApiError::BadRequest(str!(
+                "Only approved topics of the student's major can be listed"
+            )));
+        }
 
-        let report_type = if has_passed_proposal {
-            ProgressReportType::Midterm
-        } else {
-            ProgressReportType::Proposal
-        };
+        diesel::delete(
+            topicpreference::table
+                .filter(topicpreference::columns::match_round_id.eq(round.match_round_id))
+                .filter(topicpreference::columns::student_user_name.eq(&username)),
+        )
+        .execute(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to replace preferences")))?;
+        // Teachers' ranks of the student only matter for topics the student still applies to
+        diesel::delete(
+            applicantrank::table
+                .filter(applicantrank::columns::match_round_id.eq(round.match_round_id))
+                .filter(applicantrank::columns::student_user_name.eq(&username))
+                .filter(diesel::dsl::not(
+                    applicantrank::columns::topic_id.eq_any(&req.topic_ids),
+                )),
+        )
+        .execute(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to replace preferences")))?;
+
+        let new_preferences = req
+            .topic_ids
+            .iter()
+            .enumerate()
+            .map(|(index, topic_id)| NewTopicPreference {
+                match_round_id: round.match_round_id,
+                student_user_name: &username,
+                topic_id: *topic_id,
+                pref_rank: index as i16 + 1,
+            })
+            .collect::<Vec<_>>();
+        diesel::insert_into(topicpreference::table)
+            .values(&new_preferences)
+            .execute(conn)
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to store preferences")))?;
 
-        // Enforce: per (student, type) at most one pending and one passed.
-        let has_pending = diesel::select(diesel::dsl::exists(
-            progressreport::dsl::progressreport
-                .filter(progressreport::columns::student_user_name.eq(&username))
-                .filter(progressreport::columns::prog_report_type.eq(report_type as i16))
-                .filter(
-                    progressreport::columns::prog_report_outcome
-                        .eq(ProgressOutcome::NoConclusion as i16),
-                ),
-        ))
-        .get_result(conn)
-        .map_err(|_| ApiError::InternalServerError(str!("Failed to check pending reports")))?;
-        if has_pending {
-            return Err(ApiError::Conflict(str!("A pending report already exists")));
-        }
+        load_student_preferences(conn, round.match_round_id, &username)
+    })?;
 
-        let has_passed = diesel::select(diesel::dsl::exists(
-            progressreport::dsl::progressreport
-                .filter(progressreport::columns::student_user_name.eq(&username))
-                .filter(progressreport::columns::prog_report_type.eq(report_type as i16))
-                .filter(
-                    progressreport::columns::prog_report_outcome.eq(ProgressOutcome::Passed as i16),
-                ),
-        ))
-        .get_result(conn)
-        .map_err(|_| ApiError::InternalServerError(str!("Failed to check passed reports")))?;
-        if has_passed {
-            return Err(ApiError::Conflict(str!("A passed report already exists")));
-        }
+    Ok(HttpResponse::Ok().json(PreferencesGetResponse { preferences }))
+}
+
+#[get("/matching_rounds/{match_round_id}/applicants")]
+pub async fn get_applicants(
+    pool: web::Data<DbPool>,
+    session: Session,
+    match_round_id: web::Path<i32>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let user_role = get_session_user_role(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+    if !matches!(user_role, AuthInfoUserRole::Teacher) {
+        return Err(ApiError::Forbidden);
+    }
 
-        diesel::insert_into(progressreport::dsl::progressreport)
-            .values(NewProgressReport {
-                topic_id,
-                student_user_name: &username,
-                prog_report_type: report_type as i16,
-                prog_report_time: Utc::now(),
-                prog_report_attachment: &req.attachment,
-                prog_report_outcome: ProgressOutcome::NoConclusion as i16,
-                prog_report_comment: None,
-                prog_report_grade: None,
-            })
-            .execute(conn)
-            .map_err(|_| ApiError::InternalServerError(str!("Failed to create progress report")))?;
+    let username = get_session_username(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
 
-        Ok::<_, ApiError>(())
-    })?;
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
 
-    Ok(HttpResponse::Ok().finish())
+    // Teacher: Applicants of topics they supervise or co-supervise
+    let topic_ids = topic::table
+        .filter(
+            topic::columns::teacher_user_name
+                .eq(&username)
+                .or(topic::columns::topic_id.eq_any(cosupervised_topic_ids(&username))),
+        )
+        .select(topic::columns::topic_id)
+        .load::<i32>(&mut conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load topics")))?;
+
+    let topics = load_topic_applicants(&mut conn, *match_round_id, &topic_ids)?;
+
+    Ok(HttpResponse::Ok().json(ApplicantsGetResponse { topics }))
 }
 
-#[patch("/progress_reports/{report_id}")]
-pub async fn update_progress_report(
+#[put("/matching_rounds/{match_round_id}/rankings/{topic_id}")]
+pub async fn put_rankings(
     pool: web::Data<DbPool>,
     session: Session,
-    report_id: web::Path<i32>,
-    req: web::Json<ProgressReportRecordPatchRequest>,
+    path: web::Path<(i32, i32)>,
+    req: web::Json<RankingsPutRequest>,
 ) -> Result<HttpResponse, ApiError> {
     use backend_database::schema::*;
 
@@ -1520,203 +8384,133 @@ pub async fn update_progress_report(
         return Err(ApiError::Unauthorized);
     }
 
+    let user_role = get_session_user_role(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+    if !matches!(user_role, AuthInfoUserRole::Teacher) {
+        // Only teachers rank applicants
+        return Err(ApiError::Forbidden);
+    }
+
     let username = get_session_username(&session)
         .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
+
+    let (match_round_id, topic_id) = path.into_inner();
+
+    let distinct_students = req.student_user_names.iter().collect::<HashSet<_>>().len();
+    if distinct_students != req.student_user_names.len() {
+        return Err(ApiError::BadRequest(str!(
+            "A student must not be ranked more than once"
+        )));
+    }
+
     let mut conn = pool
         .get()
         .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
 
     let result = conn.build_transaction().read_write().run(|conn| {
-        let (report, student_name, teacher_username) = progressreport::table
-            .inner_join(student::table)
-            .inner_join(topic::table)
-            .filter(progressreport::columns::prog_report_id.eq(*report_id))
-            .select((
-                progressreport::all_columns,
-                student::columns::student_name,
-                topic::columns::teacher_user_name,
-            ))
-            .first::<(ProgressReport, String, String)>(conn)
-            .map_err(|_| ApiError::NotFound)?;
+        let round = load_uncommitted_matching_round(conn, match_round_id)?;
 
-        if teacher_username != username {
+        let topic = topic::dsl::topic
+            .find(topic_id)
+            .first::<Topic>(conn)
+            .map_err(|_| ApiError::NotFound)?;
+        if !can_approve_for_topic(conn, topic.topic_id, &topic.teacher_user_name, &username)? {
             return Err(ApiError::Forbidden);
         }
 
-        // Enforce per (student, type): at most one pending + one passed.
-        match req.outcome {
-            ProgressOutcome::NoConclusion => {
-                let other_pending = diesel::select(diesel::dsl::exists(
-                    progressreport::dsl::progressreport
-                        .filter(
-                            progressreport::columns::student_user_name
-                                .eq(&report.student_user_name),
-                        )
-                        .filter(
-                            progressreport::columns::prog_report_type.eq(report.prog_report_type),
-                        )
-                        .filter(
-                            progressreport::columns::prog_report_outcome
-                                .eq(ProgressOutcome::NoConclusion as i16),
-                        )
-                        .filter(progressreport::columns::prog_report_id.ne(report.prog_report_id)),
-                ))
-                .get_result::<bool>(conn)
-                .map_err(|_| {
-                    ApiError::InternalServerError(str!("Failed to check pending reports"))
-                })?;
-                if other_pending {
-                    return Err(ApiError::Conflict(str!(
-                        "Another pending report already exists"
-                    )));
-                }
-            }
-            ProgressOutcome::Passed => {
-                let other_passed = diesel::select(diesel::dsl::exists(
-                    progressreport::dsl::progressreport
-                        .filter(
-                            progressreport::columns::student_user_name
-                                .eq(&report.student_user_name),
-                        )
-                        .filter(
-                            progressreport::columns::prog_report_type.eq(report.prog_report_type),
-                        )
-                        .filter(
-                            progressreport::columns::prog_report_outcome
-                                .eq(ProgressOutcome::Passed as i16),
-                        )
-                        .filter(progressreport::columns::prog_report_id.ne(report.prog_report_id)),
-                ))
-                .get_result::<bool>(conn)
-                .map_err(|_| {
-                    ApiError::InternalServerError(str!("Failed to check passed reports"))
-                })?;
-                if other_passed {
-                    return Err(ApiError::Conflict(str!(
-                        "Another passed report already exists"
-                    )));
-                }
-            }
-            ProgressOutcome::Rejected => {}
+        let applicants = topicpreference::table
+            .filter(topicpreference::columns::match_round_id.eq(round.match_round_id))
+            .filter(topicpreference::columns::topic_id.eq(topic.topic_id))
+            .select(topicpreference::columns::student_user_name)
+            .load::<String>(conn)
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to load applicants")))?
+            .into_iter()
+            .collect::<HashSet<_>>();
+        if !req
+            .student_user_names
+            .iter()
+            .all(|student_user_name| applicants.contains(student_user_name))
+        {
+            return Err(ApiError::BadRequest(str!(
+                "Only applicants of the topic can be ranked"
+            )));
         }
 
-        diesel::update(progressreport::dsl::progressreport.find(report.prog_report_id))
-            .set((
-                progressreport::columns::prog_report_outcome.eq(req.outcome as i16),
-                progressreport::columns::prog_report_comment.eq(req.comment.clone()),
-                progressreport::columns::prog_report_grade.eq(req.grade.clone()),
-            ))
+        diesel::delete(
+            applicantrank::table
+                .filter(applicantrank::columns::match_round_id.eq(round.match_round_id))
+                .filter(applicantrank::columns::topic_id.eq(topic.topic_id)),
+        )
+        .execute(conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to replace rankings")))?;
+
+        let new_ranks = req
+            .student_user_names
+            .iter()
+            .enumerate()
+            .map(|(index, student_user_name)| NewApplicantRank {
+                match_round_id: round.match_round_id,
+                topic_id: topic.topic_id,
+                student_user_name,
+                appl_rank: index as i32 + 1,
+            })
+            .collect::<Vec<_>>();
+        diesel::insert_into(applicantrank::table)
+            .values(&new_ranks)
             .execute(conn)
-            .map_err(|_| ApiError::InternalServerError(str!("Failed to update progress report")))?;
-
-        let updated = progressreport::dsl::progressreport
-            .find(report.prog_report_id)
-            .first::<ProgressReport>(conn)
-            .map_err(|_| ApiError::NotFound)?;
-
-        let result = ProgressReportDetailResponse {
-            prog_report_id: updated.prog_report_id,
-            topic_id: updated.topic_id,
-            student_user_name: updated.student_user_name,
-            student_name,
-            prog_report_type: ProgressReportType::try_from(updated.prog_report_type)
-                .map_err(|_| ApiError::InternalServerError(str!("Invalid progress report type")))?,
-            prog_report_time: updated.prog_report_time,
-            prog_report_attachment: updated.prog_report_attachment,
-            prog_report_outcome: ProgressOutcome::try_from(updated.prog_report_outcome)
-                .map_err(|_| ApiError::InternalServerError(str!("Invalid progress outcome")))?,
-            prog_report_comment: updated.prog_report_comment,
-            prog_report_grade: updated.prog_report_grade,
-        };
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to store rankings")))?;
 
-        Ok::<_, ApiError>(result)
+        load_topic_applicants(conn, round.match_round_id, &[topic.topic_id])
     })?;
 
-    Ok(HttpResponse::Ok().json(result))
+    Ok(HttpResponse::Ok().json(ApplicantsGetResponse { topics: result }))
 }
 
-#[get("/final_defenses")]
-pub async fn get_final_defenses(
+#[get("/matching_rounds/{match_round_id}/matching")]
+pub async fn preview_matching(
     pool: web::Data<DbPool>,
     session: Session,
+    match_round_id: web::Path<i32>,
 ) -> Result<HttpResponse, ApiError> {
-    use backend_database::schema::*;
-
     if !is_session_authed(&session) {
         return Err(ApiError::Unauthorized);
     }
 
-    let username = get_session_username(&session)
-        .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
     let user_role = get_session_user_role(&session)
         .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
-
-    match user_role {
-        AuthInfoUserRole::Student | AuthInfoUserRole::Teacher | AuthInfoUserRole::DefenseBoard => {}
-        _ => return Err(ApiError::Forbidden),
+    if !matches!(user_role, AuthInfoUserRole::Office) {
+        return Err(ApiError::Forbidden);
     }
 
     let mut conn = pool
         .get()
         .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
 
-    let mut base = finaldefense::table
-        .inner_join(student::table)
-        .inner_join(topic::table)
-        .into_boxed();
+    // Read-only transaction, so that the preview sees one consistent snapshot
+    let matches = conn.build_transaction().read_only().run(|conn| {
+        use backend_database::schema::*;
 
-    match user_role {
-        AuthInfoUserRole::Student => {
-            base = base.filter(finaldefense::columns::student_user_name.eq(&username));
-        }
-        AuthInfoUserRole::Teacher => {
-            base = base.filter(topic::columns::teacher_user_name.eq(&username));
-        }
-        AuthInfoUserRole::DefenseBoard => {
-            base = base.filter(finaldefense::columns::def_board_user_name.eq(&username));
+        let round = matchinground::dsl::matchinground
+            .find(*match_round_id)
+            .first::<MatchingRound>(conn)
+            .map_err(|_| ApiError::NotFound)?;
+        if round.match_round_commit_time.is_some() {
+            return Err(ApiError::Conflict(str!(
+                "Matching round has already been committed"
+            )));
         }
-        _ => unreachable!(),
-    }
-
-    let rows = base
-        .order(finaldefense::columns::final_def_time.desc())
-        .select((
-            finaldefense::all_columns,
-            student::columns::student_name,
-            topic::columns::topic_name,
-        ))
-        .load::<(FinalDefense, String, String)>(&mut conn)
-        .map_err(|_| ApiError::InternalServerError(str!("Failed to load final defenses")))?;
-
-    if rows.is_empty() {
-        return Err(ApiError::NotFound);
-    }
 
-    let defenses = rows
-        .into_iter()
-        .map(|(d, student_name, topic_name)| FinalDefenseDetails {
-            final_def_id: d.final_def_id,
-            topic_id: d.topic_id,
-            topic_name,
-            student_user_name: d.student_user_name,
-            student_name,
-            def_board_user_name: d.def_board_user_name,
-            final_def_time: d.final_def_time,
-            final_def_attachment: d.final_def_attachment,
-            final_def_outcome: d.final_def_outcome,
-            final_def_comment: d.final_def_comment,
-            final_def_grade: d.final_def_grade,
-        })
-        .collect::<Vec<_>>();
+        compute_matching(conn, round.match_round_id)
+    })?;
 
-    Ok(HttpResponse::Ok().json(FinalDefensesGetResponse { defenses }))
+    Ok(HttpResponse::Ok().json(MatchingGetResponse { matches }))
 }
 
-#[post("/final_defenses")]
-pub async fn create_final_defense(
+#[post("/matching_rounds/{match_round_id}/commit")]
+pub async fn commit_matching(
     pool: web::Data<DbPool>,
     session: Session,
-    req: web::Json<FinalDefensesPostRequest>,
+    match_round_id: web::Path<i32>,
 ) -> Result<HttpResponse, ApiError> {
     use backend_database::schema::*;
 
@@ -1724,75 +8518,158 @@ pub async fn create_final_defense(
         return Err(ApiError::Unauthorized);
     }
 
-    let username = get_session_username(&session)
-        .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
+    let user_role = get_session_user_role(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
+    if !matches!(user_role, AuthInfoUserRole::Office) {
+        // Only office can commit matching results
+        return Err(ApiError::Forbidden);
+    }
 
     let mut conn = pool
         .get()
         .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
 
-    conn.build_transaction().read_write().run(|conn| {
-        let student = student::dsl::student
-            .find(&username)
-            .first::<Student>(conn)
-            .map_err(|_| ApiError::NotFound)?;
-        let topic_id = student
-            .topic_id
-            .ok_or_else(|| ApiError::Conflict(str!("Student has no assigned topic")))?;
-
-        // Enforce: per student, at most one pending (NULL) and one passed (true).
-        let has_pending = diesel::select(diesel::dsl::exists(
-            finaldefense::dsl::finaldefense
-                .filter(finaldefense::columns::student_user_name.eq(&username))
-                .filter(finaldefense::columns::final_def_outcome.is_null()),
-        ))
-        .get_result::<bool>(conn)
-        .map_err(|_| ApiError::InternalServerError(str!("Failed to check pending defenses")))?;
-        if has_pending {
+    let matches = conn.build_transaction().read_write().run(|conn| {
+        let round = load_uncommitted_matching_round(conn, *match_round_id)?;
+        let now = Utc::now();
+        if now < round.match_round_close_time {
             return Err(ApiError::Conflict(str!(
-                "A pending final defense already exists"
+                "Matching round is still accepting preferences"
             )));
         }
 
-        let has_passed = diesel::select(diesel::dsl::exists(
-            finaldefense::dsl::finaldefense
-                .filter(finaldefense::columns::student_user_name.eq(&username))
-                .filter(finaldefense::columns::final_def_outcome.eq(true)),
-        ))
-        .get_result::<bool>(conn)
-        .map_err(|_| ApiError::InternalServerError(str!("Failed to check passed defenses")))?;
-        if has_passed {
-            return Err(ApiError::Conflict(str!(
-                "A passed final defense already exists"
-            )));
+        let matches = compute_matching(conn, round.match_round_id)?;
+        let matched_students = matches
+            .iter()
+            .filter_map(|entry| {
+                entry
+                    .topic_id
+                    .map(|topic_id| (&entry.student_user_name, topic_id))
+            })
+            .collect::<Vec<_>>();
+
+        let matched_student_user_names = matched_students
+            .iter()
+            .map(|(student_user_name, _)| student_user_name.as_str())
+            .collect::<Vec<_>>();
+        let matched_topic_ids = matched_students
+            .iter()
+            .map(|(_, topic_id)| *topic_id)
+            .collect::<Vec<_>>();
+        lock_placement_topics(conn, &matched_student_user_names, &matched_topic_ids)?;
+
+        // Matching only fills open places, but a teacher's quota may have been lowered since
+        let teacher_user_names = topic::table
+            .filter(topic::columns::topic_id.eq_any(&matched_topic_ids))
+            .select(topic::columns::teacher_user_name)
+            .distinct()
+            .load::<String>(conn)
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to load topic teachers")))?;
+        for teacher_user_name in &teacher_user_names {
+            check_teacher_quota(conn, teacher_user_name, 0, None)?;
         }
 
-        diesel::insert_into(finaldefense::dsl::finaldefense)
-            .values(NewFinalDefense {
-                topic_id,
-                student_user_name: &username,
-                def_board_user_name: None,
-                final_def_time: Utc::now(),
-                final_def_attachment: &req.attachment,
-                final_def_outcome: None,
-                final_def_comment: None,
-                final_def_grade: None,
+        for (student_user_name, topic_id) in &matched_students {
+            diesel::update(
+                student::table
+                    .filter(student::columns::user_name.eq(student_user_name))
+                    .filter(student::columns::topic_id.is_null()),
+            )
+            .set(StudentAssignmentChangeset {
+                topic_id: *topic_id,
+                assn_time: now,
             })
             .execute(conn)
-            .map_err(|_| ApiError::InternalServerError(str!("Failed to create final defense")))?;
+            .map_err(map_placement_error)?;
+        }
+
+        // Matched students no longer need their first-come-first-served requests
+        for (student_user_name, _) in &matched_students {
+            close_sibling_requests(conn, student_user_name, "Student was matched to a topic")?;
+        }
+
+        diesel::update(&round)
+            .set(matchinground::columns::match_round_commit_time.eq(Some(now)))
+            .execute(conn)
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to commit matching round")))?;
+
+        Ok::<_, ApiError>(matches)
+    })?;
+
+    Ok(HttpResponse::Ok().json(MatchingGetResponse { matches }))
+}
+
+#[get("/notifications")]
+pub async fn get_notifications(
+    pool: web::Data<DbPool>,
+    session: Session,
+    query: web::Query<PaginationQuery>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
+
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let username = get_session_username(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
+
+    let page = query.page.unwrap_or(1);
+    let page_size = query.page_size.unwrap_or(20);
+    if page < 1 || page_size < 1 {
+        return Err(ApiError::BadRequest(str!(
+            "Page and page size must be positive"
+        )));
+    }
+    let offset = (page - 1) * page_size;
+
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
 
-        Ok::<_, ApiError>(())
-    })?;
+    let own = notification::table.filter(notification::columns::user_name.eq(&username));
+    let total: i64 = own
+        .count()
+        .get_result(&mut conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to count notifications")))?;
+    let unread: i64 = own
+        .filter(notification::columns::notif_read_time.is_null())
+        .count()
+        .get_result(&mut conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to count notifications")))?;
+    let notifications = own
+        .order((
+            notification::columns::notif_time.desc(),
+            notification::columns::notif_id.desc(),
+        ))
+        .offset(offset)
+        .limit(page_size)
+        .load::<Notification>(&mut conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to load notifications")))?
+        .into_iter()
+        .map(|notif| NotificationDetails {
+            notif_id: notif.notif_id,
+            content: notif.notif_content,
+            time: notif.notif_time,
+            read_time: notif.notif_read_time,
+        })
+        .collect();
 
-    Ok(HttpResponse::Ok().finish())
+    Ok(HttpResponse::Ok().json(NotificationsGetResponse {
+        total,
+        unread,
+        page,
+        page_size,
+        notifications,
+    }))
 }
 
-#[patch("/final_defenses/{report_id}")]
-pub async fn update_final_defense(
+#[patch("/notifications/{notif_id}")]
+pub async fn update_notification(
     pool: web::Data<DbPool>,
     session: Session,
-    report_id: web::Path<i32>,
-    req: web::Json<FinalDefensesRecordPatchRequest>,
+    notif_id: web::Path<i32>,
+    req: web::Json<NotificationPatchRequest>,
 ) -> Result<HttpResponse, ApiError> {
     use backend_database::schema::*;
 
@@ -1802,229 +8679,144 @@ pub async fn update_final_defense(
 
     let username = get_session_username(&session)
         .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
-    let user_role = get_session_user_role(&session)
-        .map_err(|_| ApiError::InternalServerError(str!("Failed to get user role from session")))?;
 
     let mut conn = pool
         .get()
         .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
 
-    let result = conn.build_transaction().read_write().run(|conn| {
-        match (user_role, req.into_inner()) {
-            (AuthInfoUserRole::Teacher, FinalDefensesRecordPatchRequest::Teacher(req)) => {
-                // Teacher reviews the request: reject -> delete; approve -> assign least-loaded defense group.
-                let (defense, student_name, topic_name, teacher_username): (
-                    FinalDefense,
-                    String,
-                    String,
-                    String,
-                ) = finaldefense::table
-                    .inner_join(student::table)
-                    .inner_join(topic::table)
-                    .filter(finaldefense::columns::final_def_id.eq(*report_id))
-                    .select((
-                        finaldefense::all_columns,
-                        student::columns::student_name,
-                        topic::columns::topic_name,
-                        topic::columns::teacher_user_name,
-                    ))
-                    .first(conn)
-                    .map_err(|_| ApiError::NotFound)?;
-
-                if teacher_username != username {
-                    return Err(ApiError::Forbidden);
-                }
-
-                // Only allow reviewing pending applications.
-                if defense.final_def_outcome.is_some() {
-                    return Err(ApiError::Conflict(str!("Final defense already finalized")));
-                }
+    let read_time = req.read.then(Utc::now);
+    let updated = diesel::update(
+        notification::table
+            .filter(notification::columns::notif_id.eq(*notif_id))
+            .filter(notification::columns::user_name.eq(&username)),
+    )
+    .set(notification::columns::notif_read_time.eq(read_time))
+    .execute(&mut conn)
+    .map_err(|_| ApiError::InternalServerError(str!("Failed to update notification")))?;
+    if updated == 0 {
+        return Err(ApiError::NotFound);
+    }
 
-                if !req.approved {
-                    diesel::delete(finaldefense::dsl::finaldefense.find(defense.final_def_id))
-                        .execute(conn)
-                        .map_err(|_| {
-                            ApiError::InternalServerError(str!("Failed to delete final defense"))
-                        })?;
+    Ok(HttpResponse::Ok().finish())
+}
 
-                    // Return details of the deleted record (pre-delete).
-                    return Ok(FinalDefenseDetails {
-                        final_def_id: defense.final_def_id,
-                        topic_id: defense.topic_id,
-                        topic_name,
-                        student_user_name: defense.student_user_name,
-                        student_name,
-                        def_board_user_name: defense.def_board_user_name,
-                        final_def_time: defense.final_def_time,
-                        final_def_attachment: defense.final_def_attachment,
-                        final_def_outcome: defense.final_def_outcome,
-                        final_def_comment: defense.final_def_comment,
-                        final_def_grade: defense.final_def_grade,
-                    });
-                }
+#[post("/attachments")]
+pub async fn upload_attachment(
+    pool: web::Data<DbPool>,
+    session: Session,
+    MultipartForm(form): MultipartForm<AttachmentUploadForm>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
 
-                if defense.def_board_user_name.is_some() {
-                    return Err(ApiError::Conflict(str!("Final defense already assigned")));
-                }
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
 
-                // Choose the defense group with the least number of pending tasks.
-                // (Pending task = assigned to a defense group AND final outcome is NULL.)
-                // We compute counts via GROUP BY, then pick the minimum in Rust to keep the Diesel types simple.
+    let username = get_session_username(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
 
-                let defense_board_usernames = defenseboard::dsl::defenseboard
-                    .select(defenseboard::columns::user_name)
-                    .order(defenseboard::columns::user_name.asc())
-                    .load::<String>(conn)
-                    .map_err(|_| {
-                        ApiError::InternalServerError(str!("Failed to load defense boards"))
-                    })?;
-                if defense_board_usernames.is_empty() {
-                    return Err(ApiError::Conflict(str!("No defense board available")));
-                }
+    // Reading, hashing and storing an upload of up to the size limit runs off the worker threads.
+    // The temporary file lives in `form` until the content is stored.
+    let upload_path = form.file.file.path().to_owned();
+    let (mime_type, sha256, size) = web::block(move || {
+        let content = std::fs::read(upload_path)
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to read upload")))?;
+        let mime_type = crate::attachment::sniff_mime_type(&content).ok_or_else(|| {
+            ApiError::BadRequest(str!("Only PDF, DOCX and ZIP files are accepted"))
+        })?;
+        let sha256 = crate::attachment::store(&content)
+            .map_err(|_| ApiError::InternalServerError(str!("Failed to store attachment")))?;
+        Ok::<_, ApiError>((mime_type, sha256, content.len() as i64))
+    })
+    .await
+    .map_err(|_| ApiError::InternalServerError(str!("Failed to store attachment")))??;
+    let file_name = crate::attachment::clean_file_name(form.file.file_name.as_deref());
 
-                let pending_counts = finaldefense::dsl::finaldefense
-                    .filter(finaldefense::columns::final_def_outcome.is_null())
-                    .filter(finaldefense::columns::def_board_user_name.is_not_null())
-                    .group_by(finaldefense::columns::def_board_user_name)
-                    .select((
-                        finaldefense::columns::def_board_user_name,
-                        diesel::dsl::count_star(),
-                    ))
-                    .load::<(Option<String>, i64)>(conn)
-                    .map_err(|_| {
-                        ApiError::InternalServerError(str!("Failed to count defense board tasks"))
-                    })?;
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
 
-                let mut count_by_board: HashMap<String, i64> = HashMap::new();
-                for (board_username, c) in pending_counts {
-                    if let Some(board_username) = board_username {
-                        count_by_board.insert(board_username, c);
-                    }
-                }
+    let attach = diesel::insert_into(attachment::table)
+        .values(NewAttachment {
+            uploader_user_name: &username,
+            attach_file_name: &file_name,
+            attach_mime_type: mime_type,
+            attach_size: size,
+            attach_sha256: &sha256,
+            attach_upload_time: Utc::now(),
+        })
+        .get_result::<Attachment>(&mut conn)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to record attachment")))?;
 
-                let mut assigned_defense_board_username = defense_board_usernames[0].clone();
-                let mut assigned_count = *count_by_board
-                    .get(&assigned_defense_board_username)
-                    .unwrap_or(&0);
-                for board_username in defense_board_usernames.into_iter().skip(1) {
-                    let c = *count_by_board.get(&board_username).unwrap_or(&0);
-                    if c < assigned_count {
-                        assigned_defense_board_username = board_username;
-                        assigned_count = c;
-                    }
-                }
+    Ok(HttpResponse::Created().json(build_attachment_details(attach)))
+}
 
-                diesel::update(finaldefense::dsl::finaldefense.find(defense.final_def_id))
-                    .set(
-                        finaldefense::columns::def_board_user_name
-                            .eq(Some(assigned_defense_board_username)),
-                    )
-                    .execute(conn)
-                    .map_err(|_| {
-                        ApiError::InternalServerError(str!("Failed to assign defense board"))
-                    })?;
+#[get("/attachments/{attach_id}")]
+pub async fn download_attachment(
+    http_req: HttpRequest,
+    pool: web::Data<DbPool>,
+    session: Session,
+    attach_id: web::Path<i32>,
+) -> Result<HttpResponse, ApiError> {
+    use backend_database::schema::*;
 
-                let updated = finaldefense::dsl::finaldefense
-                    .find(defense.final_def_id)
-                    .first::<FinalDefense>(conn)
-                    .map_err(|_| ApiError::NotFound)?;
+    if !is_session_authed(&session) {
+        return Err(ApiError::Unauthorized);
+    }
 
-                Ok(FinalDefenseDetails {
-                    final_def_id: updated.final_def_id,
-                    topic_id: updated.topic_id,
-                    topic_name,
-                    student_user_name: updated.student_user_name,
-                    student_name,
-                    def_board_user_name: updated.def_board_user_name,
-                    final_def_time: updated.final_def_time,
-                    final_def_attachment: updated.final_def_attachment,
-                    final_def_outcome: updated.final_def_outcome,
-                    final_def_comment: updated.final_def_comment,
-                    final_def_grade: updated.final_def_grade,
-                })
-            }
-            (
-                AuthInfoUserRole::DefenseBoard,
-                FinalDefensesRecordPatchRequest::DefenseBoard(req),
-            ) => {
-                let (defense, student_name, topic_name): (FinalDefense, String, String) =
-                    finaldefense::table
-                        .inner_join(student::table)
-                        .inner_join(topic::table)
-                        .filter(finaldefense::columns::final_def_id.eq(*report_id))
-                        .select((
-                            finaldefense::all_columns,
-                            student::columns::student_name,
-                            topic::columns::topic_name,
-                        ))
-                        .first(conn)
-                        .map_err(|_| ApiError::NotFound)?;
+    let is_admin = is_session_admin(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Can't deserialize auth info")))?;
 
-                if defense.def_board_user_name != Some(username) {
-                    return Err(ApiError::Forbidden);
-                }
+    let username = get_session_username(&session)
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get username from session")))?;
+    let user_role = if is_admin {
+        None
+    } else {
+        Some(get_session_user_role(&session).map_err(|_| {
+            ApiError::InternalServerError(str!("Failed to get user role from session"))
+        })?)
+    };
 
-                // Enforce: per student, at most one passed (true).
-                if req.outcome {
-                    let other_passed = diesel::select(diesel::dsl::exists(
-                        finaldefense::dsl::finaldefense
-                            .filter(
-                                finaldefense::columns::student_user_name
-                                    .eq(&defense.student_user_name),
-                            )
-                            .filter(finaldefense::columns::final_def_outcome.eq(true))
-                            .filter(finaldefense::columns::final_def_id.ne(defense.final_def_id)),
-                    ))
-                    .get_result::<bool>(conn)
-                    .map_err(|_| {
-                        ApiError::InternalServerError(str!("Failed to check passed defenses"))
-                    })?;
-                    if other_passed {
-                        return Err(ApiError::Conflict(str!(
-                            "Another passed final defense already exists"
-                        )));
-                    }
-                }
+    let mut conn = pool
+        .get()
+        .map_err(|_| ApiError::InternalServerError(str!("Failed to get database connection")))?;
 
-                diesel::update(finaldefense::dsl::finaldefense.find(defense.final_def_id))
-                    .set((
-                        finaldefense::columns::final_def_outcome.eq(Some(req.outcome)),
-                        finaldefense::columns::final_def_comment.eq(Some(req.comment.clone())),
-                        finaldefense::columns::final_def_grade.eq(Some(req.grade.clone())),
-                    ))
-                    .execute(conn)
-                    .map_err(|_| {
-                        ApiError::InternalServerError(str!("Failed to update final defense"))
-                    })?;
+    let attach = attachment::table
+        .find(*attach_id)
+        .first::<Attachment>(&mut conn)
+        .map_err(|_| ApiError::NotFound)?;
+    // Admins list progress reports as office does, and may download the same files: those
+    // submitted with progress reports or referenced in their discussions
+    let visible = can_view_attachment(
+        &mut conn,
+        &attach,
+        &username,
+        user_role.as_ref().unwrap_or(&AuthInfoUserRole::Office),
+    )?;
+    if !visible {
+        return Err(ApiError::Forbidden);
+    }
 
-                let updated = finaldefense::dsl::finaldefense
-                    .find(defense.final_def_id)
-                    .first::<FinalDefense>(conn)
-                    .map_err(|_| ApiError::NotFound)?;
+    let mut parameters = vec![DispositionParam::Filename(attach.attach_file_name.clone())];
+    if !attach.attach_file_name.is_ascii() {
+        parameters.push(DispositionParam::FilenameExt(ExtendedValue {
+            charset: Charset::Ext(str!("UTF-8")),
+            language_tag: None,
+            value: attach.attach_file_name.into_bytes(),
+        }));
+    }
 
-                Ok(FinalDefenseDetails {
-                    final_def_id: updated.final_def_id,
-                    topic_id: updated.topic_id,
-                    topic_name,
-                    student_user_name: updated.student_user_name,
-                    student_name,
-                    def_board_user_name: updated.def_board_user_name,
-                    final_def_time: updated.final_def_time,
-                    final_def_attachment: updated.final_def_attachment,
-                    final_def_outcome: updated.final_def_outcome,
-                    final_def_comment: updated.final_def_comment,
-                    final_def_grade: updated.final_def_grade,
-                })
-            }
-            (AuthInfoUserRole::Teacher, _) => Err(ApiError::BadRequest(str!(
-                "Teacher patch request must be { approved: bool }"
-            ))),
-            (AuthInfoUserRole::DefenseBoard, _) => Err(ApiError::BadRequest(str!(
-                "Defense board patch request must be { outcome, comment, grade }"
-            ))),
-            _ => Err(ApiError::Forbidden),
-        }
-    })?;
+    let mime_type = attach
+        .attach_mime_type
+        .parse()
+        .map_err(|_| ApiError::InternalServerError(str!("Invalid attachment type")))?;
+    let file = NamedFile::open(crate::attachment::content_path(&attach.attach_sha256))
+        .map_err(|_| ApiError::InternalServerError(str!("Attachment content is missing")))?
+        .set_content_type(mime_type)
+        .set_content_disposition(ContentDisposition {
+            disposition: DispositionType::Attachment,
+            parameters,
+        });
 
-    Ok(HttpResponse::Ok().json(result))
+    Ok(file.into_response(&http_req))
 }
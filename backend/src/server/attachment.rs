@@ -0,0 +1,122 @@
+//! Content-addressed storage of attachments on local disk.
+//!
+//! Each distinct content is stored once, under the hex SHA-256 digest of its bytes, in a
+//! subdirectory named after the first two digits of the digest.
+
+use anyhow::Result as AnyResult;
+use backend_database::DbPool;
+use diesel::prelude::*;
+use dotenvy_macro::dotenv;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use str_macro::str;
+
+/// MIME types accepted for uploads, as sniffed from their content
+const ACCEPTED_MIME_TYPES: &[&str] = &[
+    "application/pdf",
+    "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+    "application/zip",
+];
+
+/// Returns the largest upload accepted, in bytes.
+pub fn max_upload_bytes() -> usize {
+    dotenv!("ATTACHMENT_MAX_BYTES")
+        .parse()
+        .expect("ATTACHMENT_MAX_BYTES must be a number of bytes")
+}
+
+/// Sniffs the MIME type of an upload from its leading bytes, returning `None` unless it is a
+/// PDF, DOCX or ZIP file.
+pub fn sniff_mime_type(content: &[u8]) -> Option<&'static str> {
+    let mime_type = infer::get(content)?.mime_type();
+    ACCEPTED_MIME_TYPES
+        .iter()
+        .copied()
+        .find(|accepted| *accepted == mime_type)
+}
+
+/// Reduces a client-supplied file name to its last path component without control
+/// characters, at most 255 characters long.
+pub fn clean_file_name(file_name: Option<&str>) -> String {
+    let file_name = file_name
+        .and_then(|name| name.rsplit(['/', '\\']).next())
+        .unwrap_or_default()
+        .chars()
+        .filter(|c| !c.is_control())
+        .collect::<String>();
+    let file_name = file_name.trim();
+    if file_name.is_empty() {
+        return str!("attachment");
+    }
+    file_name.chars().take(255).collect()
+}
+
+/// Returns the hex SHA-256 digest of some content.
+pub fn sha256_hex(content: &[u8]) -> String {
+    hex::encode(Sha256::digest(content))
+}
+
+/// Returns where the content with the given digest is stored.
+pub fn content_path(sha256: &str) -> PathBuf {
+    Path::new(dotenv!("ATTACHMENT_STORE_PATH"))
+        .join(&sha256[..2])
+        .join(sha256)
+}
+
+/// Writes content into the store unless it is already there, returning its digest.
+pub fn store(content: &[u8]) -> io::Result<String> {
+    static NEXT_PART: AtomicU64 = AtomicU64::new(0);
+
+    let sha256 = sha256_hex(content);
+    let path = content_path(&sha256);
+    if path.exists() {
+        return Ok(sha256);
+    }
+
+    let dir = path
+        .parent()
+        .expect("Stored contents always have a parent directory");
+    fs::create_dir_all(dir)?;
+    // Write under a unique name first so that readers never see a partial file
+    let part = dir.join(format!(
+        "{}.{}.{}.part",
+        sha256,
+        std::process::id(),
+        NEXT_PART.fetch_add(1, Ordering::Relaxed)
+    ));
+    fs::write(&part, content)?;
+    fs::rename(&part, &path)?;
+
+    Ok(sha256)
+}
+
+/// Moves contents imported from the former inline attachment columns into the store, one at a
+/// time so that only a single content is held in memory.
+pub fn flush_legacy_contents(pool: &DbPool) -> AnyResult<()> {
+    use backend_database::schema::*;
+
+    let mut conn = pool.get()?;
+    let legacy_ids = attachment::table
+        .filter(attachment::columns::attach_legacy_content.is_not_null())
+        .order(attachment::columns::attach_id.asc())
+        .select(attachment::columns::attach_id)
+        .load::<i32>(&mut conn)?;
+    for attach_id in legacy_ids {
+        let content = attachment::table
+            .find(attach_id)
+            .select(attachment::columns::attach_legacy_content)
+            .first::<Option<Vec<u8>>>(&mut conn)?;
+        let Some(content) = content else {
+            continue;
+        };
+        store(&content)?;
+        diesel::update(attachment::table.find(attach_id))
+            .set(attachment::columns::attach_legacy_content.eq(None::<Vec<u8>>))
+            .execute(&mut conn)?;
+    }
+
+    Ok(())
+}
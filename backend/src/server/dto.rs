@@ -1,3 +1,4 @@
+use actix_multipart::form::{MultipartForm, tempfile::TempFile};
 use actix_web::{HttpResponse, ResponseError};
 use bigdecimal::BigDecimal;
 use chrono::{DateTime, Utc};
@@ -24,6 +25,31 @@ pub enum UserRole {
     Office = 4,
 }
 
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize_repr,
+    Deserialize_repr,
+    IntoPrimitive,
+    TryFromPrimitive,
+)]
+#[repr(i16)]
+pub enum TeacherTitle {
+    /// 0: 助教
+    TeachingAssistant = 0,
+    /// 1: 讲师
+    Lecturer = 1,
+    /// 2: 副教授
+    AssociateProfessor = 2,
+    /// 3: 教授
+    Professor = 3,
+    /// 4: 其他
+    Other = 4,
+}
+
 #[derive(
     Debug,
     Clone,
@@ -82,11 +108,34 @@ pub enum TopicReviewStatus {
     TryFromPrimitive,
 )]
 #[repr(i16)]
-pub enum ProgressReportType {
-    /// 0: 开题报告
-    Proposal = 0,
-    /// 1: 中期检查
-    Midterm = 1,
+pub enum TopicReviewOutcome {
+    /// 0: 已生效
+    Applied = 0,
+    /// 1: 课题不存在
+    NotFound = 1,
+    /// 2: 课题在加载后已被修改
+    Stale = 2,
+    /// 3: 审核决定无效
+    Invalid = 3,
+}
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize_repr,
+    Deserialize_repr,
+    IntoPrimitive,
+    TryFromPrimitive,
+)]
+#[repr(i16)]
+pub enum StageReviewer {
+    /// 0: 指导教师
+    Supervisor = 0,
+    /// 1: 教务处
+    Office = 1,
 }
 
 #[derive(
@@ -110,6 +159,50 @@ pub enum ProgressOutcome {
     Rejected = 2,
 }
 
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize_repr,
+    Deserialize_repr,
+    IntoPrimitive,
+    TryFromPrimitive,
+)]
+#[repr(i16)]
+pub enum SubmissionFileKind {
+    /// 0: 论文
+    Thesis = 0,
+    /// 1: 演示文稿
+    Slides = 1,
+    /// 2: 源代码
+    Source = 2,
+    /// 3: 其他
+    Other = 3,
+}
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize_repr,
+    Deserialize_repr,
+    IntoPrimitive,
+    TryFromPrimitive,
+)]
+#[repr(i16)]
+pub enum TopicProposalStatus {
+    /// 0: 待处理
+    Pending = 0,
+    /// 1: 已接受
+    Accepted = 1,
+    /// 2: 已拒绝
+    Declined = 2,
+}
+
 #[derive(
     Debug,
     Clone,
@@ -129,6 +222,8 @@ pub enum AssignmentStatus {
     Approved = 1,
     /// 2: 已拒绝
     Rejected = 2,
+    /// 3: 已撤回
+    Withdrawn = 3,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -143,6 +238,7 @@ pub struct UserGetResponse {
     pub role: UserRole,
     pub name: Option<String>,
     pub avatar: Option<String>,
+    pub title: Option<TeacherTitle>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -160,6 +256,7 @@ pub struct UserPostRequest {
     pub name: Option<String>,
     pub major_id: Option<i32>,
     pub avatar: Option<String>,
+    pub title: Option<TeacherTitle>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -171,6 +268,8 @@ pub struct TopicBrief {
     pub topic_type: TopicType,
     pub topic_review_status: TopicReviewStatus,
     pub current_student_count: i32,
+    pub tags: Vec<String>,
+    pub topic_version: i32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -186,6 +285,22 @@ pub struct TopicDetails {
     pub topic_type: TopicType,
     pub topic_review_status: TopicReviewStatus,
     pub current_student_count: i32,
+    pub tags: Vec<String>,
+    pub cosupervisors: Vec<CoSupervisorDetails>,
+    pub topic_version: i32,
+    pub topic_review_comment: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoSupervisorDetails {
+    pub teacher_user_name: String,
+    pub teacher_name: String,
+    pub can_approve: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoSupervisorPutRequest {
+    pub can_approve: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -203,6 +318,7 @@ pub struct TopicsPostRequest {
     pub topic_description: String,
     pub topic_max_students: i32,
     pub topic_type: TopicType,
+    pub tags: Option<Vec<String>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -212,6 +328,46 @@ pub struct TopicPatchRequest {
     pub topic_max_students: Option<i32>,
     pub topic_type: Option<TopicType>,
     pub topic_review_status: Option<TopicReviewStatus>,
+    pub topic_review_comment: Option<String>,
+    pub tags: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TopicReviewDecision {
+    pub topic_id: i32,
+    pub topic_review_status: TopicReviewStatus,
+    pub topic_review_comment: Option<String>,
+    /// Version of the topic the reviewer loaded
+    pub topic_version: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TopicsReviewPostRequest {
+    pub reviews: Vec<TopicReviewDecision>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TopicReviewResult {
+    pub topic_id: i32,
+    pub outcome: TopicReviewOutcome,
+    /// Current version of the topic, if it exists
+    pub topic_version: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TopicsReviewPostResponse {
+    pub results: Vec<TopicReviewResult>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagCount {
+    pub tag_name: String,
+    pub topic_count: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagsGetResponse {
+    pub tags: Vec<TagCount>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -219,6 +375,159 @@ pub struct TopicCreateResponse {
     pub topic_id: i32,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TopicProposalDetails {
+    pub prop_id: i32,
+    pub student_user_name: String,
+    pub student_name: String,
+    pub teacher_user_name: String,
+    pub teacher_name: String,
+    pub topic_name: String,
+    pub topic_description: String,
+    pub topic_type: TopicType,
+    pub prop_status: TopicProposalStatus,
+    pub prop_time: DateTime<Utc>,
+    pub prop_decided_time: Option<DateTime<Utc>>,
+    pub prop_decline_reason: Option<String>,
+    /// Topic created on acceptance
+    pub topic_id: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TopicProposalsGetResponse {
+    pub proposals: Vec<TopicProposalDetails>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TopicProposalsPostRequest {
+    pub teacher_user_name: String,
+    pub topic_name: String,
+    pub topic_description: String,
+    pub topic_type: TopicType,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TopicProposalPatchRequest {
+    pub accepted: bool,
+    /// Required when declining
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchingRoundDetails {
+    pub match_round_id: i32,
+    pub match_round_name: String,
+    /// Maximum number of preferences a student may list
+    pub max_preferences: i16,
+    pub open_time: DateTime<Utc>,
+    pub close_time: DateTime<Utc>,
+    /// Time the matching result was written to student assignments
+    pub commit_time: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchingRoundsGetResponse {
+    pub rounds: Vec<MatchingRoundDetails>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchingRoundsPostRequest {
+    pub match_round_name: String,
+    pub max_preferences: i16,
+    pub open_time: DateTime<Utc>,
+    pub close_time: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreferenceDetails {
+    pub pref_rank: i16,
+    pub topic_id: i32,
+    pub topic_name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreferencesGetResponse {
+    pub preferences: Vec<PreferenceDetails>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreferencesPutRequest {
+    /// Topics in order of preference, most preferred first
+    pub topic_ids: Vec<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApplicantDetails {
+    pub student_user_name: String,
+    pub student_name: String,
+    /// Rank the student gave to the topic
+    pub pref_rank: i16,
+    /// Rank the teacher gave to the student, if ranked
+    pub appl_rank: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TopicApplicants {
+    pub topic_id: i32,
+    pub topic_name: String,
+    pub topic_max_students: i32,
+    pub applicants: Vec<ApplicantDetails>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApplicantsGetResponse {
+    pub topics: Vec<TopicApplicants>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RankingsPutRequest {
+    /// Applicants in order of preference, most preferred first
+    pub student_user_names: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchingEntry {
+    pub student_user_name: String,
+    pub student_name: String,
+    /// Matched topic, or `None` if the student could not be placed
+    pub topic_id: Option<i32>,
+    pub topic_name: Option<String>,
+    /// Rank the student gave to the matched topic
+    pub pref_rank: Option<i16>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchingGetResponse {
+    pub matches: Vec<MatchingEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SupervisionQuotaDetails {
+    pub teacher_title: TeacherTitle,
+    pub quota_max_students: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SupervisionQuotasGetResponse {
+    pub quotas: Vec<SupervisionQuotaDetails>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SupervisionQuotaPutRequest {
+    pub quota_max_students: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeacherTitleDetails {
+    pub teacher_user_name: String,
+    pub teacher_title: TeacherTitle,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeacherTitlePutRequest {
+    pub teacher_title: TeacherTitle,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Assignment {
     pub student_user_name: String,
@@ -228,6 +537,11 @@ pub struct Assignment {
     pub topic_name: String,
     pub request_time: DateTime<Utc>,
     pub status: AssignmentStatus,
+    /// Time the request was decided or withdrawn; empty while pending
+    pub decided_time: Option<DateTime<Utc>>,
+    /// Teacher who decided the request; empty while pending, withdrawn or closed by the system
+    pub decided_by: Option<String>,
+    pub reason: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -246,6 +560,323 @@ pub struct AssignmentsPostRequest {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AssignmentRecordPatchRequest {
     pub approved: bool,
+    /// Optional explanation shown to the student
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StudentTopicPutRequest {
+    pub topic_id: i32,
+    pub reason: String,
+    /// Place the student even if the topic or its teacher's quota is full
+    pub override_capacity: Option<bool>,
+    /// Move progress reports and final defenses from the previous topic to the new one
+    pub move_records: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StudentTopicDeleteRequest {
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssignmentChangeDetails {
+    pub assn_change_id: i32,
+    pub student_user_name: String,
+    pub office_user_name: String,
+    pub old_topic_id: Option<i32>,
+    pub new_topic_id: Option<i32>,
+    pub reason: String,
+    pub time: DateTime<Utc>,
+    pub overrode_capacity: bool,
+    pub moved_records: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssignmentChangesGetResponse {
+    pub changes: Vec<AssignmentChangeDetails>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WaitlistEntryDetails {
+    pub student_user_name: String,
+    pub student_name: String,
+    /// 1-based position in the waitlist
+    pub position: i64,
+    pub join_time: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TopicWaitlistGetResponse {
+    pub topic_id: i32,
+    pub entries: Vec<WaitlistEntryDetails>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationDetails {
+    pub notif_id: i32,
+    pub content: String,
+    pub time: DateTime<Utc>,
+    pub read_time: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationsGetResponse {
+    pub total: i64,
+    pub unread: i64,
+    pub page: i64,
+    pub page_size: i64,
+    pub notifications: Vec<NotificationDetails>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationPatchRequest {
+    pub read: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelectionRoundDetails {
+    pub sel_round_id: i32,
+    pub sel_round_name: String,
+    pub open_time: DateTime<Utc>,
+    pub close_time: DateTime<Utc>,
+    /// Maximum number of requests a student may file in this round, whatever their outcome
+    pub max_requests: i16,
+    pub major_ids: Vec<i32>,
+    /// Round whose unassigned students this round takes over
+    pub carried_over_from: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelectionRoundsGetResponse {
+    pub rounds: Vec<SelectionRoundDetails>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelectionRoundsPostRequest {
+    pub sel_round_name: String,
+    pub open_time: DateTime<Utc>,
+    pub close_time: DateTime<Utc>,
+    pub max_requests: i16,
+    /// Majors of `carry_over_from` that still have students without a topic are always added
+    pub major_ids: Option<Vec<i32>>,
+    pub carry_over_from: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelectionRoundPatchRequest {
+    pub sel_round_name: Option<String>,
+    pub open_time: Option<DateTime<Utc>>,
+    pub close_time: Option<DateTime<Utc>>,
+    pub max_requests: Option<i16>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportStageDetails {
+    pub stage_id: i16,
+    pub stage_name: String,
+    /// Stages are listed, and offered to students, in ascending order
+    pub order: i32,
+    pub reviewer: StageReviewer,
+    /// Whether a report must be graded to pass this stage
+    pub grade_required: bool,
+    /// Stages a student must have passed on their topic before submitting for this one
+    pub prerequisites: Vec<i16>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportStagesGetResponse {
+    pub stages: Vec<ReportStageDetails>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportStagesPostRequest {
+    pub stage_name: String,
+    pub order: i32,
+    pub reviewer: StageReviewer,
+    pub grade_required: bool,
+    pub prerequisites: Option<Vec<i16>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportStagePatchRequest {
+    pub stage_name: Option<String>,
+    pub order: Option<i32>,
+    pub reviewer: Option<StageReviewer>,
+    pub grade_required: Option<bool>,
+    /// Replaces the stage's prerequisites when present
+    pub prerequisites: Option<Vec<i16>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RubricCriterionDetails {
+    pub criterion_id: i32,
+    pub name: String,
+    pub weight: BigDecimal,
+    pub max_score: BigDecimal,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RubricDetails {
+    /// The report stage this rubric grades; absent for the final defense
+    pub stage_id: Option<i16>,
+    pub criteria: Vec<RubricCriterionDetails>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RubricsGetResponse {
+    pub rubrics: Vec<RubricDetails>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RubricCriterionRequest {
+    pub name: String,
+    pub weight: BigDecimal,
+    pub max_score: BigDecimal,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RubricPutRequest {
+    pub stage_id: Option<i16>,
+    /// Replaces the whole rubric, in order; an empty list removes it
+    pub criteria: Vec<RubricCriterionRequest>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CriterionScoreDetails {
+    pub criterion_id: i32,
+    pub criterion_name: String,
+    pub max_score: BigDecimal,
+    pub score: BigDecimal,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CriterionScoreRequest {
+    pub criterion_id: i32,
+    pub score: BigDecimal,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadlineExtensionDetails {
+    pub student_user_name: String,
+    pub student_name: String,
+    pub due_time: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadlineDetails {
+    pub deadline_id: i32,
+    pub major_id: i32,
+    /// The report stage this deadline applies to; absent for the final defense
+    pub stage_id: Option<i16>,
+    pub due_time: DateTime<Utc>,
+    /// Whether submissions past the deadline are refused instead of being marked late
+    pub is_hard: bool,
+    /// Students see only their own extension
+    pub extensions: Vec<DeadlineExtensionDetails>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadlinesGetResponse {
+    pub deadlines: Vec<DeadlineDetails>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadlinesPostRequest {
+    pub major_id: i32,
+    pub stage_id: Option<i16>,
+    pub due_time: DateTime<Utc>,
+    pub is_hard: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadlinePatchRequest {
+    pub due_time: Option<DateTime<Utc>>,
+    pub is_hard: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadlineExtensionPutRequest {
+    pub due_time: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RevisionLockDetails {
+    pub major_id: i32,
+    /// Concluded report outcomes of the major's students can no longer be revised from then on
+    pub lock_time: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RevisionLocksGetResponse {
+    pub locks: Vec<RevisionLockDetails>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RevisionLockPutRequest {
+    pub lock_time: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimilarityDocumentDetails {
+    pub student_user_name: String,
+    pub student_name: String,
+    /// Progress report ID, or final defense ID when checking final defenses
+    pub submission_id: i32,
+    pub attachment: AttachmentDetails,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimilarityPairDetails {
+    pub first: SimilarityDocumentDetails,
+    pub second: SimilarityDocumentDetails,
+    /// Estimated share of common passages, from 0 to 1
+    pub similarity: f64,
+    /// Longest passages of the first document that also occur in the second
+    pub passages: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimilarityReportResponse {
+    /// Stage checked, or `None` for final defenses
+    pub stage_id: Option<i16>,
+    pub threshold: f64,
+    /// Number of documents compared
+    pub checked: i64,
+    /// Documents whose text could not be extracted, or too short to compare
+    pub unreadable: Vec<SimilarityDocumentDetails>,
+    /// Pairs at or above the threshold, most similar first
+    pub pairs: Vec<SimilarityPairDetails>,
+}
+
+#[derive(MultipartForm)]
+pub struct AttachmentUploadForm {
+    pub file: TempFile,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttachmentDetails {
+    pub attach_id: i32,
+    pub file_name: String,
+    /// MIME type sniffed from the content
+    pub mime_type: String,
+    pub size: i64,
+    /// Hex SHA-256 digest of the content
+    pub sha256: String,
+    pub upload_time: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubmissionFileDetails {
+    pub kind: SubmissionFileKind,
+    pub attachment: AttachmentDetails,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubmissionFileRequest {
+    pub kind: SubmissionFileKind,
+    /// An attachment uploaded by the student through `POST /attachments`
+    pub attachment_id: i32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -254,29 +885,91 @@ pub struct ProgressReportDetailResponse {
     pub topic_id: i32,
     pub student_user_name: String,
     pub student_name: String,
-    pub prog_report_type: ProgressReportType,
+    pub prog_report_stage_id: i16,
+    pub prog_report_stage_name: String,
     pub prog_report_time: DateTime<Utc>,
-    pub prog_report_attachment: String,
+    /// Counts the student's submissions for this stage of the topic, starting from 1
+    pub prog_report_version: i32,
+    pub prog_report_files: Vec<SubmissionFileDetails>,
+    /// The student's deadline for this stage when the report was submitted
+    pub prog_report_due_time: Option<DateTime<Utc>>,
+    pub prog_report_late: bool,
     pub prog_report_outcome: ProgressOutcome,
     pub prog_report_comment: Option<String>,
     pub prog_report_grade: Option<BigDecimal>,
+    pub prog_report_scores: Vec<CriterionScoreDetails>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProgressReportsGetResponse {
+    pub total: i64,
+    pub page: i64,
+    pub page_size: i64,
     pub reports: Vec<ProgressReportDetailResponse>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProgressReportsPostRequest {
-    pub attachment: String,
+    /// Defaults to the earliest stage, in stage order, that the student has yet to pass and
+    /// whose prerequisites they have all passed
+    pub stage_id: Option<i16>,
+    pub files: Vec<SubmissionFileRequest>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProgressReportsPostResponse {
+    pub prog_report_id: i32,
+    pub prog_report_version: i32,
+    pub prog_report_due_time: Option<DateTime<Utc>>,
+    pub prog_report_late: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProgressReportRecordPatchRequest {
     pub outcome: ProgressOutcome,
     pub comment: Option<String>,
+    /// Only for stages without a rubric
     pub grade: Option<BigDecimal>,
+    /// Scores for every criterion of the stage's rubric, from which the grade is computed
+    pub scores: Option<Vec<CriterionScoreRequest>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProgressReportRevisionDetails {
+    pub revision_id: i32,
+    pub prog_report_id: i32,
+    pub reviser_user_name: String,
+    pub reviser_name: Option<String>,
+    /// The revision of a prerequisite report that invalidated this one, if any
+    pub cause_id: Option<i32>,
+    pub reason: String,
+    pub time: DateTime<Utc>,
+    pub old_outcome: ProgressOutcome,
+    pub old_comment: Option<String>,
+    pub old_grade: Option<BigDecimal>,
+    pub new_outcome: ProgressOutcome,
+    pub new_comment: Option<String>,
+    pub new_grade: Option<BigDecimal>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProgressReportRevisionsGetResponse {
+    pub revisions: Vec<ProgressReportRevisionDetails>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProgressReportRevisionsPostRequest {
+    pub outcome: ProgressOutcome,
+    pub reason: String,
+    /// Replaces the review comment when present
+    pub comment: Option<String>,
+    /// Only for stages without a rubric
+    pub grade: Option<BigDecimal>,
+    /// Scores for every criterion of the stage's rubric, from which the grade is computed
+    pub scores: Option<Vec<CriterionScoreRequest>>,
+    /// Reject the reports of later stages that relied on this one passing; without it, such
+    /// a revision is refused
+    pub invalidate_dependents: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -288,20 +981,65 @@ pub struct FinalDefenseDetails {
     pub student_name: String,
     pub def_board_user_name: Option<String>,
     pub final_def_time: DateTime<Utc>,
-    pub final_def_attachment: String,
+    /// Counts the student's final defense submissions for this topic, starting from 1
+    pub final_def_version: i32,
+    pub final_def_files: Vec<SubmissionFileDetails>,
+    /// The student's final defense deadline when the defense was submitted
+    pub final_def_due_time: Option<DateTime<Utc>>,
+    pub final_def_late: bool,
     pub final_def_outcome: Option<bool>,
     pub final_def_comment: Option<String>,
     pub final_def_grade: Option<BigDecimal>,
+    pub final_def_scores: Vec<CriterionScoreDetails>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FinalDefensesGetResponse {
+    pub total: i64,
+    pub page: i64,
+    pub page_size: i64,
     pub defenses: Vec<FinalDefenseDetails>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FinalDefensesPostRequest {
-    pub attachment: String,
+    pub files: Vec<SubmissionFileRequest>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FinalDefensesPostResponse {
+    pub final_def_id: i32,
+    pub final_def_version: i32,
+    pub final_def_due_time: Option<DateTime<Utc>>,
+    pub final_def_late: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommentDetails {
+    pub comment_id: i32,
+    /// The top-level comment this one replies to
+    pub parent_id: Option<i32>,
+    pub author_user_name: String,
+    /// Present for students and teachers
+    pub author_name: Option<String>,
+    pub author_role: UserRole,
+    pub content: String,
+    pub time: DateTime<Utc>,
+    pub attachment: Option<AttachmentDetails>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommentsGetResponse {
+    pub comments: Vec<CommentDetails>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommentsPostRequest {
+    pub content: String,
+    /// Replies to a top-level comment of the same thread
+    pub parent_id: Option<i32>,
+    /// An attachment uploaded by the commenter through `POST /attachments`
+    pub attachment_id: Option<i32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -313,7 +1051,10 @@ pub struct FinalDefensesRecordTeacherPatchRequest {
 pub struct FinalDefensesRecordDefenseBoardPatchRequest {
     pub outcome: bool,
     pub comment: String,
-    pub grade: BigDecimal,
+    /// Only when the final defense has no rubric
+    pub grade: Option<BigDecimal>,
+    /// Scores for every criterion of the final defense rubric, from which the grade is computed
+    pub scores: Option<Vec<CriterionScoreRequest>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -1,12 +1,17 @@
 mod api;
+mod attachment;
 mod auth;
 mod dto;
+mod matching;
+mod similarity;
 
 use actix_files::{Files, NamedFile};
+use actix_multipart::MultipartError;
+use actix_multipart::form::MultipartFormConfig;
 use actix_session::{SessionMiddleware, storage::CookieSessionStore};
 use actix_web::{
-    App, HttpServer, Result as ActixResult, cookie::Key, middleware::Compress, middleware::Logger,
-    web,
+    App, HttpServer, Result as ActixResult, cookie::Key, error::PayloadError, middleware::Compress,
+    middleware::Logger, web,
 };
 use argon2::Argon2;
 use dotenvy_macro::dotenv;
@@ -31,6 +36,8 @@ async fn main() -> std::io::Result<()> {
     env_logger::init_from_env(Env::default().default_filter_or(dotenv!("RUST_LOG")));
 
     let pool = backend_database::get_conn_pool();
+    attachment::flush_legacy_contents(&pool)
+        .expect("Failed to move legacy attachments into the store");
 
     HttpServer::new(move || {
         App::new()
@@ -46,6 +53,19 @@ async fn main() -> std::io::Result<()> {
                 .build(),
             )
             .app_data(web::Data::new(pool.clone()))
+            .app_data(
+                MultipartFormConfig::default()
+                    .total_limit(attachment::max_upload_bytes())
+                    .error_handler(|err, _| {
+                        let msg = match err {
+                            MultipartError::Payload(PayloadError::Overflow) => {
+                                format!("Upload exceeds {} bytes", attachment::max_upload_bytes())
+                            }
+                            err => err.to_string(),
+                        };
+                        dto::ApiError::BadRequest(msg).into()
+                    }),
+            )
             .service(
                 web::scope("/api")
                     .service(api::ping)
@@ -57,17 +77,72 @@ async fn main() -> std::io::Result<()> {
                     .service(api::get_topics)
                     .service(api::create_topic)
                     .service(api::search_topics)
+                    .service(api::get_topic_tags)
                     .service(api::get_topic_detail)
                     .service(api::update_topic)
+                    .service(api::review_topics)
+                    .service(api::put_topic_cosupervisor)
+                    .service(api::delete_topic_cosupervisor)
+                    .service(api::get_topic_waitlist)
+                    .service(api::join_topic_waitlist)
+                    .service(api::leave_topic_waitlist)
+                    .service(api::get_topic_proposals)
+                    .service(api::create_topic_proposal)
+                    .service(api::update_topic_proposal)
                     .service(api::get_assignments)
                     .service(api::create_assignment)
                     .service(api::update_assignment_status)
+                    .service(api::withdraw_assignment)
+                    .service(api::put_student_topic)
+                    .service(api::delete_student_topic)
+                    .service(api::get_assignment_changes)
+                    .service(api::get_selection_rounds)
+                    .service(api::create_selection_round)
+                    .service(api::update_selection_round)
+                    .service(api::get_report_stages)
+                    .service(api::create_report_stage)
+                    .service(api::update_report_stage)
+                    .service(api::delete_report_stage)
+                    .service(api::get_deadlines)
+                    .service(api::create_deadline)
+                    .service(api::update_deadline)
+                    .service(api::delete_deadline)
+                    .service(api::put_deadline_extension)
+                    .service(api::delete_deadline_extension)
+                    .service(api::get_revision_locks)
+                    .service(api::put_revision_lock)
+                    .service(api::delete_revision_lock)
+                    .service(api::get_rubrics)
+                    .service(api::put_rubric)
                     .service(api::get_progress_reports)
                     .service(api::create_progress_report)
                     .service(api::update_progress_report)
+                    .service(api::get_progress_report_revisions)
+                    .service(api::create_progress_report_revision)
                     .service(api::get_final_defenses)
                     .service(api::create_final_defense)
-                    .service(api::update_final_defense),
+                    .service(api::update_final_defense)
+                    .service(api::get_progress_report_comments)
+                    .service(api::create_progress_report_comment)
+                    .service(api::get_final_defense_comments)
+                    .service(api::create_final_defense_comment)
+                    .service(api::get_similarity_report)
+                    .service(api::get_supervision_quotas)
+                    .service(api::put_supervision_quota)
+                    .service(api::delete_supervision_quota)
+                    .service(api::put_teacher_title)
+                    .service(api::get_matching_rounds)
+                    .service(api::create_matching_round)
+                    .service(api::get_preferences)
+                    .service(api::put_preferences)
+                    .service(api::get_applicants)
+                    .service(api::put_rankings)
+                    .service(api::preview_matching)
+                    .service(api::commit_matching)
+                    .service(api::get_notifications)
+                    .service(api::update_notification)
+                    .service(api::upload_attachment)
+                    .service(api::download_attachment),
             )
             .service(
                 Files::new("/", dotenv!("STATIC_FILES_PATH"))
@@ -0,0 +1,220 @@
+//! Topic allocation by student-proposing deferred acceptance.
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+/// Matches students to topics, returning the topic each placed student is matched to.
+///
+/// `preferences` lists each student's topics, most preferred first. `capacities` holds the
+/// places still open on each topic; topics absent from it take no students. `rankings` lists
+/// each topic's applicants as ranked by its teacher, best first. Applicants a teacher did not
+/// rank come after ranked ones, ordered by user name.
+///
+/// The result is the student-optimal stable matching, so it does not depend on the order in
+/// which students propose.
+pub fn deferred_acceptance(
+    preferences: &BTreeMap<String, Vec<i32>>,
+    capacities: &HashMap<i32, usize>,
+    rankings: &HashMap<i32, Vec<String>>,
+) -> BTreeMap<String, i32> {
+    let positions: HashMap<i32, HashMap<&str, usize>> = rankings
+        .iter()
+        .map(|(topic_id, ranking)| {
+            let positions = ranking
+                .iter()
+                .enumerate()
+                .map(|(position, student)| (student.as_str(), position))
+                .collect();
+            (*topic_id, positions)
+        })
+        .collect();
+    let priority = |topic_id: i32, student: &str| {
+        let position = positions
+            .get(&topic_id)
+            .and_then(|positions| positions.get(student).copied())
+            .unwrap_or(usize::MAX);
+        (position, student.to_owned())
+    };
+
+    let mut next_choice: HashMap<&str, usize> = HashMap::new();
+    let mut held: HashMap<i32, Vec<&str>> = HashMap::new();
+    let mut free: VecDeque<&str> = preferences.keys().map(String::as_str).collect();
+
+    while let Some(student) = free.pop_front() {
+        let choices = &preferences[student];
+        let next = next_choice.entry(student).or_insert(0);
+        while let Some(&topic_id) = choices.get(*next) {
+            *next += 1;
+
+            let capacity = capacities.get(&topic_id).copied().unwrap_or(0);
+            if capacity == 0 {
+                continue;
+            }
+
+            let holders = held.entry(topic_id).or_default();
+            holders.push(student);
+            if holders.len() <= capacity {
+                break;
+            }
+
+            // Over capacity: the topic keeps its best applicants and rejects the worst one
+            holders.sort_by_key(|holder| priority(topic_id, holder));
+            let Some(rejected) = holders.pop() else {
+                break;
+            };
+            if rejected != student {
+                free.push_back(rejected);
+                break;
+            }
+        }
+    }
+
+    held.into_iter()
+        .flat_map(|(topic_id, holders)| {
+            holders
+                .into_iter()
+                .map(move |student| (student.to_owned(), topic_id))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn preferences(entries: &[(&str, &[i32])]) -> BTreeMap<String, Vec<i32>> {
+        entries
+            .iter()
+            .map(|(student, topics)| (student.to_string(), topics.to_vec()))
+            .collect()
+    }
+
+    fn rankings(entries: &[(i32, &[&str])]) -> HashMap<i32, Vec<String>> {
+        entries
+            .iter()
+            .map(|(topic_id, students)| {
+                (*topic_id, students.iter().map(|s| s.to_string()).collect())
+            })
+            .collect()
+    }
+
+    /// Asserts that no student and topic would both rather be matched to each other.
+    fn assert_stable(
+        preferences: &BTreeMap<String, Vec<i32>>,
+        capacities: &HashMap<i32, usize>,
+        rankings: &HashMap<i32, Vec<String>>,
+        matched: &BTreeMap<String, i32>,
+    ) {
+        let rank_of = |topic_id: i32, student: &str| {
+            let position = rankings
+                .get(&topic_id)
+                .and_then(|ranking| ranking.iter().position(|s| s == student))
+                .unwrap_or(usize::MAX);
+            (position, student.to_owned())
+        };
+
+        for (student, choices) in preferences {
+            let current = matched.get(student);
+            for &topic_id in choices {
+                if Some(&topic_id) == current {
+                    break;
+                }
+                let capacity = capacities.get(&topic_id).copied().unwrap_or(0);
+                let holders = matched
+                    .iter()
+                    .filter(|(_, t)| **t == topic_id)
+                    .map(|(s, _)| s.as_str())
+                    .collect::<Vec<_>>();
+                assert!(
+                    holders.len() >= capacity,
+                    "{student} prefers topic {topic_id}, which has a free place"
+                );
+                assert!(
+                    holders
+                        .iter()
+                        .all(|holder| rank_of(topic_id, holder) < rank_of(topic_id, student)),
+                    "{student} and topic {topic_id} form a blocking pair"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn capacity_goes_to_the_better_ranked_applicant() {
+        let preferences = preferences(&[("a", &[1, 2]), ("b", &[1, 2])]);
+        let capacities = HashMap::from([(1, 1), (2, 1)]);
+        let rankings = rankings(&[(1, &["b", "a"])]);
+
+        let matched = deferred_acceptance(&preferences, &capacities, &rankings);
+
+        assert_eq!(matched.get("b"), Some(&1));
+        assert_eq!(matched.get("a"), Some(&2));
+        assert_stable(&preferences, &capacities, &rankings, &matched);
+    }
+
+    #[test]
+    fn capacity_fills_exactly_when_applicants_tie() {
+        let preferences = preferences(&[("a", &[1]), ("b", &[1]), ("c", &[1]), ("d", &[1])]);
+        let capacities = HashMap::from([(1, 2)]);
+        let rankings = HashMap::new();
+
+        let matched = deferred_acceptance(&preferences, &capacities, &rankings);
+
+        // Unranked applicants tie on rank and are ordered by user name
+        assert_eq!(
+            matched,
+            BTreeMap::from([(String::from("a"), 1), (String::from("b"), 1)])
+        );
+    }
+
+    #[test]
+    fn unranked_applicants_come_after_ranked_ones() {
+        let preferences = preferences(&[("a", &[1]), ("b", &[1]), ("z", &[1])]);
+        let capacities = HashMap::from([(1, 2)]);
+        let rankings = rankings(&[(1, &["z"])]);
+
+        let matched = deferred_acceptance(&preferences, &capacities, &rankings);
+
+        assert_eq!(matched.get("z"), Some(&1));
+        assert_eq!(matched.get("a"), Some(&1));
+        assert_eq!(matched.get("b"), None);
+    }
+
+    #[test]
+    fn topics_without_open_places_take_no_students() {
+        let preferences = preferences(&[("a", &[1, 2, 3])]);
+        let capacities = HashMap::from([(1, 0), (3, 1)]);
+        let rankings = HashMap::new();
+
+        let matched = deferred_acceptance(&preferences, &capacities, &rankings);
+
+        assert_eq!(matched.get("a"), Some(&3));
+    }
+
+    #[test]
+    fn matching_is_stable() {
+        let preferences = preferences(&[
+            ("s1", &[1, 2, 3]),
+            ("s2", &[1, 3, 2]),
+            ("s3", &[2, 1, 3]),
+            ("s4", &[2, 3, 1]),
+            ("s5", &[3, 1, 2]),
+            ("s6", &[1, 2]),
+            ("s7", &[3]),
+        ]);
+        let capacities = HashMap::from([(1, 2), (2, 2), (3, 2)]);
+        let rankings = rankings(&[
+            (1, &["s5", "s4", "s3", "s2", "s1"]),
+            (2, &["s1", "s6", "s2"]),
+            (3, &["s7", "s6", "s4", "s2", "s1", "s3", "s5"]),
+        ]);
+
+        let matched = deferred_acceptance(&preferences, &capacities, &rankings);
+
+        for topic_id in [1, 2, 3] {
+            let holders = matched.values().filter(|t| **t == topic_id).count();
+            assert!(holders <= capacities[&topic_id]);
+        }
+        assert_eq!(matched.len(), 6);
+        assert_stable(&preferences, &capacities, &rankings, &matched);
+    }
+}
@@ -0,0 +1,366 @@
+//! Local similarity check of submitted documents by shingled MinHash.
+//!
+//! A document is reduced to a sequence of tokens: every CJK character is a token of its own,
+//! and any other run of letters and digits is one token, compared case-insensitively. Each run
+//! of [`SHINGLE_LEN`] consecutive tokens is hashed into a shingle, and the document is
+//! summarized by the minimum of [`SIGNATURE_LEN`] independent hashes over its shingles. The
+//! share of equal minimums between two signatures estimates the Jaccard similarity of their
+//! shingle sets.
+
+use quick_xml::Reader;
+use quick_xml::escape::resolve_predefined_entity;
+use quick_xml::events::Event;
+use std::collections::HashSet;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::io::{Cursor, Read};
+use std::ops::Range;
+
+/// Number of consecutive tokens hashed into one shingle
+const SHINGLE_LEN: usize = 6;
+
+/// Number of hashes in a MinHash signature
+const SIGNATURE_LEN: usize = 128;
+
+/// Documents with fewer distinct shingles are too short to compare meaningfully
+const MIN_SHINGLES: usize = 20;
+
+/// Maximum number of matched passages reported for a pair of documents
+const MAX_PASSAGES: usize = 5;
+
+/// Maximum length of a reported passage, in characters
+const MAX_PASSAGE_CHARS: usize = 300;
+
+const PDF_MIME_TYPE: &str = "application/pdf";
+const DOCX_MIME_TYPE: &str =
+    "application/vnd.openxmlformats-officedocument.wordprocessingml.document";
+const ZIP_MIME_TYPE: &str = "application/zip";
+
+/// Extracts the text of a PDF or DOCX document. Returns `None` for other types and for
+/// documents that cannot be read.
+///
+/// Some DOCX documents are sniffed as plain ZIP archives, so these are tried as DOCX too.
+pub fn extract_text(content: &[u8], mime_type: &str) -> Option<String> {
+    match mime_type {
+        // The PDF parser panics on some malformed documents instead of failing
+        PDF_MIME_TYPE => std::panic::catch_unwind(|| pdf_extract::extract_text_from_mem(content))
+            .ok()?
+            .ok(),
+        DOCX_MIME_TYPE | ZIP_MIME_TYPE => extract_docx_text(content),
+        _ => None,
+    }
+}
+
+/// Collects the runs of text of the main part of a DOCX document, one paragraph per line.
+fn extract_docx_text(content: &[u8]) -> Option<String> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(content)).ok()?;
+    let mut xml = String::new();
+    archive
+        .by_name("word/document.xml")
+        .ok()?
+        .read_to_string(&mut xml)
+        .ok()?;
+
+    let mut reader = Reader::from_str(&xml);
+    let mut text = String::new();
+    let mut in_run_text = false;
+    loop {
+        match reader.read_event().ok()? {
+            Event::Start(e) if e.local_name().as_ref() == "t" => in_run_text = true,
+            Event::End(e) if e.local_name().as_ref() == "t" => in_run_text = false,
+            Event::End(e) if e.local_name().as_ref() == "p" => text.push('\n'),
+            Event::Empty(e) if matches!(e.local_name().as_ref(), "tab" | "br") => text.push(' '),
+            Event::Text(e) if in_run_text => text.push_str(&e.xml10_content()),
+            Event::GeneralRef(e) if in_run_text => {
+                if let Ok(Some(c)) = e.resolve_char_ref() {
+                    text.push(c);
+                } else if let Some(resolved) = resolve_predefined_entity(&e) {
+                    text.push_str(resolved);
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Some(text)
+}
+
+fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{30FF}'     // Hiragana, Katakana
+        | '\u{3400}'..='\u{4DBF}'   // CJK Unified Ideographs Extension A
+        | '\u{4E00}'..='\u{9FFF}'   // CJK Unified Ideographs
+        | '\u{AC00}'..='\u{D7AF}'   // Hangul Syllables
+        | '\u{F900}'..='\u{FAFF}'   // CJK Compatibility Ideographs
+    )
+}
+
+fn hash_of<T: Hash + ?Sized>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// The SplitMix64 finalizer, used to derive independent hash functions from one shingle hash.
+fn mix(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^ (x >> 31)
+}
+
+/// Splits text into tokens, returning the hash of each token and where it lies in the text.
+fn tokenize(text: &str) -> Vec<(u64, Range<usize>)> {
+    let mut tokens = Vec::new();
+    let mut word: Option<usize> = None;
+    let push_word = |tokens: &mut Vec<(u64, Range<usize>)>, start: usize, end: usize| {
+        tokens.push((hash_of(&text[start..end].to_lowercase()), start..end));
+    };
+
+    for (i, c) in text.char_indices() {
+        if is_cjk(c) {
+            if let Some(start) = word.take() {
+                push_word(&mut tokens, start, i);
+            }
+            tokens.push((hash_of(&c), i..i + c.len_utf8()));
+        } else if c.is_alphanumeric() {
+            word.get_or_insert(i);
+        } else if let Some(start) = word.take() {
+            push_word(&mut tokens, start, i);
+        }
+    }
+    if let Some(start) = word {
+        push_word(&mut tokens, start, text.len());
+    }
+
+    tokens
+}
+
+/// A document prepared for comparison.
+pub struct Fingerprint {
+    text: String,
+    /// Where each token lies in the text
+    spans: Vec<Range<usize>>,
+    /// The shingle starting at each token, for all but the last `SHINGLE_LEN - 1` tokens
+    shingles: Vec<u64>,
+    signature: Vec<u64>,
+}
+
+impl Fingerprint {
+    /// Fingerprints a document's text, or returns `None` if it is too short to compare.
+    pub fn new(text: String) -> Option<Self> {
+        let (hashes, spans): (Vec<_>, Vec<_>) = tokenize(&text).into_iter().unzip();
+        let shingles = hashes.windows(SHINGLE_LEN).map(hash_of).collect::<Vec<_>>();
+        let distinct = shingles.iter().copied().collect::<HashSet<_>>();
+        if distinct.len() < MIN_SHINGLES {
+            return None;
+        }
+
+        let signature = (0..SIGNATURE_LEN as u64)
+            .map(|i| {
+                let seed = mix(i.wrapping_add(0x9E37_79B9_7F4A_7C15));
+                distinct
+                    .iter()
+                    .map(|&shingle| mix(shingle ^ seed))
+                    .min()
+                    .unwrap_or(u64::MAX)
+            })
+            .collect();
+
+        Some(Self {
+            text,
+            spans,
+            shingles,
+            signature,
+        })
+    }
+
+    /// Estimates the Jaccard similarity between the shingle sets of two documents, from 0
+    /// to 1.
+    pub fn similarity(&self, other: &Self) -> f64 {
+        let equal = self
+            .signature
+            .iter()
+            .zip(&other.signature)
+            .filter(|(a, b)| a == b)
+            .count();
+        equal as f64 / SIGNATURE_LEN as f64
+    }
+
+    /// Returns the longest passages of this document whose shingles also occur in the other,
+    /// longest first.
+    pub fn matched_passages(&self, other: &Self) -> Vec<String> {
+        let theirs = other.shingles.iter().copied().collect::<HashSet<_>>();
+
+        // Consecutive shared shingles cover one contiguous run of tokens
+        let mut runs: Vec<Range<usize>> = Vec::new();
+        for (i, shingle) in self.shingles.iter().enumerate() {
+            if !theirs.contains(shingle) {
+                continue;
+            }
+            let covered = i..i + SHINGLE_LEN;
+            match runs.last_mut() {
+                Some(run) if run.end >= covered.start => run.end = covered.end,
+                _ => runs.push(covered),
+            }
+        }
+        runs.sort_by_key(|run| std::cmp::Reverse(run.len()));
+
+        runs.into_iter()
+            .take(MAX_PASSAGES)
+            .map(|run| {
+                let text = &self.text[self.spans[run.start].start..self.spans[run.end - 1].end];
+                let passage = text.split_whitespace().collect::<Vec<_>>().join(" ");
+                if passage.chars().count() > MAX_PASSAGE_CHARS {
+                    let mut passage = passage.chars().take(MAX_PASSAGE_CHARS).collect::<String>();
+                    passage.push('…');
+                    passage
+                } else {
+                    passage
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    /// Joins numbered words into a text, e.g. `alpha0 alpha1 ...`.
+    fn words(prefix: &str, range: Range<usize>) -> String {
+        range
+            .map(|i| format!("{prefix}{i}"))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Returns a text of distinct CJK ideographs.
+    fn ideographs(count: u32) -> String {
+        (0..count)
+            .map(|i| char::from_u32(0x4E00 + i).unwrap())
+            .collect()
+    }
+
+    fn docx(document_xml: &str) -> Vec<u8> {
+        let mut archive = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        archive
+            .start_file(
+                "word/document.xml",
+                zip::write::SimpleFileOptions::default(),
+            )
+            .unwrap();
+        archive.write_all(document_xml.as_bytes()).unwrap();
+        archive.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn cjk_characters_and_latin_words_are_separate_tokens() {
+        let text = "论文Thesis 2024, hello-World";
+
+        let tokens = tokenize(text)
+            .into_iter()
+            .map(|(_, span)| &text[span])
+            .collect::<Vec<_>>();
+
+        assert_eq!(tokens, ["论", "文", "Thesis", "2024", "hello", "World"]);
+    }
+
+    #[test]
+    fn latin_words_compare_case_insensitively() {
+        let hashes = |text: &str| {
+            tokenize(text)
+                .into_iter()
+                .map(|(hash, _)| hash)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(hashes("Hello WORLD"), hashes("hello world"));
+    }
+
+    #[test]
+    fn short_documents_are_not_fingerprinted() {
+        assert!(Fingerprint::new(words("w", 0..SHINGLE_LEN + MIN_SHINGLES - 2)).is_none());
+        assert!(Fingerprint::new(words("w", 0..SHINGLE_LEN + MIN_SHINGLES - 1)).is_some());
+    }
+
+    #[test]
+    fn identical_documents_are_fully_similar() {
+        let first = Fingerprint::new(words("alpha", 0..30)).unwrap();
+        let second = Fingerprint::new(words("alpha", 0..30)).unwrap();
+
+        assert_eq!(first.similarity(&second), 1.0);
+        assert_eq!(first.matched_passages(&second), [words("alpha", 0..30)]);
+    }
+
+    #[test]
+    fn disjoint_documents_share_nothing() {
+        let first = Fingerprint::new(words("alpha", 0..60)).unwrap();
+        let second = Fingerprint::new(words("beta", 0..60)).unwrap();
+
+        assert!(first.similarity(&second) < 0.1);
+        assert!(first.matched_passages(&second).is_empty());
+    }
+
+    #[test]
+    fn passages_cover_only_the_shared_text() {
+        let shared = format!("{}，共同段落", words("shared", 0..10));
+        let first = Fingerprint::new(format!(
+            "{}\n{}\n{}",
+            words("alpha", 0..30),
+            shared,
+            words("gamma", 0..30)
+        ))
+        .unwrap();
+        let second = Fingerprint::new(format!(
+            "{} {} {}",
+            words("beta", 0..30),
+            shared,
+            words("delta", 0..30)
+        ))
+        .unwrap();
+
+        assert_eq!(first.matched_passages(&second), [shared]);
+    }
+
+    #[test]
+    fn long_passages_are_truncated() {
+        let text = ideographs(MAX_PASSAGE_CHARS as u32 + 100);
+        let first = Fingerprint::new(text.clone()).unwrap();
+        let second = Fingerprint::new(text.clone()).unwrap();
+
+        let passages = first.matched_passages(&second);
+
+        assert_eq!(passages.len(), 1);
+        let expected = text.chars().take(MAX_PASSAGE_CHARS).collect::<String>() + "…";
+        assert_eq!(passages[0], expected);
+    }
+
+    #[test]
+    fn docx_text_keeps_paragraphs_and_entities() {
+        let content = docx(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+<w:body>
+<w:p><w:r><w:t>Hello</w:t></w:r><w:r><w:tab/><w:t>a &amp; b&#33;</w:t></w:r></w:p>
+<w:p><w:r><w:t>第二段</w:t></w:r></w:p>
+</w:body>
+</w:document>"#,
+        );
+
+        assert_eq!(
+            extract_text(&content, DOCX_MIME_TYPE).as_deref(),
+            Some("Hello a & b!\n第二段\n")
+        );
+        assert_eq!(
+            extract_text(&content, ZIP_MIME_TYPE).as_deref(),
+            Some("Hello a & b!\n第二段\n")
+        );
+    }
+
+    #[test]
+    fn unreadable_documents_have_no_text() {
+        assert_eq!(extract_text(b"not a zip archive", DOCX_MIME_TYPE), None);
+        assert_eq!(extract_text(&docx("<w:document/>"), "text/plain"), None);
+    }
+}
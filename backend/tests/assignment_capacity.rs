@@ -0,0 +1,343 @@
+//! Concurrency test of assignment approvals against the running server binary.
+//!
+//! The test needs the database from `.env`, migrated, and the configured server port free. It
+//! creates its own teacher, topics and students, and removes them again when done.
+
+use argon2::Argon2;
+use argon2::password_hash::{PasswordHasher, SaltString};
+use backend_database::model::{NewAssignmentRequest, NewStudent, NewSysUser, NewTeacher, NewTopic};
+use backend_database::schema::*;
+use chrono::Utc;
+use diesel::prelude::*;
+use dotenvy_macro::dotenv;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::process::{Child, Command};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const TOPIC_COUNT: usize = 3;
+const STUDENT_COUNT: usize = 24;
+const TOPIC_MAX_STUDENTS: i32 = 6;
+const PASSWORD: &str = "capacity";
+
+fn server_addr() -> String {
+    format!("{}:{}", dotenv!("SERVER_HOST"), dotenv!("SERVER_PORT"))
+}
+
+/// Sends one HTTP/1.1 request and returns the status code and the session cookie, if set.
+fn send(method: &str, path: &str, cookie: Option<&str>, body: &str) -> (u16, Option<String>) {
+    let mut stream = TcpStream::connect(server_addr()).expect("Failed to connect to server");
+    let cookie_header = cookie
+        .map(|cookie| format!("Cookie: {}\r\n", cookie))
+        .unwrap_or_default();
+    write!(
+        stream,
+        "{} /api{} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\n\
+         Content-Length: {}\r\nConnection: close\r\n{}\r\n{}",
+        method,
+        path,
+        server_addr(),
+        body.len(),
+        cookie_header,
+        body
+    )
+    .expect("Failed to send request");
+
+    let mut response = Vec::new();
+    stream
+        .read_to_end(&mut response)
+        .expect("Failed to read response");
+    let response = String::from_utf8_lossy(&response);
+    let (head, _) = response.split_once("\r\n\r\n").unwrap_or((&response, ""));
+    let mut lines = head.lines();
+    let status = lines
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|code| code.parse().ok())
+        .expect("Malformed status line");
+    let cookie = lines
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.eq_ignore_ascii_case("set-cookie"))
+        .and_then(|(_, value)| value.trim().split(';').next())
+        .map(str::to_owned);
+
+    (status, cookie)
+}
+
+/// The server binary, stopped when dropped.
+struct Server(Child);
+
+impl Server {
+    fn start() -> Self {
+        assert!(
+            TcpStream::connect(server_addr()).is_err(),
+            "{} is already in use; stop the running server first",
+            server_addr()
+        );
+        let child = Command::new(env!("CARGO_BIN_EXE_backend_server"))
+            .spawn()
+            .expect("Failed to start server");
+        let server = Self(child);
+
+        let deadline = Instant::now() + Duration::from_secs(30);
+        while TcpStream::connect(server_addr()).is_err() {
+            assert!(Instant::now() < deadline, "Server did not start in time");
+            thread::sleep(Duration::from_millis(100));
+        }
+        server
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+/// Teachers with a topic each, and students who each requested every topic, removed when
+/// dropped. Separate teachers keep the teacher lock from serializing the approvals.
+struct Fixture {
+    conn: PgConnection,
+    teacher_user_names: Vec<String>,
+    student_user_names: Vec<String>,
+    topic_ids: Vec<i32>,
+}
+
+impl Fixture {
+    fn create() -> Self {
+        let conn = PgConnection::establish(dotenv!("DATABASE_URL"))
+            .expect("Failed to connect to database");
+
+        let suffix = format!("{:06}", std::process::id() % 1_000_000);
+        let teacher_user_names = (0..TOPIC_COUNT)
+            .map(|i| format!("capt{}{:02}", suffix, i))
+            .collect::<Vec<_>>();
+        let student_user_names = (0..STUDENT_COUNT)
+            .map(|i| format!("caps{}{:02}", suffix, i))
+            .collect::<Vec<_>>();
+
+        let salt_bytes = format!("capacity{}", suffix);
+        let salt = SaltString::encode_b64(salt_bytes.as_bytes()).expect("Failed to encode salt");
+        let hash = Argon2::default()
+            .hash_password(PASSWORD.as_bytes(), &salt)
+            .expect("Failed to hash password")
+            .hash
+            .expect("Failed to get password hash");
+
+        let mut fixture = Self {
+            conn,
+            teacher_user_names,
+            student_user_names,
+            topic_ids: Vec::new(),
+        };
+        let conn = &mut fixture.conn;
+
+        let major_id = major::table
+            .select(major::columns::major_id)
+            .first::<i32>(conn)
+            .expect("No major to create students in");
+
+        let user_names = fixture
+            .teacher_user_names
+            .iter()
+            .chain(&fixture.student_user_names)
+            .collect::<Vec<_>>();
+        diesel::insert_into(sysuser::table)
+            .values(
+                user_names
+                    .iter()
+                    .map(|user_name| NewSysUser {
+                        user_name,
+                        user_password_hash: hash.as_bytes(),
+                        user_password_salt: salt_bytes.as_bytes(),
+                        user_avatar: None,
+                    })
+                    .collect::<Vec<_>>(),
+            )
+            .execute(conn)
+            .expect("Failed to create users");
+        diesel::insert_into(teacher::table)
+            .values(
+                fixture
+                    .teacher_user_names
+                    .iter()
+                    .map(|user_name| NewTeacher {
+                        user_name,
+                        teacher_name: user_name,
+                        teacher_title: 4,
+                    })
+                    .collect::<Vec<_>>(),
+            )
+            .execute(conn)
+            .expect("Failed to create teachers");
+        diesel::insert_into(student::table)
+            .values(
+                fixture
+                    .student_user_names
+                    .iter()
+                    .map(|user_name| NewStudent {
+                        user_name,
+                        topic_id: None,
+                        major_id,
+                        student_name: user_name,
+                        assn_time: Utc::now(),
+                    })
+                    .collect::<Vec<_>>(),
+            )
+            .execute(conn)
+            .expect("Failed to create students");
+
+        for teacher_user_name in &fixture.teacher_user_names {
+            let topic_name = format!("Capacity test {}", teacher_user_name);
+            let topic_id = diesel::insert_into(topic::table)
+                .values(NewTopic {
+                    major_id,
+                    teacher_user_name,
+                    topic_name: &topic_name,
+                    topic_description: "",
+                    topic_max_students: TOPIC_MAX_STUDENTS,
+                    topic_type: 0,
+                    topic_review_status: 1,
+                })
+                .returning(topic::columns::topic_id)
+                .get_result::<i32>(conn)
+                .expect("Failed to create topic");
+            fixture.topic_ids.push(topic_id);
+        }
+
+        let requests = fixture
+            .student_user_names
+            .iter()
+            .flat_map(|user_name| {
+                fixture
+                    .topic_ids
+                    .iter()
+                    .map(move |&topic_id| NewAssignmentRequest {
+                        student_user_name: user_name,
+                        topic_id,
+                        assn_req_time: Utc::now(),
+                        sel_round_id: None,
+                    })
+            })
+            .collect::<Vec<_>>();
+        diesel::insert_into(assignmentrequest::table)
+            .values(requests)
+            .execute(conn)
+            .expect("Failed to create assignment requests");
+
+        fixture
+    }
+}
+
+impl Drop for Fixture {
+    fn drop(&mut self) {
+        let conn = &mut self.conn;
+        let _ = diesel::delete(
+            notification::table
+                .filter(notification::columns::user_name.eq_any(&self.teacher_user_names)),
+        )
+        .execute(conn);
+        let _ = diesel::delete(
+            assignmentrequest::table
+                .filter(assignmentrequest::columns::topic_id.eq_any(&self.topic_ids)),
+        )
+        .execute(conn);
+        let _ = diesel::delete(
+            student::table.filter(student::columns::user_name.eq_any(&self.student_user_names)),
+        )
+        .execute(conn);
+        let _ =
+            diesel::delete(topic::table.filter(topic::columns::topic_id.eq_any(&self.topic_ids)))
+                .execute(conn);
+        let _ = diesel::delete(
+            teacher::table.filter(teacher::columns::user_name.eq_any(&self.teacher_user_names)),
+        )
+        .execute(conn);
+        let _ = diesel::delete(
+            sysuser::table
+                .filter(sysuser::columns::user_name.eq_any(&self.student_user_names))
+                .or_filter(sysuser::columns::user_name.eq_any(&self.teacher_user_names)),
+        )
+        .execute(conn);
+    }
+}
+
+#[test]
+#[ignore = "needs the database and a free server port"]
+fn parallel_approvals_fill_topics_exactly() {
+    let mut fixture = Fixture::create();
+    let _server = Server::start();
+
+    let cookies = fixture
+        .teacher_user_names
+        .iter()
+        .map(|user_name| {
+            let (status, cookie) = send(
+                "POST",
+                "/login",
+                None,
+                &format!(
+                    r#"{{"username":"{}","password":"{}"}}"#,
+                    user_name, PASSWORD
+                ),
+            );
+            assert_eq!(status, 200, "Teacher login failed");
+            cookie.expect("Login set no session cookie")
+        })
+        .collect::<Vec<_>>();
+
+    // Approve every request at once, so that each approval also closes the student's requests
+    // for the other topics while those are being filled
+    let statuses = thread::scope(|scope| {
+        let handles = fixture
+            .student_user_names
+            .iter()
+            .flat_map(|user_name| {
+                fixture
+                    .topic_ids
+                    .iter()
+                    .zip(&cookies)
+                    .map(move |(&topic_id, cookie)| (user_name, topic_id, cookie))
+            })
+            .map(|(user_name, topic_id, cookie)| {
+                scope.spawn(move || {
+                    send(
+                        "PATCH",
+                        &format!("/assignments/{}/{}", user_name, topic_id),
+                        Some(cookie),
+                        r#"{"approved":true}"#,
+                    )
+                    .0
+                })
+            })
+            .collect::<Vec<_>>();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("Request thread panicked"))
+            .collect::<Vec<_>>()
+    });
+
+    assert!(
+        statuses
+            .iter()
+            .all(|status| matches!(status, 200 | 404 | 409)),
+        "Unexpected responses: {:?}",
+        statuses
+    );
+    assert_eq!(
+        statuses.iter().filter(|&&status| status == 200).count(),
+        fixture.topic_ids.len() * TOPIC_MAX_STUDENTS as usize
+    );
+
+    let topic_ids = fixture.topic_ids.clone();
+    for topic_id in topic_ids {
+        let assigned: i64 = student::table
+            .filter(student::columns::topic_id.eq(topic_id))
+            .count()
+            .get_result(&mut fixture.conn)
+            .expect("Failed to count students");
+        assert_eq!(assigned, TOPIC_MAX_STUDENTS as i64);
+    }
+}
@@ -1,9 +1,11 @@
 import type { PingResponse } from './models'
 import type { HttpClientConfig } from './utils/http-client'
 import { AssignmentService } from './services/assignment.service'
+import { AttachmentService } from './services/attachment.service'
 import { AuthService } from './services/auth.service'
 import { FinalDefenseService } from './services/final-defense.service'
 import { ProgressReportService } from './services/progress-report.service'
+import { RubricService } from './services/rubric.service'
 import { TopicService } from './services/topic.service'
 import { HttpClient } from './utils/http-client'
 
@@ -13,6 +15,8 @@ export class ThesisManagementApiClient {
   public assignments: AssignmentService
   public progressReports: ProgressReportService
   public finalDefenses: FinalDefenseService
+  public attachments: AttachmentService
+  public rubrics: RubricService
   private httpClient: HttpClient
 
   constructor (config: HttpClientConfig) {
@@ -23,6 +27,8 @@ export class ThesisManagementApiClient {
     this.assignments = new AssignmentService(this.httpClient)
     this.progressReports = new ProgressReportService(this.httpClient)
     this.finalDefenses = new FinalDefenseService(this.httpClient)
+    this.attachments = new AttachmentService(this.httpClient)
+    this.rubrics = new RubricService(this.httpClient)
   }
 
   async ping (): Promise<PingResponse> {
@@ -1,4 +1,10 @@
-import type { AssignmentStatus, ProgressOutcome, TopicReviewStatus, TopicType } from './models'
+import type {
+  AssignmentStatus,
+  ProgressOutcome,
+  SubmissionFileKind,
+  TopicReviewStatus,
+  TopicType,
+} from './models'
 
 // Topic Types
 export const TOPIC_TYPE_MAP = new Map<TopicType, string>([
@@ -34,6 +40,7 @@ export const ASSIGNMENT_STATUS_MAP = new Map<AssignmentStatus, string>([
   [0, '待审核'],
   [1, '已通过'],
   [2, '已拒绝'],
+  [3, '已撤回'],
 ])
 
 export const ASSIGNMENT_STATUSES = Array.from(ASSIGNMENT_STATUS_MAP.entries()).map(
@@ -57,6 +64,14 @@ export const PROGRESS_OUTCOMES = Array.from(PROGRESS_OUTCOME_MAP.entries()).map(
   }),
 )
 
+// Submission File Kind
+export const SUBMISSION_FILE_KIND_MAP = new Map<SubmissionFileKind, string>([
+  [0, '论文'],
+  [1, '演示文稿'],
+  [2, '源代码'],
+  [3, '其他'],
+])
+
 // Helper functions
 export function getTopicTypeName (type: TopicType): string {
   return TOPIC_TYPE_MAP.get(type) || '未知'
@@ -8,6 +8,7 @@ export * from './exceptions'
 // Models
 export * from './models'
 export { AssignmentService } from './services/assignment.service'
+export { AttachmentService } from './services/attachment.service'
 // Services
 export { AuthService } from './services/auth.service'
 
@@ -15,6 +16,8 @@ export { FinalDefenseService } from './services/final-defense.service'
 
 export { ProgressReportService } from './services/progress-report.service'
 
+export { RubricService } from './services/rubric.service'
+
 export { TopicService } from './services/topic.service'
 export { getErrorMessage } from './utils/error-messages'
 // Utils
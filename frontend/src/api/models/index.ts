@@ -2,10 +2,13 @@
 export type UserRole = 'admin' | 'student' | 'teacher' | 'defense_board' | 'office'
 
 export type TopicType = 0 | 1 | 2 | 3 | 4
+export type TeacherTitle = 0 | 1 | 2 | 3 | 4
 export type TopicReviewStatus = 0 | 1 | 2
-export type ProgressReportType = 0 | 1
+export type TopicReviewOutcome = 0 | 1 | 2 | 3
 export type ProgressOutcome = 0 | 1 | 2
-export type AssignmentStatus = 0 | 1 | 2
+export type SubmissionFileKind = 0 | 1 | 2 | 3
+export type AssignmentStatus = 0 | 1 | 2 | 3
+export type TopicProposalStatus = 0 | 1 | 2
 
 // Ping
 export interface PingResponse {
@@ -23,6 +26,7 @@ export interface UserGetResponse {
   role: UserRole
   name?: string
   avatar?: string
+  title?: TeacherTitle
 }
 
 export interface UserPatchRequest {
@@ -38,6 +42,7 @@ export interface UserPostRequest {
   name?: string
   major_id?: number
   avatar?: string
+  title?: TeacherTitle
 }
 
 // Topic models
@@ -49,6 +54,8 @@ export interface TopicBrief {
   topic_type: TopicType
   topic_review_status: TopicReviewStatus
   current_student_count: number
+  tags: string[]
+  topic_version: number
 }
 
 export interface TopicDetails {
@@ -63,6 +70,20 @@ export interface TopicDetails {
   topic_type: TopicType
   topic_review_status: TopicReviewStatus
   current_student_count: number
+  tags: string[]
+  cosupervisors: CoSupervisorDetails[]
+  topic_version: number
+  topic_review_comment?: string
+}
+
+export interface CoSupervisorDetails {
+  teacher_user_name: string
+  teacher_name: string
+  can_approve: boolean
+}
+
+export interface CoSupervisorPutRequest {
+  can_approve: boolean
 }
 
 export interface TopicsGetResponse {
@@ -78,6 +99,7 @@ export interface TopicsPostRequest {
   topic_description: string
   topic_max_students: number
   topic_type: TopicType
+  tags?: string[]
 }
 
 export interface TopicsPostTeacherRequest {
@@ -85,10 +107,65 @@ export interface TopicsPostTeacherRequest {
   topic_description?: string
   topic_max_students?: number
   topic_type?: TopicType
+  tags?: string[]
 }
 
 export interface TopicsPostOfficeRequest {
   topic_review_status: TopicReviewStatus
+  topic_review_comment?: string
+}
+
+export interface TopicProposalDetails {
+  prop_id: number
+  student_user_name: string
+  student_name: string
+  teacher_user_name: string
+  teacher_name: string
+  topic_name: string
+  topic_description: string
+  topic_type: TopicType
+  prop_status: TopicProposalStatus
+  prop_time: string
+  prop_decided_time?: string
+  prop_decline_reason?: string
+  topic_id?: number
+}
+
+export interface TopicProposalsGetResponse {
+  proposals: TopicProposalDetails[]
+}
+
+export interface TopicProposalsPostRequest {
+  teacher_user_name: string
+  topic_name: string
+  topic_description: string
+  topic_type: TopicType
+}
+
+export interface TopicProposalPatchRequest {
+  accepted: boolean
+  reason?: string
+}
+
+export interface TopicReviewDecision {
+  topic_id: number
+  topic_review_status: TopicReviewStatus
+  topic_review_comment?: string
+  topic_version: number
+}
+
+export interface TopicsReviewPostRequest {
+  reviews: TopicReviewDecision[]
+}
+
+export interface TopicReviewResult {
+  topic_id: number
+  outcome: TopicReviewOutcome
+  topic_version?: number
+}
+
+export interface TopicsReviewPostResponse {
+  results: TopicReviewResult[]
 }
 
 export interface TopicsPostAdminRequest {
@@ -99,6 +176,15 @@ export interface TopicsPostAdminRequest {
   topic_review_status?: TopicReviewStatus
 }
 
+export interface TagCount {
+  tag_name: string
+  topic_count: number
+}
+
+export interface TagsGetResponse {
+  tags: TagCount[]
+}
+
 export interface TopicCreateResponse {
   topic_id: number
 }
@@ -112,6 +198,9 @@ export interface Assignment {
   topic_name: string
   request_time: string
   status: AssignmentStatus
+  decided_time?: string
+  decided_by?: string
+  reason?: string
 }
 
 export interface AssignmentsGetResponse {
@@ -121,12 +210,172 @@ export interface AssignmentsGetResponse {
   assignments: Assignment[]
 }
 
+export interface AssignmentsQueryParams extends PaginationParams {
+  status?: AssignmentStatus
+  topic_id?: number
+  major_id?: number
+  student_name?: string
+  order?: 'asc' | 'desc'
+}
+
 export interface AssignmentsPostRequest {
   topic_id: number
 }
 
 export interface AssignmentRecordPatchRequest {
   approved: boolean
+  reason?: string
+}
+
+export interface StudentTopicPutRequest {
+  topic_id: number
+  reason: string
+  override_capacity?: boolean
+  move_records?: boolean
+}
+
+export interface StudentTopicDeleteRequest {
+  reason: string
+}
+
+export interface AssignmentChangeDetails {
+  assn_change_id: number
+  student_user_name: string
+  office_user_name: string
+  old_topic_id?: number
+  new_topic_id?: number
+  reason: string
+  time: string
+  overrode_capacity: boolean
+  moved_records: boolean
+}
+
+export interface AssignmentChangesGetResponse {
+  changes: AssignmentChangeDetails[]
+}
+
+export interface WaitlistEntryDetails {
+  student_user_name: string
+  student_name: string
+  position: number
+  join_time: string
+}
+
+export interface TopicWaitlistGetResponse {
+  topic_id: number
+  entries: WaitlistEntryDetails[]
+}
+
+export interface SelectionRoundDetails {
+  sel_round_id: number
+  sel_round_name: string
+  open_time: string
+  close_time: string
+  max_requests: number
+  major_ids: number[]
+  carried_over_from?: number
+}
+
+export interface SelectionRoundsGetResponse {
+  rounds: SelectionRoundDetails[]
+}
+
+export interface SelectionRoundsPostRequest {
+  sel_round_name: string
+  open_time: string
+  close_time: string
+  max_requests: number
+  major_ids?: number[]
+  carry_over_from?: number
+}
+
+export interface SelectionRoundPatchRequest {
+  sel_round_name?: string
+  open_time?: string
+  close_time?: string
+  max_requests?: number
+}
+
+// Attachment models
+export interface AttachmentDetails {
+  attach_id: number
+  file_name: string
+  mime_type: string
+  size: number
+  sha256: string
+  upload_time: string
+}
+
+export interface SubmissionFileDetails {
+  kind: SubmissionFileKind
+  attachment: AttachmentDetails
+}
+
+export interface SubmissionFileRequest {
+  kind: SubmissionFileKind
+  attachment_id: number
+}
+
+// Rubric models
+export interface RubricCriterionDetails {
+  criterion_id: number
+  name: string
+  weight: number
+  max_score: number
+}
+
+export interface RubricDetails {
+  stage_id?: number
+  criteria: RubricCriterionDetails[]
+}
+
+export interface RubricsGetResponse {
+  rubrics: RubricDetails[]
+}
+
+export interface RubricCriterionRequest {
+  name: string
+  weight: number
+  max_score: number
+}
+
+export interface RubricPutRequest {
+  stage_id?: number
+  criteria: RubricCriterionRequest[]
+}
+
+export interface CriterionScoreDetails {
+  criterion_id: number
+  criterion_name: string
+  max_score: number
+  score: number
+}
+
+export interface CriterionScoreRequest {
+  criterion_id: number
+  score: number
+}
+
+// Comment models
+export interface CommentDetails {
+  comment_id: number
+  parent_id?: number
+  author_user_name: string
+  author_name?: string
+  author_role: UserRole
+  content: string
+  time: string
+  attachment?: AttachmentDetails
+}
+
+export interface CommentsGetResponse {
+  comments: CommentDetails[]
+}
+
+export interface CommentsPostRequest {
+  content: string
+  parent_id?: number
+  attachment_id?: number
 }
 
 // Progress Report models
@@ -135,26 +384,82 @@ export interface ProgressReportDetailResponse {
   topic_id: number
   student_user_name: string
   student_name: string
-  prog_report_type: ProgressReportType
+  prog_report_stage_id: number
+  prog_report_stage_name: string
   prog_report_time: string
-  prog_report_attachment: string
+  prog_report_version: number
+  prog_report_files: SubmissionFileDetails[]
+  prog_report_due_time?: string
+  prog_report_late: boolean
   prog_report_outcome: ProgressOutcome
   prog_report_comment?: string
   prog_report_grade?: number
+  prog_report_scores: CriterionScoreDetails[]
 }
 
 export interface ProgressReportsGetResponse {
+  total: number
+  page: number
+  page_size: number
   reports: ProgressReportDetailResponse[]
 }
 
+export interface ProgressReportRevisionDetails {
+  revision_id: number
+  prog_report_id: number
+  reviser_user_name: string
+  reviser_name?: string
+  cause_id?: number
+  reason: string
+  time: string
+  old_outcome: ProgressOutcome
+  old_comment?: string
+  old_grade?: number
+  new_outcome: ProgressOutcome
+  new_comment?: string
+  new_grade?: number
+}
+
+export interface ProgressReportRevisionsGetResponse {
+  revisions: ProgressReportRevisionDetails[]
+}
+
+export interface ProgressReportRevisionsPostRequest {
+  outcome: ProgressOutcome
+  reason: string
+  comment?: string
+  grade?: number
+  scores?: CriterionScoreRequest[]
+  invalidate_dependents?: boolean
+}
+
+export interface ProgressReportsQueryParams extends PaginationParams {
+  major_id?: number
+  teacher_user_name?: string
+  stage_id?: number
+  outcome?: ProgressOutcome
+  pending?: boolean
+  student?: string
+  order?: 'asc' | 'desc'
+}
+
 export interface ProgressReportsPostRequest {
-  attachment: string
+  stage_id?: number
+  files: SubmissionFileRequest[]
+}
+
+export interface ProgressReportsPostResponse {
+  prog_report_id: number
+  prog_report_version: number
+  prog_report_due_time?: string
+  prog_report_late: boolean
 }
 
 export interface ProgressReportRecordPatchRequest {
   outcome: ProgressOutcome
   comment?: string
   grade?: number
+  scores?: CriterionScoreRequest[]
 }
 
 // Final Defense models
@@ -166,18 +471,39 @@ export interface FinalDefenseDetails {
   student_name: string
   def_board_user_name?: string
   final_def_time: string
-  final_def_attachment: string
+  final_def_version: number
+  final_def_files: SubmissionFileDetails[]
+  final_def_due_time?: string
+  final_def_late: boolean
   final_def_outcome?: boolean
   final_def_comment?: string
   final_def_grade?: number
+  final_def_scores: CriterionScoreDetails[]
 }
 
 export interface FinalDefensesGetResponse {
+  total: number
+  page: number
+  page_size: number
   defenses: FinalDefenseDetails[]
 }
 
+export interface FinalDefensesQueryParams extends PaginationParams {
+  outcome?: boolean
+  pending?: boolean
+  student?: string
+  order?: 'asc' | 'desc'
+}
+
 export interface FinalDefensesPostRequest {
-  attachment: string
+  files: SubmissionFileRequest[]
+}
+
+export interface FinalDefensesPostResponse {
+  final_def_id: number
+  final_def_version: number
+  final_def_due_time?: string
+  final_def_late: boolean
 }
 
 export interface FinalDefensesRecordTeacherPatchRequest {
@@ -187,7 +513,176 @@ export interface FinalDefensesRecordTeacherPatchRequest {
 export interface FinalDefensesRecordDefenseBoardPatchRequest {
   outcome: boolean
   comment: string
-  grade: number
+  grade?: number
+  scores?: CriterionScoreRequest[]
+}
+
+// Deadline models
+export interface DeadlineExtensionDetails {
+  student_user_name: string
+  student_name: string
+  due_time: string
+}
+
+export interface DeadlineDetails {
+  deadline_id: number
+  major_id: number
+  stage_id?: number
+  due_time: string
+  is_hard: boolean
+  extensions: DeadlineExtensionDetails[]
+}
+
+export interface DeadlinesGetResponse {
+  deadlines: DeadlineDetails[]
+}
+
+export interface DeadlinesPostRequest {
+  major_id: number
+  stage_id?: number
+  due_time: string
+  is_hard: boolean
+}
+
+export interface DeadlinePatchRequest {
+  due_time?: string
+  is_hard?: boolean
+}
+
+export interface DeadlineExtensionPutRequest {
+  due_time: string
+}
+
+// Revision lock models
+export interface RevisionLockDetails {
+  major_id: number
+  lock_time: string
+}
+
+export interface RevisionLocksGetResponse {
+  locks: RevisionLockDetails[]
+}
+
+export interface RevisionLockPutRequest {
+  lock_time: string
+}
+
+// Similarity check models
+export interface SimilarityQueryParams {
+  stage_id?: number
+  major_id?: number
+  threshold?: number
+}
+
+export interface SimilarityDocumentDetails {
+  student_user_name: string
+  student_name: string
+  submission_id: number
+  attachment: AttachmentDetails
+}
+
+export interface SimilarityPairDetails {
+  first: SimilarityDocumentDetails
+  second: SimilarityDocumentDetails
+  similarity: number
+  passages: string[]
+}
+
+export interface SimilarityReportResponse {
+  stage_id?: number
+  threshold: number
+  checked: number
+  unreadable: SimilarityDocumentDetails[]
+  pairs: SimilarityPairDetails[]
+}
+
+// Matching models
+export interface MatchingRoundDetails {
+  match_round_id: number
+  match_round_name: string
+  max_preferences: number
+  open_time: string
+  close_time: string
+  commit_time?: string
+}
+
+export interface MatchingRoundsGetResponse {
+  rounds: MatchingRoundDetails[]
+}
+
+export interface MatchingRoundsPostRequest {
+  match_round_name: string
+  max_preferences: number
+  open_time: string
+  close_time: string
+}
+
+export interface PreferenceDetails {
+  pref_rank: number
+  topic_id: number
+  topic_name: string
+}
+
+export interface PreferencesGetResponse {
+  preferences: PreferenceDetails[]
+}
+
+export interface PreferencesPutRequest {
+  topic_ids: number[]
+}
+
+export interface ApplicantDetails {
+  student_user_name: string
+  student_name: string
+  pref_rank: number
+  appl_rank?: number
+}
+
+export interface TopicApplicants {
+  topic_id: number
+  topic_name: string
+  topic_max_students: number
+  applicants: ApplicantDetails[]
+}
+
+export interface ApplicantsGetResponse {
+  topics: TopicApplicants[]
+}
+
+export interface RankingsPutRequest {
+  student_user_names: string[]
+}
+
+export interface MatchingEntry {
+  student_user_name: string
+  student_name: string
+  topic_id?: number
+  topic_name?: string
+  pref_rank?: number
+}
+
+export interface MatchingGetResponse {
+  matches: MatchingEntry[]
+}
+
+// Notification models
+export interface NotificationDetails {
+  notif_id: number
+  content: string
+  time: string
+  read_time?: string
+}
+
+export interface NotificationsGetResponse {
+  total: number
+  unread: number
+  page: number
+  page_size: number
+  notifications: NotificationDetails[]
+}
+
+export interface NotificationPatchRequest {
+  read: boolean
 }
 
 // Pagination params (for convenience)
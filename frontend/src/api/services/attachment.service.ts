@@ -0,0 +1,16 @@
+import type { AttachmentDetails } from '../models'
+import type { HttpClient } from '../utils/http-client'
+
+export class AttachmentService {
+  constructor (private http: HttpClient) {}
+
+  async uploadAttachment (file: File): Promise<AttachmentDetails> {
+    const formData = new FormData()
+    formData.append('file', file)
+    return this.http.upload<AttachmentDetails>('/attachments', formData)
+  }
+
+  async downloadAttachment (attachId: number): Promise<Blob> {
+    return this.http.get<Blob>(`/attachments/${attachId}`, { responseType: 'blob' })
+  }
+}
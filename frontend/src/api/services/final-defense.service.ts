@@ -1,7 +1,12 @@
 import type {
+  CommentDetails,
+  CommentsGetResponse,
+  CommentsPostRequest,
   FinalDefenseDetails,
   FinalDefensesGetResponse,
   FinalDefensesPostRequest,
+  FinalDefensesPostResponse,
+  FinalDefensesQueryParams,
   FinalDefensesRecordDefenseBoardPatchRequest,
   FinalDefensesRecordTeacherPatchRequest,
 } from '../models'
@@ -10,12 +15,25 @@ import type { HttpClient } from '../utils/http-client'
 export class FinalDefenseService {
   constructor (private http: HttpClient) {}
 
-  async getFinalDefenses (): Promise<FinalDefensesGetResponse> {
-    return this.http.get<FinalDefensesGetResponse>('/final_defenses')
+  async getFinalDefenses (params?: FinalDefensesQueryParams): Promise<FinalDefensesGetResponse> {
+    return this.http.get<FinalDefensesGetResponse>('/final_defenses', { params })
   }
 
-  async createFinalDefense (data: FinalDefensesPostRequest): Promise<void> {
-    await this.http.post('/final_defenses', data)
+  async getAllFinalDefenses (
+    params?: Omit<FinalDefensesQueryParams, 'page' | 'page_size'>,
+  ): Promise<FinalDefenseDetails[]> {
+    const defenses: FinalDefenseDetails[] = []
+    for (let page = 1; ; page++) {
+      const response = await this.getFinalDefenses({ ...params, page, page_size: 100 })
+      defenses.push(...response.defenses)
+      if (response.defenses.length === 0 || defenses.length >= response.total) {
+        return defenses
+      }
+    }
+  }
+
+  async createFinalDefense (data: FinalDefensesPostRequest): Promise<FinalDefensesPostResponse> {
+    return this.http.post<FinalDefensesPostResponse>('/final_defenses', data)
   }
 
   async updateFinalDefenseAsTeacher (
@@ -31,4 +49,12 @@ export class FinalDefenseService {
   ): Promise<FinalDefenseDetails> {
     return this.http.patch<FinalDefenseDetails>(`/final_defenses/${reportId}`, data)
   }
+
+  async getComments (reportId: number): Promise<CommentsGetResponse> {
+    return this.http.get<CommentsGetResponse>(`/final_defenses/${reportId}/comments`)
+  }
+
+  async createComment (reportId: number, data: CommentsPostRequest): Promise<CommentDetails> {
+    return this.http.post<CommentDetails>(`/final_defenses/${reportId}/comments`, data)
+  }
 }
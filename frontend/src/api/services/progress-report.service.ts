@@ -1,20 +1,42 @@
 import type {
+  CommentDetails,
+  CommentsGetResponse,
+  CommentsPostRequest,
   ProgressReportDetailResponse,
   ProgressReportRecordPatchRequest,
+  ProgressReportRevisionsGetResponse,
+  ProgressReportRevisionsPostRequest,
   ProgressReportsGetResponse,
   ProgressReportsPostRequest,
+  ProgressReportsPostResponse,
+  ProgressReportsQueryParams,
+  SimilarityQueryParams,
+  SimilarityReportResponse,
 } from '../models'
 import type { HttpClient } from '../utils/http-client'
 
 export class ProgressReportService {
   constructor (private http: HttpClient) {}
 
-  async getProgressReports (): Promise<ProgressReportsGetResponse> {
-    return this.http.get<ProgressReportsGetResponse>('/progress_reports')
+  async getProgressReports (params?: ProgressReportsQueryParams): Promise<ProgressReportsGetResponse> {
+    return this.http.get<ProgressReportsGetResponse>('/progress_reports', { params })
   }
 
-  async createProgressReport (data: ProgressReportsPostRequest): Promise<void> {
-    await this.http.post('/progress_reports', data)
+  async getAllProgressReports (
+    params?: Omit<ProgressReportsQueryParams, 'page' | 'page_size'>,
+  ): Promise<ProgressReportDetailResponse[]> {
+    const reports: ProgressReportDetailResponse[] = []
+    for (let page = 1; ; page++) {
+      const response = await this.getProgressReports({ ...params, page, page_size: 100 })
+      reports.push(...response.reports)
+      if (response.reports.length === 0 || reports.length >= response.total) {
+        return reports
+      }
+    }
+  }
+
+  async createProgressReport (data: ProgressReportsPostRequest): Promise<ProgressReportsPostResponse> {
+    return this.http.post<ProgressReportsPostResponse>('/progress_reports', data)
   }
 
   async updateProgressReport (
@@ -23,4 +45,27 @@ export class ProgressReportService {
   ): Promise<ProgressReportDetailResponse> {
     return this.http.patch<ProgressReportDetailResponse>(`/progress_reports/${reportId}`, data)
   }
+
+  async getComments (reportId: number): Promise<CommentsGetResponse> {
+    return this.http.get<CommentsGetResponse>(`/progress_reports/${reportId}/comments`)
+  }
+
+  async createComment (reportId: number, data: CommentsPostRequest): Promise<CommentDetails> {
+    return this.http.post<CommentDetails>(`/progress_reports/${reportId}/comments`, data)
+  }
+
+  async getRevisions (reportId: number): Promise<ProgressReportRevisionsGetResponse> {
+    return this.http.get<ProgressReportRevisionsGetResponse>(`/progress_reports/${reportId}/revisions`)
+  }
+
+  async createRevision (
+    reportId: number,
+    data: ProgressReportRevisionsPostRequest,
+  ): Promise<ProgressReportDetailResponse> {
+    return this.http.post<ProgressReportDetailResponse>(`/progress_reports/${reportId}/revisions`, data)
+  }
+
+  async getSimilarityReport (params?: SimilarityQueryParams): Promise<SimilarityReportResponse> {
+    return this.http.get<SimilarityReportResponse>('/similarity_reports', { params })
+  }
 }
@@ -0,0 +1,14 @@
+import type { RubricDetails, RubricPutRequest, RubricsGetResponse } from '../models'
+import type { HttpClient } from '../utils/http-client'
+
+export class RubricService {
+  constructor (private http: HttpClient) {}
+
+  async getRubrics (): Promise<RubricsGetResponse> {
+    return this.http.get<RubricsGetResponse>('/rubrics')
+  }
+
+  async putRubric (data: RubricPutRequest): Promise<RubricDetails> {
+    return this.http.put<RubricDetails>('/rubrics', data)
+  }
+}
@@ -123,6 +123,16 @@ export const ERROR_MESSAGES = {
     },
   },
 
+  // Similarity check errors
+  similarityReports: {
+    get: {
+      400: '相似度阈值无效',
+      401: '未登录',
+      403: '权限不足',
+      404: '未找到指定阶段',
+    },
+  },
+
   // Default error messages
   default: {
     400: '请求格式错误',
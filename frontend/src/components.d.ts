@@ -12,12 +12,14 @@ export {}
 declare module 'vue' {
   export interface GlobalComponents {
     AppBar: typeof import('./components/AppBar.vue')['default']
+    CommentThread: typeof import('./components/CommentThread.vue')['default']
     DefenseBoardDrawer: typeof import('./components/DefenseBoardDrawer.vue')['default']
     HelloWorld: typeof import('./components/HelloWorld.vue')['default']
     InfoDrawer: typeof import('./components/InfoDrawer.vue')['default']
     OfficeDrawer: typeof import('./components/OfficeDrawer.vue')['default']
     RouterLink: typeof import('vue-router')['RouterLink']
     RouterView: typeof import('vue-router')['RouterView']
+    SimilarityReport: typeof import('./components/SimilarityReport.vue')['default']
     StudentDrawer: typeof import('./components/StudentDrawer.vue')['default']
     TeacherDrawer: typeof import('./components/TeacherDrawer.vue')['default']
     UserInfoBar: typeof import('./components/UserInfoBar.vue')['default']
@@ -20,11 +20,13 @@ declare module 'vue-router/auto-routes' {
   export interface RouteNamedMap {
     '/': RouteRecordInfo<'/', '/', Record<never, never>, Record<never, never>>,
     '/defense-board/scoring': RouteRecordInfo<'/defense-board/scoring', '/defense-board/scoring', Record<never, never>, Record<never, never>>,
+    '/office/similarity': RouteRecordInfo<'/office/similarity', '/office/similarity', Record<never, never>, Record<never, never>>,
     '/office/topics': RouteRecordInfo<'/office/topics', '/office/topics', Record<never, never>, Record<never, never>>,
     '/student/progress': RouteRecordInfo<'/student/progress', '/student/progress', Record<never, never>, Record<never, never>>,
     '/student/select': RouteRecordInfo<'/student/select', '/student/select', Record<never, never>, Record<never, never>>,
     '/teacher/assignments': RouteRecordInfo<'/teacher/assignments', '/teacher/assignments', Record<never, never>, Record<never, never>>,
     '/teacher/progress': RouteRecordInfo<'/teacher/progress', '/teacher/progress', Record<never, never>, Record<never, never>>,
+    '/teacher/similarity': RouteRecordInfo<'/teacher/similarity', '/teacher/similarity', Record<never, never>, Record<never, never>>,
     '/teacher/topics': RouteRecordInfo<'/teacher/topics', '/teacher/topics', Record<never, never>, Record<never, never>>,
   }
 
@@ -47,6 +49,10 @@ declare module 'vue-router/auto-routes' {
       routes: '/defense-board/scoring'
       views: never
     }
+    'src/pages/office/similarity.vue': {
+      routes: '/office/similarity'
+      views: never
+    }
     'src/pages/office/topics.vue': {
       routes: '/office/topics'
       views: never
@@ -67,6 +73,10 @@ declare module 'vue-router/auto-routes' {
       routes: '/teacher/progress'
       views: never
     }
+    'src/pages/teacher/similarity.vue': {
+      routes: '/teacher/similarity'
+      views: never
+    }
     'src/pages/teacher/topics.vue': {
       routes: '/teacher/topics'
       views: never